@@ -52,11 +52,12 @@ final class SdzApiClient {
         query: SdzSpotSearchQuery? = nil,
         includeAuth: Bool = false
     ) async throws -> [SdzSpot] {
-        try await request(
+        let page: SdzPageResponse<SdzSpot> = try await request(
             path: "/sdz/spots",
             queryItems: query?.queryItems,
             includeAuthIfAvailable: includeAuth
         )
+        return page.items
     }
 
     /// Fetches a specific spot by ID.
@@ -124,7 +125,8 @@ final class SdzApiClient {
             throw SdzApiError.authRequired
         }
         do {
-            return try await request(path: "/sdz/mylist", requiresAuth: true)
+            let page: SdzPageResponse<SdzSpot> = try await request(path: "/sdz/mylist", requiresAuth: true)
+            return page.items
         } catch let error as SdzApiError {
             switch error {
             case .statusCode(404), .api(statusCode: 404, error: _):
@@ -322,6 +324,11 @@ struct SdzUploadUrlResponse: Codable {
     let expiresAt: Date
 }
 
+struct SdzPageResponse<Item: Decodable>: Decodable {
+    let items: [Item]
+    let nextPageToken: String?
+}
+
 struct SdzMyListActionRequest: Codable {
     let spotId: String
 }
@@ -21,7 +21,7 @@ impl SdzAddMyListUseCase {
         auth_user: SdzAuthUser,
         input: SdzAddMyListInput,
     ) -> Result<(), SdzApiError> {
-        let existing = spot_repo.find_by_id(&input.sdz_spot_id).await?;
+        let existing = spot_repo.find_active_by_id(&input.sdz_spot_id).await?;
         if existing.is_none() {
             return Err(SdzApiError::NotFound);
         }
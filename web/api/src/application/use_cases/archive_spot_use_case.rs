@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::spot_repository::SdzSpotRepository,
+    domain::models::{SdzSpot, SdzSpotFieldMask},
+    presentation::error::SdzApiError,
+};
+
+/// 管理者によるアーカイブ。検索・詳細からは外れるが、データとマイリストは残す
+pub struct SdzArchiveSpotUseCase;
+
+impl SdzArchiveSpotUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        admin_user_id: &str,
+        spot_id: String,
+    ) -> Result<SdzSpot, SdzApiError> {
+        let existing = repo
+            .find_by_id(&spot_id)
+            .await?
+            .ok_or(SdzApiError::NotFound)?;
+        if existing.is_archived() {
+            return Ok(existing);
+        }
+        repo.update_if_unchanged(
+            existing.archive(admin_user_id),
+            &existing,
+            &SdzSpotFieldMask::archive_state(),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::use_cases::{
+            restore_spot_use_case::SdzRestoreSpotUseCase, spot_repository::SdzSpotQuery,
+        },
+        domain::models::SdzCreateSpotParams,
+        infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository,
+    };
+
+    #[tokio::test]
+    async fn archived_spot_is_hidden_until_restored() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: "spot-1".into(),
+            name: "closed park".into(),
+            description: None,
+            location: None,
+            tags: vec!["park".into()],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap();
+        repo.create(spot).await.unwrap();
+
+        let archived = SdzArchiveSpotUseCase::new()
+            .execute(repo.clone(), "admin-1", "spot-1".into())
+            .await
+            .unwrap();
+        assert!(archived.is_archived());
+        assert_eq!(archived.sdz_deleted_by.as_deref(), Some("admin-1"));
+        assert!(repo.find_active_by_id("spot-1").await.unwrap().is_none());
+        let active = repo
+            .list_recent(&SdzSpotQuery::default(), 10, None)
+            .await
+            .unwrap();
+        assert!(active.is_empty());
+        let archived_query = SdzSpotQuery {
+            archived: true,
+            ..SdzSpotQuery::default()
+        };
+        let archived_list = repo.list_recent(&archived_query, 10, None).await.unwrap();
+        assert_eq!(archived_list.len(), 1);
+
+        let restored = SdzRestoreSpotUseCase::new()
+            .execute(repo.clone(), "spot-1".into())
+            .await
+            .unwrap();
+        assert!(!restored.is_archived());
+        assert!(restored.sdz_deleted_by.is_none());
+        assert!(repo.find_active_by_id("spot-1").await.unwrap().is_some());
+    }
+}
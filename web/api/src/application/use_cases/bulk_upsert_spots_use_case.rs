@@ -0,0 +1,460 @@
+use std::{collections::HashSet, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    application::use_cases::{
+        create_spot_use_case::{CreateSpotInput, SdzCreateSpotUseCase},
+        spot_repository::{SdzSpotRepository, SdzSpotVersionedWrite, SDZ_BATCH_UPSERT_LIMIT},
+        spot_revision_repository::SdzSpotRevisionRepository,
+    },
+    domain::{
+        models::{sdz_now_jst, SdzSpot, SdzSpotApprovalStatus},
+        revision::{sdz_spot_changed_fields, SdzSpotRevision},
+    },
+    presentation::error::SdzApiError,
+};
+
+/// 1リクエストで受け付ける行数（空行を除く）
+pub const SDZ_BULK_MAX_LINES: usize = 5000;
+
+/// NDJSONの1行。`externalId`があれば同じIDのスポットを上書きし、なければ常に新規作成する
+#[derive(Debug, Clone, Deserialize)]
+pub struct SdzBulkSpotRecord {
+    #[serde(rename = "externalId")]
+    pub external_id: Option<String>,
+    #[serde(flatten)]
+    pub input: CreateSpotInput,
+}
+
+/// 行番号（1始まり）と、JSONとして読めたかどうか
+#[derive(Debug, Clone)]
+pub struct SdzBulkSpotLine {
+    pub line: usize,
+    pub record: Result<SdzBulkSpotRecord, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SdzBulkSpotStatus {
+    Created,
+    Updated,
+    Invalid,
+    /// 検証は通ったが保存に失敗した（再送すればよい）
+    Failed,
+}
+
+/// 1行ごとの処理結果。NDJSONで順次返す
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SdzBulkSpotResult {
+    pub line: usize,
+    #[serde(rename = "externalId", skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(rename = "spotId", skip_serializing_if = "Option::is_none")]
+    pub sdz_spot_id: Option<String>,
+    pub status: SdzBulkSpotStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 処理を終えた行の件数（ログ用）。受信側が切断した場合は送れた分まで
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SdzBulkSpotSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub invalid: usize,
+    pub failed: usize,
+}
+
+impl SdzBulkSpotSummary {
+    fn record(&mut self, status: SdzBulkSpotStatus) {
+        match status {
+            SdzBulkSpotStatus::Created => self.created += 1,
+            SdzBulkSpotStatus::Updated => self.updated += 1,
+            SdzBulkSpotStatus::Invalid => self.invalid += 1,
+            SdzBulkSpotStatus::Failed => self.failed += 1,
+        }
+    }
+}
+
+/// 外部IDから常に同じspotIdを作る（パイプラインの再実行で重複登録しないため）
+pub fn sdz_external_spot_id(external_id: &str) -> String {
+    Uuid::new_v5(
+        &Uuid::NAMESPACE_URL,
+        format!("sdz:spot:{}", external_id).as_bytes(),
+    )
+    .to_string()
+}
+
+struct SdzBulkCandidate {
+    line: usize,
+    external_id: Option<String>,
+    spot: SdzSpot,
+    /// 行で承認状態を指定したか。指定がなければ既存スポットの承認状態を引き継ぐ
+    approval_specified: bool,
+}
+
+/// 管理者によるスポットの一括登録（BigQuery → Cloud Functions のパイプライン用）。
+/// 検証は`SdzCreateSpotUseCase`と同じで、`SDZ_BATCH_UPSERT_LIMIT`件ずつアトミックに保存する
+pub struct SdzBulkUpsertSpotsUseCase;
+
+impl SdzBulkUpsertSpotsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// NDJSONを行に分ける。行数超過のみリクエスト全体のエラーにし、壊れた行は行単位で返す
+    pub fn parse(&self, body: &str) -> Result<Vec<SdzBulkSpotLine>, SdzApiError> {
+        let lines: Vec<SdzBulkSpotLine> = body
+            .lines()
+            .enumerate()
+            .filter(|(_, raw)| !raw.trim().is_empty())
+            .map(|(index, raw)| SdzBulkSpotLine {
+                line: index + 1,
+                record: serde_json::from_str(raw).map_err(|e| e.to_string()),
+            })
+            .collect();
+        if lines.is_empty() {
+            return Err(SdzApiError::BadRequest(
+                "body must contain NDJSON records".into(),
+            ));
+        }
+        if lines.len() > SDZ_BULK_MAX_LINES {
+            return Err(SdzApiError::BadRequest(format!(
+                "bulk request must be <= {} lines",
+                SDZ_BULK_MAX_LINES
+            )));
+        }
+        Ok(lines)
+    }
+
+    /// 行ごとの結果を`results`に送る。不正な行はすぐに、保存した行はバッチの確定後に送る。
+    /// 受信側が切断したら以降のバッチは処理しない
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        revision_repo: Arc<dyn SdzSpotRevisionRepository>,
+        admin_user_id: &str,
+        lines: Vec<SdzBulkSpotLine>,
+        results: mpsc::Sender<SdzBulkSpotResult>,
+    ) -> SdzBulkSpotSummary {
+        let mut summary = SdzBulkSpotSummary::default();
+        let create_use_case = SdzCreateSpotUseCase::for_admin();
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        for SdzBulkSpotLine { line, record } in lines {
+            let external_id = record
+                .as_ref()
+                .ok()
+                .and_then(|record| record.external_id.clone());
+            let spot = record.and_then(|record| {
+                let spot_id = match record.external_id.as_deref().map(str::trim) {
+                    Some("") => return Err("externalId must not be empty".to_string()),
+                    Some(external_id) => sdz_external_spot_id(external_id),
+                    None => Uuid::new_v4().to_string(),
+                };
+                if !seen.insert(spot_id.clone()) {
+                    return Err("duplicate externalId in request".to_string());
+                }
+                let mut input = record.input;
+                let approval_specified = input.approval_status.is_some();
+                // 新規作成は管理者作成と同じく、承認状態の指定がなければ承認済みにする
+                input
+                    .approval_status
+                    .get_or_insert(SdzSpotApprovalStatus::Approved);
+                create_use_case
+                    .build_spot(spot_id, admin_user_id.to_string(), input)
+                    .map(|spot| (spot, approval_specified))
+                    .map_err(|e| match e {
+                        SdzApiError::BadRequest(message) => message,
+                        other => other.to_string(),
+                    })
+            });
+            match spot {
+                Ok((spot, approval_specified)) => candidates.push(SdzBulkCandidate {
+                    line,
+                    external_id,
+                    spot,
+                    approval_specified,
+                }),
+                Err(message) => {
+                    let result = SdzBulkSpotResult {
+                        line,
+                        external_id,
+                        sdz_spot_id: None,
+                        status: SdzBulkSpotStatus::Invalid,
+                        message: Some(message),
+                    };
+                    if results.send(result).await.is_err() {
+                        return summary;
+                    }
+                    summary.record(SdzBulkSpotStatus::Invalid);
+                }
+            }
+        }
+
+        let mut candidates = candidates.into_iter().peekable();
+        while candidates.peek().is_some() {
+            let chunk: Vec<_> = candidates.by_ref().take(SDZ_BATCH_UPSERT_LIMIT).collect();
+            for result in self
+                .write_chunk(repo.as_ref(), revision_repo.as_ref(), admin_user_id, chunk)
+                .await
+            {
+                let status = result.status;
+                if results.send(result).await.is_err() {
+                    return summary;
+                }
+                summary.record(status);
+            }
+        }
+        summary
+    }
+
+    /// 既存スポットは登録者・作成日時・アーカイブ状態と、行で指定がなければ承認状態を引き継いで上書きする。
+    /// 内容が変わらない行は書き込まず、updatedとして返す。
+    /// 読み取り後に他から作成・更新された行は上書きせず、failedとして返す（再送すれば読み直して反映される）
+    async fn write_chunk(
+        &self,
+        repo: &dyn SdzSpotRepository,
+        revision_repo: &dyn SdzSpotRevisionRepository,
+        admin_user_id: &str,
+        chunk: Vec<SdzBulkCandidate>,
+    ) -> Vec<SdzBulkSpotResult> {
+        let mut writes = Vec::new();
+        let mut revisions = Vec::new();
+        let mut statuses = Vec::new();
+        for candidate in &chunk {
+            let mut spot = candidate.spot.clone();
+            let existing = match repo.find_versioned(&spot.sdz_spot_id).await {
+                Ok(existing) => existing,
+                Err(err) => return failed(chunk, &err),
+            };
+            let status = match existing {
+                None => {
+                    writes.push(SdzSpotVersionedWrite {
+                        spot,
+                        expected_version: None,
+                    });
+                    SdzBulkSpotStatus::Created
+                }
+                Some((existing, version)) => {
+                    spot.sdz_user_id = existing.sdz_user_id.clone();
+                    spot.created_at = existing.created_at;
+                    spot.sdz_deleted_at = existing.sdz_deleted_at;
+                    spot.sdz_deleted_by = existing.sdz_deleted_by.clone();
+                    spot.updated_at = existing.updated_at;
+                    // パイプラインの再実行で却下済みのスポットを承認し直さない
+                    if !candidate.approval_specified {
+                        spot.sdz_approval_status = existing.sdz_approval_status.clone();
+                    }
+                    if !sdz_spot_changed_fields(&existing, &spot).is_empty() {
+                        spot.updated_at = sdz_now_jst();
+                        revisions.push(SdzSpotRevision::new(&existing, &spot, admin_user_id));
+                        writes.push(SdzSpotVersionedWrite {
+                            spot,
+                            expected_version: Some(version),
+                        });
+                    }
+                    SdzBulkSpotStatus::Updated
+                }
+            };
+            statuses.push(status);
+        }
+
+        let conflicted: HashSet<String> = if writes.is_empty() {
+            HashSet::new()
+        } else {
+            match repo.upsert_many_if_unchanged(writes).await {
+                Ok(conflicted) => conflicted.into_iter().collect(),
+                Err(err) => return failed(chunk, &err),
+            }
+        };
+        if !conflicted.is_empty() {
+            tracing::warn!(
+                conflicted = conflicted.len(),
+                "bulk spot rows were modified after they were read"
+            );
+        }
+        // 版の記録に失敗しても保存自体は確定しているため、ログに残して続行する
+        for revision in revisions {
+            let spot_id = revision.sdz_spot_id.clone();
+            if conflicted.contains(&spot_id) {
+                continue;
+            }
+            if let Err(err) = revision_repo.append(revision).await {
+                tracing::error!(
+                    spot_id = %spot_id,
+                    error = ?err,
+                    "failed to record spot revision"
+                );
+            }
+        }
+        chunk
+            .into_iter()
+            .zip(statuses)
+            .map(|(candidate, status)| {
+                let conflict = conflicted.contains(&candidate.spot.sdz_spot_id);
+                SdzBulkSpotResult {
+                    line: candidate.line,
+                    external_id: candidate.external_id,
+                    sdz_spot_id: Some(candidate.spot.sdz_spot_id),
+                    status: if conflict {
+                        SdzBulkSpotStatus::Failed
+                    } else {
+                        status
+                    },
+                    message: conflict.then(|| "spot was modified by another request".to_string()),
+                }
+            })
+            .collect()
+    }
+}
+
+fn failed(chunk: Vec<SdzBulkCandidate>, err: &SdzApiError) -> Vec<SdzBulkSpotResult> {
+    tracing::error!(error = ?err, lines = chunk.len(), "bulk spot write failed");
+    chunk
+        .into_iter()
+        .map(|candidate| SdzBulkSpotResult {
+            line: candidate.line,
+            external_id: candidate.external_id,
+            sdz_spot_id: Some(candidate.spot.sdz_spot_id),
+            status: SdzBulkSpotStatus::Failed,
+            message: Some(err.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{
+        in_memory_spot_repository::SdzInMemorySpotRepository,
+        in_memory_spot_revision_repository::SdzInMemorySpotRevisionRepository,
+    };
+
+    async fn run(
+        repo: Arc<dyn SdzSpotRepository>,
+        revision_repo: Arc<dyn SdzSpotRevisionRepository>,
+        body: &str,
+    ) -> Vec<SdzBulkSpotResult> {
+        let use_case = SdzBulkUpsertSpotsUseCase::new();
+        let lines = use_case.parse(body).unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        let summary = use_case
+            .execute(repo, revision_repo, "admin-1", lines, tx)
+            .await;
+        let mut results = Vec::new();
+        while let Some(result) = rx.recv().await {
+            results.push(result);
+        }
+        assert_eq!(
+            summary.created + summary.updated + summary.invalid + summary.failed,
+            results.len()
+        );
+        results
+    }
+
+    #[tokio::test]
+    async fn upserts_by_external_id_and_reports_each_line() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let revision_repo: Arc<dyn SdzSpotRevisionRepository> =
+            Arc::new(SdzInMemorySpotRevisionRepository::default());
+        let body = r#"{"externalId":"bq-1","name":"Park","tags":["パーク"]}
+{"externalId":"bq-2","name":"  "}
+
+not json
+{"name":"No external id","location":{"lat":35.0,"lng":139.0}}
+{"externalId":"bq-1","name":"Dup"}
+"#;
+        let results = run(repo.clone(), revision_repo.clone(), body).await;
+        let summary: Vec<_> = results
+            .iter()
+            .map(|r| (r.line, r.status, r.message.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, SdzBulkSpotStatus::Invalid, true),
+                (4, SdzBulkSpotStatus::Invalid, true),
+                (6, SdzBulkSpotStatus::Invalid, true),
+                (1, SdzBulkSpotStatus::Created, false),
+                (5, SdzBulkSpotStatus::Created, false),
+            ]
+        );
+        assert_eq!(results[0].message.as_deref(), Some("name is required"));
+        let spot_id = sdz_external_spot_id("bq-1");
+        assert_eq!(results[3].sdz_spot_id.as_deref(), Some(spot_id.as_str()));
+        let created = repo.find_by_id(&spot_id).await.unwrap().unwrap();
+        assert!(created.is_approved());
+        assert_eq!(created.sdz_user_id, "admin-1");
+
+        let results = run(
+            repo.clone(),
+            revision_repo.clone(),
+            "{\"externalId\":\"bq-1\",\"name\":\"Renamed\",\"tags\":[\"パーク\"]}\n",
+        )
+        .await;
+        assert_eq!(results[0].status, SdzBulkSpotStatus::Updated);
+        let updated = repo.find_by_id(&spot_id).await.unwrap().unwrap();
+        assert_eq!(updated.name, "Renamed");
+        assert_eq!(updated.created_at, created.created_at);
+        let revisions = revision_repo
+            .list_by_spot(&spot_id, usize::MAX, None)
+            .await
+            .unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].changed_fields, vec!["name"]);
+    }
+
+    #[tokio::test]
+    async fn reingest_keeps_rejected_status_unless_specified() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let revision_repo: Arc<dyn SdzSpotRevisionRepository> =
+            Arc::new(SdzInMemorySpotRevisionRepository::default());
+        let line = "{\"externalId\":\"bq-1\",\"name\":\"Park\",\"tags\":[\"パーク\"]}\n";
+        run(repo.clone(), revision_repo.clone(), line).await;
+        let spot_id = sdz_external_spot_id("bq-1");
+        let mut rejected = repo.find_by_id(&spot_id).await.unwrap().unwrap();
+        rejected.sdz_approval_status = Some(SdzSpotApprovalStatus::Rejected);
+        repo.upsert_many(vec![rejected]).await.unwrap();
+
+        let results = run(repo.clone(), revision_repo.clone(), line).await;
+        assert_eq!(results[0].status, SdzBulkSpotStatus::Updated);
+        let spot = repo.find_by_id(&spot_id).await.unwrap().unwrap();
+        assert_eq!(
+            spot.sdz_approval_status,
+            Some(SdzSpotApprovalStatus::Rejected)
+        );
+        assert!(revision_repo
+            .list_by_spot(&spot_id, usize::MAX, None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        run(
+            repo.clone(),
+            revision_repo,
+            "{\"externalId\":\"bq-1\",\"name\":\"Park\",\"tags\":[\"パーク\"],\"approvalStatus\":\"approved\"}\n",
+        )
+        .await;
+        assert!(repo
+            .find_by_id(&spot_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_approved());
+    }
+
+    #[test]
+    fn parse_rejects_empty_and_oversized_bodies() {
+        let use_case = SdzBulkUpsertSpotsUseCase::new();
+        assert!(use_case.parse("\n\n").is_err());
+        let body = "{\"name\":\"a\"}\n".repeat(SDZ_BULK_MAX_LINES + 1);
+        assert!(matches!(
+            use_case.parse(&body),
+            Err(SdzApiError::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::Serialize;
+
+use crate::{
+    application::use_cases::spot_repository::{SdzSpotQuery, SdzSpotRepository},
+    domain::{
+        geo::sdz_encode_geohash,
+        models::{SdzSpotBounds, SdzSpotLocation},
+    },
+    presentation::error::SdzApiError,
+};
+
+/// 地図のズームレベルの上限
+pub const SDZ_CLUSTER_MAX_ZOOM: u8 = 22;
+
+/// geohashセル単位のクラスタ
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzSpotCluster {
+    pub geohash: String,
+    pub count: usize,
+    pub centroid: SdzSpotLocation,
+    #[serde(rename = "parkCount")]
+    pub park_count: usize,
+    #[serde(rename = "streetCount")]
+    pub street_count: usize,
+    /// 1件だけのクラスタはそのスポットIDを返し、ピンとして描画できるようにする
+    #[serde(rename = "spotId", skip_serializing_if = "Option::is_none")]
+    pub sdz_spot_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzSpotClusters {
+    pub precision: usize,
+    pub clusters: Vec<SdzSpotCluster>,
+    /// リポジトリの走査上限に達し、一部のスポットが集計されていない可能性がある
+    pub truncated: bool,
+}
+
+pub struct SdzClusterSpotsUseCase;
+
+impl SdzClusterSpotsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        bounds: SdzSpotBounds,
+        zoom: u8,
+    ) -> Result<SdzSpotClusters, SdzApiError> {
+        let precision = sdz_cluster_precision(zoom);
+        let query = SdzSpotQuery {
+            approved_only: true,
+            ..Default::default()
+        };
+        // 件数では打ち切らず、範囲内を取り切れたかはリポジトリの走査結果で判断する
+        let found = repo
+            .list_in_bounds(&bounds, &query, usize::MAX, None)
+            .await?;
+        let (spots, truncated) = (found.spots, found.truncated);
+
+        let mut cells: BTreeMap<String, ClusterAccumulator> = BTreeMap::new();
+        for spot in spots.iter().filter(|spot| spot.is_approved()) {
+            let Some(location) = spot.location.as_ref() else {
+                continue;
+            };
+            let cell = cells
+                .entry(sdz_encode_geohash(location.lat, location.lng, precision))
+                .or_default();
+            cell.count += 1;
+            cell.lat_sum += location.lat;
+            cell.lng_sum += location.lng;
+            if spot.is_park() {
+                cell.park_count += 1;
+            }
+            if spot.is_street() {
+                cell.street_count += 1;
+            }
+            cell.sdz_spot_id = Some(spot.sdz_spot_id.clone());
+        }
+
+        let clusters = cells
+            .into_iter()
+            .map(|(geohash, cell)| SdzSpotCluster {
+                geohash,
+                count: cell.count,
+                centroid: SdzSpotLocation {
+                    lat: cell.lat_sum / cell.count as f64,
+                    lng: cell.lng_sum / cell.count as f64,
+                },
+                park_count: cell.park_count,
+                street_count: cell.street_count,
+                sdz_spot_id: cell.sdz_spot_id.filter(|_| cell.count == 1),
+            })
+            .collect();
+        Ok(SdzSpotClusters {
+            precision,
+            clusters,
+            truncated,
+        })
+    }
+}
+
+/// ズームレベルからクラスタのgeohash桁数を決める（1セルが画面上で数十px程度になる目安）
+pub fn sdz_cluster_precision(zoom: u8) -> usize {
+    match zoom.min(SDZ_CLUSTER_MAX_ZOOM) {
+        0..=2 => 1,
+        3..=4 => 2,
+        5..=7 => 3,
+        8..=9 => 4,
+        10..=12 => 5,
+        13..=14 => 6,
+        15..=17 => 7,
+        _ => 8,
+    }
+}
+
+#[derive(Default)]
+struct ClusterAccumulator {
+    count: usize,
+    lat_sum: f64,
+    lng_sum: f64,
+    park_count: usize,
+    street_count: usize,
+    sdz_spot_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::models::{SdzCreateSpotParams, SdzSpot, SdzSpotApprovalStatus},
+        infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository,
+    };
+
+    fn sample_spot(id: &str, lat: f64, lng: f64, tags: &[&str]) -> SdzSpot {
+        let mut spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            location: Some(SdzSpotLocation { lat, lng }),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user".into(),
+        })
+        .unwrap();
+        spot.sdz_approval_status = Some(SdzSpotApprovalStatus::Approved);
+        spot
+    }
+
+    #[tokio::test]
+    async fn clusters_group_spots_by_cell() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        repo.create(sample_spot("shibuya-1", 35.658, 139.701, &["パーク"]))
+            .await
+            .unwrap();
+        repo.create(sample_spot("shibuya-2", 35.659, 139.702, &["ストリート"]))
+            .await
+            .unwrap();
+        repo.create(sample_spot("osaka", 34.702, 135.495, &["ストリート"]))
+            .await
+            .unwrap();
+        let mut pending = sample_spot("pending", 35.658, 139.700, &[]);
+        pending.sdz_approval_status = Some(SdzSpotApprovalStatus::Pending);
+        repo.create(pending).await.unwrap();
+
+        let bounds = SdzSpotBounds::parse("33,134,36,140").unwrap();
+        let result = SdzClusterSpotsUseCase::new()
+            .execute(repo, bounds, 6)
+            .await
+            .unwrap();
+        assert_eq!(result.precision, 3);
+        assert!(!result.truncated);
+        assert_eq!(result.clusters.len(), 2);
+
+        let tokyo = result
+            .clusters
+            .iter()
+            .find(|cluster| cluster.count == 2)
+            .unwrap();
+        assert_eq!(tokyo.park_count, 1);
+        assert_eq!(tokyo.street_count, 1);
+        assert!(tokyo.sdz_spot_id.is_none());
+        assert!((tokyo.centroid.lat - 35.6585).abs() < 1e-9);
+
+        let osaka = result
+            .clusters
+            .iter()
+            .find(|cluster| cluster.count == 1)
+            .unwrap();
+        assert_eq!(osaka.sdz_spot_id.as_deref(), Some("osaka"));
+    }
+
+    #[test]
+    fn precision_grows_with_zoom() {
+        assert_eq!(sdz_cluster_precision(0), 1);
+        assert_eq!(sdz_cluster_precision(11), 5);
+        assert_eq!(sdz_cluster_precision(30), 8);
+    }
+}
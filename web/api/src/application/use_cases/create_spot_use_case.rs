@@ -7,16 +7,28 @@ use crate::{
     domain::models::{
         SdzCreateSpotParams, SdzSpot, SdzSpotApprovalStatus, SdzSpotLocation,
         SdzSpotParkAttributes, SdzSpotValidationError, SdzStreetAttributes,
+        SDZ_MAX_IMAGE_SPOTS_PER_USER,
     },
     presentation::error::SdzApiError,
     presentation::middleware::auth::SdzAuthUser,
 };
 
-pub struct SdzCreateSpotUseCase;
+pub struct SdzCreateSpotUseCase {
+    enforce_image_quota: bool,
+}
 
 impl SdzCreateSpotUseCase {
     pub fn new() -> Self {
-        Self
+        Self {
+            enforce_image_quota: true,
+        }
+    }
+
+    /// 管理者による登録。画像付きスポットの件数制限を適用しない
+    pub fn for_admin() -> Self {
+        Self {
+            enforce_image_quota: false,
+        }
     }
 
     pub async fn execute(
@@ -25,8 +37,25 @@ impl SdzCreateSpotUseCase {
         auth_user: SdzAuthUser,
         input: CreateSpotInput,
     ) -> Result<SdzSpot, SdzApiError> {
-        let spot = SdzSpot::new_with_id(SdzCreateSpotParams {
-            sdz_spot_id: Uuid::new_v4().to_string(),
+        let spot = self.build_spot(Uuid::new_v4().to_string(), auth_user.sdz_user_id, input)?;
+
+        if self.enforce_image_quota && !spot.images.is_empty() {
+            sdz_ensure_image_spot_quota(repo.as_ref(), &spot.sdz_user_id).await?;
+        }
+        repo.create(spot.clone()).await?;
+
+        Ok(spot)
+    }
+
+    /// 入力を検証してスポットを組み立てる（保存はしない）。一括登録でも同じ検証を使う
+    pub fn build_spot(
+        &self,
+        spot_id: String,
+        user_id: String,
+        input: CreateSpotInput,
+    ) -> Result<SdzSpot, SdzApiError> {
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: spot_id,
             name: input.name,
             description: input.description,
             location: input.location.map(|loc| SdzSpotLocation {
@@ -48,13 +77,9 @@ impl SdzCreateSpotUseCase {
             sdz_google_rating: input.google_rating,
             sdz_google_rating_count: input.google_rating_count,
             sdz_google_types: input.google_types.unwrap_or_default(),
-            sdz_user_id: auth_user.sdz_user_id,
+            sdz_user_id: user_id,
         })
-        .map_err(map_validation_error)?;
-
-        repo.create(spot.clone()).await?;
-
-        Ok(spot)
+        .map_err(map_validation_error)
     }
 }
 
@@ -98,6 +123,21 @@ pub struct CreateSpotLocation {
     pub lng: f64,
 }
 
+/// 画像付きスポットをもう1件登録できるか確認する
+pub async fn sdz_ensure_image_spot_quota(
+    repo: &dyn SdzSpotRepository,
+    user_id: &str,
+) -> Result<(), SdzApiError> {
+    let count = repo.count_image_spots_by_user(user_id).await?;
+    if count >= SDZ_MAX_IMAGE_SPOTS_PER_USER {
+        return Err(SdzApiError::QuotaExceeded(format!(
+            "image spots per user must be <= {}",
+            SDZ_MAX_IMAGE_SPOTS_PER_USER
+        )));
+    }
+    Ok(())
+}
+
 fn map_validation_error(err: SdzSpotValidationError) -> SdzApiError {
     SdzApiError::BadRequest(err.to_string())
 }
@@ -105,7 +145,10 @@ fn map_validation_error(err: SdzSpotValidationError) -> SdzApiError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository;
+    use crate::{
+        domain::models::SdzSpotFieldMask,
+        infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository,
+    };
 
     fn build_input() -> CreateSpotInput {
         CreateSpotInput {
@@ -156,6 +199,55 @@ mod tests {
             .is_some());
     }
 
+    #[tokio::test]
+    async fn create_spot_enforces_image_quota() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        let auth = || SdzAuthUser {
+            sdz_user_id: "user-1".into(),
+        };
+        let with_image = || {
+            let mut input = build_input();
+            input.images = Some(vec!["https://example.com/a.jpg".into()]);
+            input
+        };
+        let use_case = SdzCreateSpotUseCase::new();
+        let mut created = Vec::new();
+        for _ in 0..SDZ_MAX_IMAGE_SPOTS_PER_USER {
+            created.push(
+                use_case
+                    .execute(repo.clone(), auth(), with_image())
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let err = use_case
+            .execute(repo.clone(), auth(), with_image())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdzApiError::QuotaExceeded(_)));
+        // アーカイブしたスポットは上限に数えない
+        repo.update(
+            created[0].archive("user-1"),
+            &SdzSpotFieldMask::archive_state(),
+        )
+        .await
+        .unwrap();
+        use_case
+            .execute(repo.clone(), auth(), with_image())
+            .await
+            .unwrap();
+        // 画像なしのスポットや管理者登録は制限しない
+        use_case
+            .execute(repo.clone(), auth(), build_input())
+            .await
+            .unwrap();
+        SdzCreateSpotUseCase::for_admin()
+            .execute(repo, auth(), with_image())
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn create_spot_invalid_lat() {
         let repo = Arc::new(SdzInMemorySpotRepository::default());
@@ -0,0 +1,364 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    application::use_cases::{
+        generate_upload_url_use_case::sdz_spot_image_prefix,
+        mylist_repository::SdzMyListRepository, spot_repository::SdzSpotRepository,
+        spot_revision_repository::SdzSpotRevisionRepository,
+        storage_repository::SdzStorageRepository,
+    },
+    presentation::{error::SdzApiError, middleware::auth::SdzAuthUser},
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzDeleteSpotResult {
+    #[serde(rename = "spotId")]
+    pub sdz_spot_id: String,
+    #[serde(rename = "removedMylistEntries")]
+    pub removed_mylist_entries: usize,
+    #[serde(rename = "deletedImages")]
+    pub deleted_images: usize,
+    #[serde(rename = "deletedRevisions")]
+    pub deleted_revisions: usize,
+}
+
+pub struct SdzDeleteSpotUseCase {
+    require_owner: bool,
+}
+
+impl SdzDeleteSpotUseCase {
+    pub fn new() -> Self {
+        Self {
+            require_owner: true,
+        }
+    }
+
+    /// 管理者による削除。投稿者本人でなくても削除できる
+    pub fn for_admin() -> Self {
+        Self {
+            require_owner: false,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        spot_repo: Arc<dyn SdzSpotRepository>,
+        mylist_repo: Arc<dyn SdzMyListRepository>,
+        storage_repo: Arc<dyn SdzStorageRepository>,
+        revision_repo: Arc<dyn SdzSpotRevisionRepository>,
+        auth_user: SdzAuthUser,
+        spot_id: String,
+    ) -> Result<SdzDeleteSpotResult, SdzApiError> {
+        // アーカイブ済みの完全削除は管理者のみ
+        let spot = if self.require_owner {
+            spot_repo.find_active_by_id(&spot_id).await?
+        } else {
+            spot_repo.find_by_id(&spot_id).await?
+        }
+        .ok_or(SdzApiError::NotFound)?;
+        if self.require_owner && spot.sdz_user_id != auth_user.sdz_user_id {
+            return Err(SdzApiError::Forbidden(
+                "only the owner can delete this spot".to_string(),
+            ));
+        }
+
+        spot_repo.delete(&spot_id).await?;
+
+        // スポット本体の削除後は後片付けのみ。失敗しても削除自体は成功として扱い、ログに残す
+        let removed_mylist_entries = match mylist_repo.remove_spot_from_all(&spot_id).await {
+            Ok(count) => count,
+            Err(err) => {
+                tracing::warn!(
+                    spot_id = %spot_id,
+                    error = ?err,
+                    "failed to remove deleted spot from mylists"
+                );
+                0
+            }
+        };
+
+        // 同じspotIdで再登録されたスポット（externalId由来のIDなど）が古い履歴を引き継がないよう、版も消す
+        let deleted_revisions = match revision_repo.delete_by_spot(&spot_id).await {
+            Ok(count) => count,
+            Err(err) => {
+                tracing::warn!(
+                    spot_id = %spot_id,
+                    error = ?err,
+                    "failed to delete revisions of deleted spot"
+                );
+                0
+            }
+        };
+
+        // imagesは投稿者が任意のURLを指定できるため、投稿者本人のアップロード先にあり、
+        // 他のスポットから参照されていない画像だけを削除する
+        let owner_prefix = sdz_spot_image_prefix(&spot.sdz_user_id);
+        let mut deleted_images = 0;
+        for image_url in &spot.images {
+            let Some(object_name) = storage_repo.object_name_from_url(image_url) else {
+                continue;
+            };
+            if !object_name.starts_with(&owner_prefix) {
+                tracing::warn!(
+                    spot_id = %spot_id,
+                    image_url = %image_url,
+                    "skip deleting image uploaded by another user"
+                );
+                continue;
+            }
+            let result = match spot_repo.has_spot_with_image(image_url).await {
+                Ok(true) => continue,
+                Ok(false) => storage_repo.delete_object(&object_name).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(()) => deleted_images += 1,
+                Err(err) => tracing::warn!(
+                    spot_id = %spot_id,
+                    image_url = %image_url,
+                    error = ?err,
+                    "failed to delete spot image"
+                ),
+            }
+        }
+
+        Ok(SdzDeleteSpotResult {
+            sdz_spot_id: spot_id,
+            removed_mylist_entries,
+            deleted_images,
+            deleted_revisions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use crate::{
+        application::use_cases::storage_repository::{SdzUploadUrlRequest, SdzUploadUrlResult},
+        domain::{
+            models::{SdzCreateSpotParams, SdzSpot},
+            revision::SdzSpotRevision,
+        },
+        infrastructure::{
+            in_memory_mylist_repository::SdzInMemoryMyListRepository,
+            in_memory_spot_repository::SdzInMemorySpotRepository,
+            in_memory_spot_revision_repository::SdzInMemorySpotRevisionRepository,
+        },
+    };
+
+    #[derive(Default)]
+    struct RecordingStorage {
+        deleted: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SdzStorageRepository for RecordingStorage {
+        async fn create_upload_url(
+            &self,
+            _request: SdzUploadUrlRequest,
+        ) -> Result<SdzUploadUrlResult, SdzApiError> {
+            Err(SdzApiError::Internal)
+        }
+
+        fn object_name_from_url(&self, object_url: &str) -> Option<String> {
+            object_url
+                .strip_prefix("https://storage.googleapis.com/sdz/")
+                .map(str::to_string)
+        }
+
+        async fn delete_object(&self, object_name: &str) -> Result<(), SdzApiError> {
+            self.deleted.lock().await.push(object_name.to_string());
+            Ok(())
+        }
+    }
+
+    async fn seed_spot(repo: &Arc<dyn SdzSpotRepository>) -> SdzSpot {
+        seed_spot_with_images(
+            repo,
+            "spot-1",
+            vec!["https://storage.googleapis.com/sdz/spots/user-1/a.jpg".into()],
+        )
+        .await
+    }
+
+    async fn seed_spot_with_images(
+        repo: &Arc<dyn SdzSpotRepository>,
+        spot_id: &str,
+        images: Vec<String>,
+    ) -> SdzSpot {
+        let spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: spot_id.into(),
+            name: "test park".into(),
+            description: None,
+            location: None,
+            tags: vec!["park".into()],
+            images,
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap();
+        repo.create(spot).await.unwrap()
+    }
+
+    fn auth(user_id: &str) -> SdzAuthUser {
+        SdzAuthUser {
+            sdz_user_id: user_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_spot_cascades_to_mylists_and_images() {
+        let spot_repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let mylist_repo = Arc::new(SdzInMemoryMyListRepository::default());
+        let storage = Arc::new(RecordingStorage::default());
+        let revision_repo: Arc<dyn SdzSpotRevisionRepository> =
+            Arc::new(SdzInMemorySpotRevisionRepository::default());
+        let spot = seed_spot(&spot_repo).await;
+        let mut renamed = spot.clone();
+        renamed.name = "renamed park".into();
+        revision_repo
+            .append(SdzSpotRevision::new(&spot, &renamed, "user-1"))
+            .await
+            .unwrap();
+        mylist_repo.add("user-1", "spot-1").await.unwrap();
+        mylist_repo.add("user-2", "spot-1").await.unwrap();
+        mylist_repo.add("user-2", "spot-2").await.unwrap();
+
+        let result = SdzDeleteSpotUseCase::new()
+            .execute(
+                spot_repo.clone(),
+                mylist_repo.clone(),
+                storage.clone(),
+                revision_repo.clone(),
+                auth("user-1"),
+                "spot-1".into(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.removed_mylist_entries, 2);
+        assert_eq!(result.deleted_images, 1);
+        assert_eq!(result.deleted_revisions, 1);
+        assert!(revision_repo
+            .list_by_spot("spot-1", usize::MAX, None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(spot_repo.find_by_id("spot-1").await.unwrap().is_none());
+        assert!(mylist_repo.list_by_user("user-1").await.unwrap().is_empty());
+        let remaining = mylist_repo.list_by_user("user-2").await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].sdz_spot_id, "spot-2");
+        assert_eq!(
+            *storage.deleted.lock().await,
+            vec!["spots/user-1/a.jpg".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_spot_keeps_foreign_and_shared_images() {
+        let spot_repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let mylist_repo = Arc::new(SdzInMemoryMyListRepository::default());
+        let storage = Arc::new(RecordingStorage::default());
+        let revision_repo: Arc<dyn SdzSpotRevisionRepository> =
+            Arc::new(SdzInMemorySpotRevisionRepository::default());
+        let shared = "https://storage.googleapis.com/sdz/spots/user-1/shared.jpg".to_string();
+        seed_spot_with_images(
+            &spot_repo,
+            "spot-1",
+            vec![
+                "https://storage.googleapis.com/sdz/spots/user-1/own.jpg".into(),
+                "https://storage.googleapis.com/sdz/spots/user-2/victim.jpg".into(),
+                shared.clone(),
+            ],
+        )
+        .await;
+        seed_spot_with_images(&spot_repo, "spot-2", vec![shared]).await;
+
+        let result = SdzDeleteSpotUseCase::new()
+            .execute(
+                spot_repo,
+                mylist_repo,
+                storage.clone(),
+                revision_repo.clone(),
+                auth("user-1"),
+                "spot-1".into(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.deleted_images, 1);
+        assert_eq!(
+            *storage.deleted.lock().await,
+            vec!["spots/user-1/own.jpg".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_spot_requires_owner_unless_admin() {
+        let spot_repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let mylist_repo: Arc<dyn SdzMyListRepository> =
+            Arc::new(SdzInMemoryMyListRepository::default());
+        let storage: Arc<dyn SdzStorageRepository> = Arc::new(RecordingStorage::default());
+        let revision_repo: Arc<dyn SdzSpotRevisionRepository> =
+            Arc::new(SdzInMemorySpotRevisionRepository::default());
+        seed_spot(&spot_repo).await;
+
+        let err = SdzDeleteSpotUseCase::new()
+            .execute(
+                spot_repo.clone(),
+                mylist_repo.clone(),
+                storage.clone(),
+                revision_repo.clone(),
+                auth("user-2"),
+                "spot-1".into(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdzApiError::Forbidden(_)));
+        assert!(spot_repo.find_by_id("spot-1").await.unwrap().is_some());
+
+        SdzDeleteSpotUseCase::for_admin()
+            .execute(
+                spot_repo.clone(),
+                mylist_repo.clone(),
+                storage.clone(),
+                revision_repo.clone(),
+                auth("admin-1"),
+                "spot-1".into(),
+            )
+            .await
+            .unwrap();
+
+        let err = SdzDeleteSpotUseCase::for_admin()
+            .execute(
+                spot_repo,
+                mylist_repo,
+                storage,
+                revision_repo,
+                auth("admin-1"),
+                "spot-1".into(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdzApiError::NotFound));
+    }
+}
@@ -0,0 +1,270 @@
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::{
+        mylist_repository::SdzMyListRepository, pagination::sdz_cmp_newest_first,
+        spot_repository::SdzSpotRepository,
+    },
+    domain::models::{SdzSpot, SdzSpotLocation},
+    presentation::{error::SdzApiError, middleware::auth::SdzAuthUser},
+};
+
+const SDZ_MYLIST_EXPORT_TITLE: &str = "SpotDiggz マイリスト";
+
+/// マイリストの書き出し形式（Google Earth向けのKMLとGPSアプリ向けのGPX）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdzMyListExportFormat {
+    Kml,
+    Gpx,
+}
+
+impl SdzMyListExportFormat {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "kml" => Some(Self::Kml),
+            "gpx" => Some(Self::Gpx),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Kml => "application/vnd.google-earth.kml+xml",
+            Self::Gpx => "application/gpx+xml",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Kml => "sdz-mylist.kml",
+            Self::Gpx => "sdz-mylist.gpx",
+        }
+    }
+}
+
+/// マイリストの全スポットをKML/GPXにする。位置情報のないスポットは地点にできないため含めない
+pub struct SdzExportMyListUseCase;
+
+impl SdzExportMyListUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `web_base_url`はスポット詳細ページへのリンク（`{web_base_url}/spots/{spotId}`）に使う
+    pub async fn execute(
+        &self,
+        mylist_repo: Arc<dyn SdzMyListRepository>,
+        spot_repo: Arc<dyn SdzSpotRepository>,
+        auth_user: SdzAuthUser,
+        format: SdzMyListExportFormat,
+        web_base_url: &str,
+    ) -> Result<String, SdzApiError> {
+        // 一覧APIのページ送りを繰り返すとページごとにマイリスト全件を読み直すため、一度だけ読んで並べる
+        let mut entries = mylist_repo.list_by_user(&auth_user.sdz_user_id).await?;
+        entries.sort_by(|a, b| {
+            sdz_cmp_newest_first(
+                (&a.created_at, &a.sdz_spot_id),
+                (&b.created_at, &b.sdz_spot_id),
+            )
+        });
+        let mut spots = Vec::new();
+        for entry in entries {
+            // 削除・アーカイブ済みスポットはスキップする
+            if let Some(spot) = spot_repo.find_active_by_id(&entry.sdz_spot_id).await? {
+                spots.push(spot);
+            }
+        }
+
+        let waypoints: Vec<SdzWaypoint> = spots
+            .iter()
+            .filter_map(|spot| SdzWaypoint::from_spot(spot, web_base_url))
+            .collect();
+        Ok(match format {
+            SdzMyListExportFormat::Kml => render_kml(&waypoints),
+            SdzMyListExportFormat::Gpx => render_gpx(&waypoints),
+        })
+    }
+}
+
+struct SdzWaypoint<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    location: &'a SdzSpotLocation,
+    link: String,
+}
+
+impl<'a> SdzWaypoint<'a> {
+    fn from_spot(spot: &'a SdzSpot, web_base_url: &str) -> Option<Self> {
+        Some(Self {
+            name: &spot.name,
+            description: spot.description.as_deref(),
+            location: spot.location.as_ref()?,
+            link: format!(
+                "{}/spots/{}",
+                web_base_url.trim_end_matches('/'),
+                spot.sdz_spot_id
+            ),
+        })
+    }
+}
+
+fn render_kml(waypoints: &[SdzWaypoint]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <kml xmlns=\"http://www.opengis.net/kml/2.2\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n\
+         <Document>\n",
+    );
+    xml.push_str(&format!(
+        "<name>{}</name>\n",
+        sdz_xml_escape(SDZ_MYLIST_EXPORT_TITLE)
+    ));
+    for waypoint in waypoints {
+        // Google Earthはatom:linkを表示しないため、説明文の末尾にもリンクを入れる
+        let description = match waypoint.description {
+            Some(description) => format!("{}\n{}", description, waypoint.link),
+            None => waypoint.link.clone(),
+        };
+        xml.push_str(&format!(
+            "<Placemark>\n<name>{}</name>\n<atom:link href=\"{}\"/>\n<description>{}</description>\n\
+             <Point><coordinates>{},{}</coordinates></Point>\n</Placemark>\n",
+            sdz_xml_escape(waypoint.name),
+            sdz_xml_escape(&waypoint.link),
+            sdz_xml_escape(&description),
+            waypoint.location.lng,
+            waypoint.location.lat
+        ));
+    }
+    xml.push_str("</Document>\n</kml>\n");
+    xml
+}
+
+fn render_gpx(waypoints: &[SdzWaypoint]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <gpx version=\"1.1\" creator=\"spot-diggz\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+    );
+    xml.push_str(&format!(
+        "<metadata><name>{}</name></metadata>\n",
+        sdz_xml_escape(SDZ_MYLIST_EXPORT_TITLE)
+    ));
+    for waypoint in waypoints {
+        xml.push_str(&format!(
+            "<wpt lat=\"{}\" lon=\"{}\">\n<name>{}</name>\n",
+            waypoint.location.lat,
+            waypoint.location.lng,
+            sdz_xml_escape(waypoint.name)
+        ));
+        if let Some(description) = waypoint.description {
+            xml.push_str(&format!("<desc>{}</desc>\n", sdz_xml_escape(description)));
+        }
+        xml.push_str(&format!(
+            "<link href=\"{}\"><text>SpotDiggz</text></link>\n</wpt>\n",
+            sdz_xml_escape(&waypoint.link)
+        ));
+    }
+    xml.push_str("</gpx>\n");
+    xml
+}
+
+fn sdz_xml_escape(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::models::SdzCreateSpotParams,
+        infrastructure::{
+            in_memory_mylist_repository::SdzInMemoryMyListRepository,
+            in_memory_spot_repository::SdzInMemorySpotRepository,
+        },
+    };
+
+    fn sample_spot(id: &str, location: Option<SdzSpotLocation>) -> SdzSpot {
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: id.to_string(),
+            name: format!("{id} <R&R>"),
+            description: Some("ledge \"low\"".to_string()),
+            location,
+            tags: vec![],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".to_string(),
+        })
+        .expect("valid spot")
+    }
+
+    async fn export(format: SdzMyListExportFormat) -> String {
+        let mylist_repo = Arc::new(SdzInMemoryMyListRepository::default());
+        let spot_repo = Arc::new(SdzInMemorySpotRepository::default());
+        let location = SdzSpotLocation {
+            lat: 35.5,
+            lng: 139.25,
+        };
+        spot_repo
+            .create(sample_spot("spot-1", Some(location)))
+            .await
+            .unwrap();
+        spot_repo.create(sample_spot("spot-2", None)).await.unwrap();
+        mylist_repo.add("user-1", "spot-1").await.unwrap();
+        mylist_repo.add("user-1", "spot-2").await.unwrap();
+
+        SdzExportMyListUseCase::new()
+            .execute(
+                mylist_repo,
+                spot_repo,
+                SdzAuthUser {
+                    sdz_user_id: "user-1".to_string(),
+                },
+                format,
+                "https://example.com/",
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn exports_kml_placemarks_with_link() {
+        let kml = export(SdzMyListExportFormat::Kml).await;
+        assert_eq!(kml.matches("<Placemark>").count(), 1);
+        assert!(kml.contains("<name>spot-1 &lt;R&amp;R&gt;</name>"));
+        assert!(kml.contains("<atom:link href=\"https://example.com/spots/spot-1\"/>"));
+        assert!(kml.contains(
+            "<description>ledge &quot;low&quot;\nhttps://example.com/spots/spot-1</description>"
+        ));
+        assert!(kml.contains("<coordinates>139.25,35.5</coordinates>"));
+    }
+
+    #[tokio::test]
+    async fn exports_gpx_waypoints_with_link() {
+        let gpx = export(SdzMyListExportFormat::Gpx).await;
+        assert_eq!(gpx.matches("<wpt ").count(), 1);
+        assert!(gpx.contains("<wpt lat=\"35.5\" lon=\"139.25\">"));
+        assert!(gpx.contains("<desc>ledge &quot;low&quot;</desc>"));
+        assert!(gpx.contains("<link href=\"https://example.com/spots/spot-1\">"));
+    }
+}
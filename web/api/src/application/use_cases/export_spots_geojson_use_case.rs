@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    application::use_cases::spot_repository::{
+        sdz_fetch_all_spots, SdzSpotQuery, SdzSpotRepository,
+    },
+    domain::models::{SdzSpot, SdzSpotParkAttributes, SdzStreetAttributes},
+    presentation::error::SdzApiError,
+};
+
+/// RFC 7946のFeatureCollection。位置情報のないスポットは`unlocated`（foreign member）に分ける
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzSpotFeatureCollection {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub features: Vec<SdzSpotFeature>,
+    pub unlocated: Vec<SdzUnlocatedSpot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzSpotFeature {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub id: String,
+    pub geometry: SdzPointGeometry,
+    pub properties: SdzSpotFeatureProperties,
+}
+
+/// 座標は`[経度, 緯度]`の順
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzPointGeometry {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub coordinates: [f64; 2],
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzSpotFeatureProperties {
+    #[serde(rename = "spotId")]
+    pub sdz_spot_id: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    #[serde(rename = "parkAttributes", skip_serializing_if = "Option::is_none")]
+    pub sdz_park_attributes: Option<SdzSpotParkAttributes>,
+    #[serde(rename = "streetAttributes", skip_serializing_if = "Option::is_none")]
+    pub sdz_street_attributes: Option<SdzStreetAttributes>,
+    #[serde(rename = "googlePlaceId", skip_serializing_if = "Option::is_none")]
+    pub sdz_google_place_id: Option<String>,
+    #[serde(rename = "googleMapsUrl", skip_serializing_if = "Option::is_none")]
+    pub sdz_google_maps_url: Option<String>,
+    #[serde(rename = "address", skip_serializing_if = "Option::is_none")]
+    pub sdz_address: Option<String>,
+    #[serde(rename = "phoneNumber", skip_serializing_if = "Option::is_none")]
+    pub sdz_phone_number: Option<String>,
+    #[serde(rename = "googleRating", skip_serializing_if = "Option::is_none")]
+    pub sdz_google_rating: Option<f64>,
+    #[serde(rename = "googleRatingCount", skip_serializing_if = "Option::is_none")]
+    pub sdz_google_rating_count: Option<u32>,
+    #[serde(rename = "googleTypes", skip_serializing_if = "Vec::is_empty")]
+    pub sdz_google_types: Vec<String>,
+}
+
+/// 地図に載せられなかったスポット（位置情報の登録漏れの確認用）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SdzUnlocatedSpot {
+    #[serde(rename = "spotId")]
+    pub sdz_spot_id: String,
+    pub name: String,
+}
+
+impl SdzSpotFeatureCollection {
+    pub fn from_spots(spots: Vec<SdzSpot>) -> Self {
+        let mut features = Vec::new();
+        let mut unlocated = Vec::new();
+        for spot in spots {
+            match spot.location.clone() {
+                Some(location) => features.push(SdzSpotFeature {
+                    kind: "Feature",
+                    id: spot.sdz_spot_id.clone(),
+                    geometry: SdzPointGeometry {
+                        kind: "Point",
+                        coordinates: [location.lng, location.lat],
+                    },
+                    properties: SdzSpotFeatureProperties {
+                        sdz_spot_id: spot.sdz_spot_id,
+                        name: spot.name,
+                        tags: spot.tags,
+                        sdz_park_attributes: spot.sdz_park_attributes,
+                        sdz_street_attributes: spot.sdz_street_attributes,
+                        sdz_google_place_id: spot.sdz_google_place_id,
+                        sdz_google_maps_url: spot.sdz_google_maps_url,
+                        sdz_address: spot.sdz_address,
+                        sdz_phone_number: spot.sdz_phone_number,
+                        sdz_google_rating: spot.sdz_google_rating,
+                        sdz_google_rating_count: spot.sdz_google_rating_count,
+                        sdz_google_types: spot.sdz_google_types,
+                    },
+                }),
+                None => unlocated.push(SdzUnlocatedSpot {
+                    sdz_spot_id: spot.sdz_spot_id,
+                    name: spot.name,
+                }),
+            }
+        }
+        Self {
+            kind: "FeatureCollection",
+            features,
+            unlocated,
+        }
+    }
+}
+
+/// 公開中の承認済みスポットを全件GeoJSONにする（パートナー連携・QA用の地図向け）
+pub struct SdzExportSpotsGeoJsonUseCase;
+
+impl SdzExportSpotsGeoJsonUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+    ) -> Result<SdzSpotFeatureCollection, SdzApiError> {
+        let query = SdzSpotQuery {
+            approved_only: true,
+            ..SdzSpotQuery::default()
+        };
+        let spots = sdz_fetch_all_spots(repo.as_ref(), &query).await?;
+        Ok(SdzSpotFeatureCollection::from_spots(spots))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::models::{SdzCreateSpotParams, SdzSpotApprovalStatus, SdzSpotLocation},
+        infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository,
+    };
+
+    fn build_spot(
+        spot_id: &str,
+        location: Option<SdzSpotLocation>,
+        status: SdzSpotApprovalStatus,
+    ) -> SdzSpot {
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: spot_id.into(),
+            name: format!("{} name", spot_id),
+            description: Some("not exported".into()),
+            location,
+            tags: vec!["パーク".into()],
+            images: vec![],
+            sdz_approval_status: Some(status),
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: Some("place-1".into()),
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: Some(4.5),
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn exports_approved_points_and_reports_unlocated() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let location = || {
+            Some(SdzSpotLocation {
+                lat: 35.5,
+                lng: 139.5,
+            })
+        };
+        repo.create(build_spot(
+            "located",
+            location(),
+            SdzSpotApprovalStatus::Approved,
+        ))
+        .await
+        .unwrap();
+        repo.create(build_spot("nowhere", None, SdzSpotApprovalStatus::Approved))
+            .await
+            .unwrap();
+        repo.create(build_spot(
+            "pending",
+            location(),
+            SdzSpotApprovalStatus::Pending,
+        ))
+        .await
+        .unwrap();
+
+        let collection = SdzExportSpotsGeoJsonUseCase::new()
+            .execute(repo)
+            .await
+            .unwrap();
+        let json = serde_json::to_value(&collection).unwrap();
+        assert_eq!(json["type"], "FeatureCollection");
+        let features = json["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0]["id"], "located");
+        assert_eq!(
+            features[0]["geometry"],
+            serde_json::json!({ "type": "Point", "coordinates": [139.5, 35.5] })
+        );
+        let properties = &features[0]["properties"];
+        assert_eq!(properties["name"], "located name");
+        assert_eq!(properties["googlePlaceId"], "place-1");
+        assert_eq!(properties["googleRating"], 4.5);
+        assert!(properties.get("description").is_none());
+        assert_eq!(
+            collection.unlocated,
+            vec![SdzUnlocatedSpot {
+                sdz_spot_id: "nowhere".into(),
+                name: "nowhere name".into(),
+            }]
+        );
+    }
+}
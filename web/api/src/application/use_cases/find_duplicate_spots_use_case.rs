@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    application::use_cases::spot_repository::{
+        sdz_fetch_all_spots, SdzSpotQuery, SdzSpotRepository,
+    },
+    domain::{
+        dedup::{sdz_find_duplicate_pairs, SdzDuplicateScore},
+        models::SdzSpot,
+    },
+    presentation::error::SdzApiError,
+};
+
+/// `threshold`未指定時の既定値
+pub const SDZ_DEDUP_DEFAULT_THRESHOLD: f64 = 0.6;
+/// 1回の応答に含めるペアの上限（スコアの高い順に残す）
+pub const SDZ_DEDUP_MAX_PAIRS: usize = 500;
+
+/// 重複候補のペア。`spots`は登録の古い順（統合時に残す側が先頭）
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzDuplicateSpotPair {
+    #[serde(flatten)]
+    pub score: SdzDuplicateScore,
+    pub spots: [SdzSpot; 2],
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzDuplicateSpotReport {
+    pub threshold: f64,
+    pub pairs: Vec<SdzDuplicateSpotPair>,
+    /// 上限を超えたためスコアの低いペアを省略した場合にtrue
+    pub truncated: bool,
+}
+
+/// 管理画面向けに、公開中の全スポットから重複候補のペアを検出する（アーカイブ済みは対象外）
+pub struct SdzFindDuplicateSpotsUseCase;
+
+impl SdzFindDuplicateSpotsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        threshold: f64,
+    ) -> Result<SdzDuplicateSpotReport, SdzApiError> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(SdzApiError::BadRequest(
+                "threshold must be between 0 and 1".to_string(),
+            ));
+        }
+        let spots = sdz_fetch_all_spots(repo.as_ref(), &SdzSpotQuery::default()).await?;
+        let mut pairs = sdz_find_duplicate_pairs(&spots, threshold);
+        let truncated = pairs.len() > SDZ_DEDUP_MAX_PAIRS;
+        pairs.truncate(SDZ_DEDUP_MAX_PAIRS);
+
+        let pairs = pairs
+            .into_iter()
+            .map(|(a, b, score)| {
+                let (a, b) = (&spots[a], &spots[b]);
+                let (older, newer) =
+                    if (b.created_at, &b.sdz_spot_id) < (a.created_at, &a.sdz_spot_id) {
+                        (b, a)
+                    } else {
+                        (a, b)
+                    };
+                SdzDuplicateSpotPair {
+                    score,
+                    spots: [older.clone(), newer.clone()],
+                }
+            })
+            .collect();
+        Ok(SdzDuplicateSpotReport {
+            threshold,
+            pairs,
+            truncated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::models::{SdzCreateSpotParams, SdzSpotLocation},
+        infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository,
+    };
+
+    fn build_spot(spot_id: &str, name: &str, lat: f64, lng: f64) -> SdzSpot {
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: spot_id.into(),
+            name: name.into(),
+            description: None,
+            location: Some(SdzSpotLocation { lat, lng }),
+            tags: vec![],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reports_nearby_similar_spots_older_first() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let mut original = build_spot("original", "渋谷スケートパーク", 35.6600, 139.7000);
+        original.created_at -= chrono::Duration::days(30);
+        repo.create(original).await.unwrap();
+        repo.create(build_spot("copy", "渋谷 スケートパーク", 35.6601, 139.7001))
+            .await
+            .unwrap();
+        repo.create(build_spot("far", "渋谷スケートパーク", 34.7000, 135.5000))
+            .await
+            .unwrap();
+
+        let use_case = SdzFindDuplicateSpotsUseCase::new();
+        let report = use_case.execute(repo.clone(), 0.6).await.unwrap();
+        assert_eq!(report.pairs.len(), 1);
+        assert!(!report.truncated);
+        let pair = &report.pairs[0];
+        assert_eq!(pair.spots[0].sdz_spot_id, "original");
+        assert_eq!(pair.spots[1].sdz_spot_id, "copy");
+        assert_eq!(pair.score.name_similarity, 1.0);
+
+        let err = use_case.execute(repo, 1.5).await.unwrap_err();
+        assert!(matches!(err, SdzApiError::BadRequest(_)));
+    }
+}
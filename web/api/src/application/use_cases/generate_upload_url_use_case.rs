@@ -28,8 +28,8 @@ impl SdzGenerateUploadUrlUseCase {
             .ok_or_else(|| SdzApiError::BadRequest("unsupported contentType".into()))?;
 
         let object_name = format!(
-            "spots/{}/{}.{}",
-            auth_user.sdz_user_id,
+            "{}{}.{}",
+            sdz_spot_image_prefix(&auth_user.sdz_user_id),
             Uuid::new_v4(),
             extension
         );
@@ -43,6 +43,11 @@ impl SdzGenerateUploadUrlUseCase {
     }
 }
 
+/// ユーザーがアップロードする画像のオブジェクト名の接頭辞。削除時の所有者確認にも使う
+pub fn sdz_spot_image_prefix(user_id: &str) -> String {
+    format!("spots/{}/", user_id)
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct SdzGenerateUploadUrlInput {
     #[serde(rename = "contentType")]
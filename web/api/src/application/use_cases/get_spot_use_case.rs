@@ -19,7 +19,7 @@ impl SdzGetSpotUseCase {
         viewer_user_id: Option<String>,
     ) -> Result<SdzSpot, SdzApiError> {
         let spot = repo
-            .find_by_id(&spot_id)
+            .find_active_by_id(&spot_id)
             .await?
             .ok_or(SdzApiError::NotFound)?;
 
@@ -84,6 +84,8 @@ mod tests {
             sdz_user_id: "user-1".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::{
+        spot_repository::{SdzSpotRepository, SDZ_BATCH_UPSERT_LIMIT},
+        spot_revision_repository::SdzSpotRevisionRepository,
+    },
+    domain::{
+        models::{sdz_now_jst, SdzSpot},
+        revision::{sdz_spot_changed_fields, SdzSpotRevision},
+    },
+    presentation::error::SdzApiError,
+};
+
+/// 取り込み対象のスポットが現在のストアに対してどう変わるか
+#[derive(Debug, Clone, PartialEq)]
+pub enum SdzSpotImportChange {
+    Added,
+    /// 変わったフィールド（`sdz_spot_changed_fields`の表記）
+    Changed(Vec<String>),
+    Unchanged,
+}
+
+#[derive(Debug, Clone)]
+pub struct SdzSpotImportEntry {
+    pub spot: SdzSpot,
+    pub change: SdzSpotImportChange,
+    /// 取り込み前の既存スポット（版の記録に使う）
+    pub existing: Option<SdzSpot>,
+}
+
+/// dry-runの結果。`apply`に渡すとAdded/Changedのみ書き込む
+#[derive(Debug, Clone, Default)]
+pub struct SdzSpotImportPlan {
+    pub entries: Vec<SdzSpotImportEntry>,
+}
+
+impl SdzSpotImportPlan {
+    pub fn count(&self, kind: fn(&SdzSpotImportChange) -> bool) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| kind(&entry.change))
+            .count()
+    }
+
+    fn pending_writes(&self) -> Vec<&SdzSpotImportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.change != SdzSpotImportChange::Unchanged)
+            .collect()
+    }
+}
+
+/// マスターデータ（CSVなど）の取り込み。CSVの内容を正として既存スポットを上書きする
+pub struct SdzImportSpotsUseCase;
+
+impl SdzImportSpotsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 既存スポットと突き合わせて差分を出す。書き込みは行わない。
+    /// 既存スポットの登録者・作成日時・アーカイブ状態は取り込み内容で上書きしない
+    pub async fn plan(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        spots: Vec<SdzSpot>,
+    ) -> Result<SdzSpotImportPlan, SdzApiError> {
+        let mut entries = Vec::with_capacity(spots.len());
+        for mut spot in spots {
+            let existing = repo.find_by_id(&spot.sdz_spot_id).await?;
+            let change = match &existing {
+                None => SdzSpotImportChange::Added,
+                Some(existing) => {
+                    spot.sdz_user_id = existing.sdz_user_id.clone();
+                    spot.created_at = existing.created_at;
+                    spot.sdz_deleted_at = existing.sdz_deleted_at;
+                    spot.sdz_deleted_by = existing.sdz_deleted_by.clone();
+                    let changed = sdz_spot_changed_fields(existing, &spot);
+                    if changed.is_empty() {
+                        spot.updated_at = existing.updated_at;
+                        SdzSpotImportChange::Unchanged
+                    } else {
+                        spot.updated_at = sdz_now_jst();
+                        SdzSpotImportChange::Changed(changed)
+                    }
+                }
+            };
+            entries.push(SdzSpotImportEntry {
+                spot,
+                change,
+                existing,
+            });
+        }
+        Ok(SdzSpotImportPlan { entries })
+    }
+
+    /// 差分を`SDZ_BATCH_UPSERT_LIMIT`件ずつ一括保存し、書き込んだ件数を返す。
+    /// 各バッチはアトミックだが、バッチをまたいだ途中失敗では先行分が残る。
+    /// 上書きした既存スポットはバッチの保存後に`editor_user_id`の版として記録する
+    pub async fn apply(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        revision_repo: Arc<dyn SdzSpotRevisionRepository>,
+        editor_user_id: &str,
+        plan: &SdzSpotImportPlan,
+    ) -> Result<usize, SdzApiError> {
+        let entries = plan.pending_writes();
+        for chunk in entries.chunks(SDZ_BATCH_UPSERT_LIMIT) {
+            repo.upsert_many(chunk.iter().map(|entry| entry.spot.clone()).collect())
+                .await?;
+            // 版の記録に失敗しても保存自体は確定しているため、ログに残して続行する
+            for entry in chunk {
+                let Some(existing) = &entry.existing else {
+                    continue;
+                };
+                let revision = SdzSpotRevision::new(existing, &entry.spot, editor_user_id);
+                if let Err(err) = revision_repo.append(revision).await {
+                    tracing::error!(
+                        spot_id = %entry.spot.sdz_spot_id,
+                        error = ?err,
+                        "failed to record spot revision"
+                    );
+                }
+            }
+        }
+        Ok(entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::models::SdzCreateSpotParams,
+        infrastructure::{
+            in_memory_spot_repository::SdzInMemorySpotRepository,
+            in_memory_spot_revision_repository::SdzInMemorySpotRevisionRepository,
+        },
+    };
+
+    fn build_spot(spot_id: &str, name: &str, user_id: &str) -> SdzSpot {
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: spot_id.into(),
+            name: name.into(),
+            description: None,
+            location: None,
+            tags: vec![],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: user_id.into(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn plan_reports_diff_and_apply_writes_only_changes() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let same = build_spot("same", "Same", "owner");
+        let renamed = build_spot("renamed", "Before", "owner");
+        repo.create(same.clone()).await.unwrap();
+        repo.create(renamed.clone()).await.unwrap();
+
+        let use_case = SdzImportSpotsUseCase::new();
+        let plan = use_case
+            .plan(
+                repo.clone(),
+                vec![
+                    build_spot("same", "Same", "importer"),
+                    build_spot("renamed", "After", "importer"),
+                    build_spot("new", "New", "importer"),
+                ],
+            )
+            .await
+            .unwrap();
+        let changes: Vec<_> = plan.entries.iter().map(|e| e.change.clone()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                SdzSpotImportChange::Unchanged,
+                SdzSpotImportChange::Changed(vec!["name".into()]),
+                SdzSpotImportChange::Added,
+            ]
+        );
+        // dry-runの段階では何も書き込まない
+        assert!(repo.find_by_id("new").await.unwrap().is_none());
+
+        let revision_repo: Arc<dyn SdzSpotRevisionRepository> =
+            Arc::new(SdzInMemorySpotRevisionRepository::default());
+        let written = use_case
+            .apply(repo.clone(), revision_repo.clone(), "importer", &plan)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+        // 上書きしたスポットだけ版が残る
+        let revisions = revision_repo
+            .list_by_spot("renamed", usize::MAX, None)
+            .await
+            .unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].changed_fields, vec!["name"]);
+        assert_eq!(revisions[0].sdz_editor_user_id, "importer");
+        for spot_id in ["same", "new"] {
+            assert!(revision_repo
+                .list_by_spot(spot_id, usize::MAX, None)
+                .await
+                .unwrap()
+                .is_empty());
+        }
+        let stored = repo.find_by_id("renamed").await.unwrap().unwrap();
+        assert_eq!(stored.name, "After");
+        assert_eq!(stored.sdz_user_id, "owner");
+        assert_eq!(stored.created_at, renamed.created_at);
+        let stored = repo.find_by_id("same").await.unwrap().unwrap();
+        assert_eq!(stored.updated_at, same.updated_at);
+        assert!(repo.find_by_id("new").await.unwrap().is_some());
+    }
+}
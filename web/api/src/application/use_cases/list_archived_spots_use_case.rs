@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::{
+        pagination::{SdzPage, SdzPageCursor, SdzPageRequest},
+        spot_repository::{SdzSpotQuery, SdzSpotRepository},
+    },
+    domain::models::SdzSpot,
+    presentation::error::SdzApiError,
+};
+
+/// 管理画面向けのアーカイブ済みスポット一覧（新着順）
+pub struct SdzListArchivedSpotsUseCase;
+
+impl SdzListArchivedSpotsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        page: SdzPageRequest,
+    ) -> Result<SdzPage<SdzSpot>, SdzApiError> {
+        let query = SdzSpotQuery {
+            archived: true,
+            ..SdzSpotQuery::default()
+        };
+        // 1件多く取得して次ページの有無を判定する
+        let mut spots = repo
+            .list_recent(&query, page.limit + 1, page.after.as_ref())
+            .await?;
+        let has_more = spots.len() > page.limit;
+        spots.truncate(page.limit);
+        let next_page_token = spots
+            .last()
+            .filter(|_| has_more)
+            .map(|spot| SdzPageCursor::from_spot(spot).encode());
+        Ok(SdzPage {
+            items: spots,
+            next_page_token,
+            truncated: false,
+        })
+    }
+}
@@ -2,9 +2,12 @@ use std::sync::Arc;
 
 use crate::{
     application::use_cases::{
-        mylist_repository::SdzMyListRepository, spot_repository::SdzSpotRepository,
+        mylist_repository::SdzMyListRepository,
+        pagination::{sdz_cmp_newest_first, SdzPage, SdzPageCursor, SdzPageRequest},
+        spot_repository::SdzSpotRepository,
+        spot_view::SdzSpotView,
     },
-    domain::models::SdzSpot,
+    domain::models::sdz_now_jst,
     presentation::{error::SdzApiError, middleware::auth::SdzAuthUser},
 };
 
@@ -20,17 +23,41 @@ impl SdzListMyListUseCase {
         mylist_repo: Arc<dyn SdzMyListRepository>,
         spot_repo: Arc<dyn SdzSpotRepository>,
         auth_user: SdzAuthUser,
-    ) -> Result<Vec<SdzSpot>, SdzApiError> {
+        page: SdzPageRequest,
+    ) -> Result<SdzPage<SdzSpotView>, SdzApiError> {
+        let now = sdz_now_jst();
         let mut entries = mylist_repo.list_by_user(&auth_user.sdz_user_id).await?;
-        entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        entries.sort_by(|a, b| {
+            sdz_cmp_newest_first(
+                (&a.created_at, &a.sdz_spot_id),
+                (&b.created_at, &b.sdz_spot_id),
+            )
+        });
+        if let Some(cursor) = &page.after {
+            entries.retain(|entry| cursor.comes_after(&entry.created_at, &entry.sdz_spot_id));
+        }
 
         let mut spots = Vec::new();
-        for entry in entries {
-            if let Some(spot) = spot_repo.find_by_id(&entry.sdz_spot_id).await? {
-                spots.push(spot);
+        let mut last_cursor = None;
+        let mut remaining = entries.into_iter();
+        for entry in remaining.by_ref() {
+            last_cursor = Some(SdzPageCursor::new(entry.created_at, &entry.sdz_spot_id));
+            // 削除・アーカイブ済みスポットはスキップする
+            if let Some(spot) = spot_repo.find_active_by_id(&entry.sdz_spot_id).await? {
+                spots.push(SdzSpotView::new(spot, now));
+                if spots.len() >= page.limit {
+                    break;
+                }
             }
         }
-        Ok(spots)
+        let has_more = remaining.next().is_some();
+        Ok(SdzPage {
+            items: spots,
+            next_page_token: last_cursor
+                .filter(|_| has_more)
+                .map(|cursor| cursor.encode()),
+            truncated: false,
+        })
     }
 }
 
@@ -95,13 +122,54 @@ mod tests {
             sdz_user_id: "user-1".to_string(),
         };
         let list = use_case
-            .execute(mylist_repo, spot_repo, auth)
+            .execute(mylist_repo, spot_repo, auth, SdzPageRequest::first(10))
             .await
             .unwrap();
 
-        assert_eq!(list.len(), 2);
-        let ids: Vec<_> = list.into_iter().map(|spot| spot.sdz_spot_id).collect();
+        assert_eq!(list.items.len(), 2);
+        assert!(list.next_page_token.is_none());
+        let ids: Vec<_> = list
+            .items
+            .into_iter()
+            .map(|view| view.spot.sdz_spot_id)
+            .collect();
         assert!(ids.contains(&"spot-1".to_string()));
         assert!(ids.contains(&"spot-2".to_string()));
     }
+
+    #[tokio::test]
+    async fn list_mylist_paginates() {
+        let mylist_repo = Arc::new(SdzInMemoryMyListRepository::default());
+        let spot_repo = Arc::new(SdzInMemorySpotRepository::default());
+        for id in ["spot-1", "spot-2", "spot-3"] {
+            spot_repo.create(sample_spot(id, "user-1")).await.unwrap();
+            mylist_repo.add("user-1", id).await.unwrap();
+        }
+        mylist_repo.add("user-1", "spot-deleted").await.unwrap();
+
+        let use_case = SdzListMyListUseCase::new();
+        let auth = SdzAuthUser {
+            sdz_user_id: "user-1".to_string(),
+        };
+        let mut page = SdzPageRequest::first(2);
+        let mut ids = Vec::new();
+        loop {
+            let result = use_case
+                .execute(
+                    mylist_repo.clone(),
+                    spot_repo.clone(),
+                    auth.clone(),
+                    page.clone(),
+                )
+                .await
+                .unwrap();
+            ids.extend(result.items.into_iter().map(|view| view.spot.sdz_spot_id));
+            let Some(token) = result.next_page_token else {
+                break;
+            };
+            page = SdzPageRequest::from_query(Some(2), Some(&token)).unwrap();
+        }
+        ids.sort();
+        assert_eq!(ids, vec!["spot-1", "spot-2", "spot-3"]);
+    }
 }
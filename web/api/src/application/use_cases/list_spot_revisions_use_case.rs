@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::{
+        pagination::{SdzPage, SdzPageCursor, SdzPageRequest},
+        spot_repository::SdzSpotRepository,
+        spot_revision_repository::SdzSpotRevisionRepository,
+    },
+    domain::revision::SdzSpotRevision,
+    presentation::error::SdzApiError,
+};
+
+/// 管理画面向けの更新履歴（新しい順）
+pub struct SdzListSpotRevisionsUseCase;
+
+impl SdzListSpotRevisionsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        spot_repo: Arc<dyn SdzSpotRepository>,
+        revision_repo: Arc<dyn SdzSpotRevisionRepository>,
+        spot_id: String,
+        page: SdzPageRequest,
+    ) -> Result<SdzPage<SdzSpotRevision>, SdzApiError> {
+        // 完全削除済みのスポットは履歴も見せない（アーカイブ済みは対象）
+        if spot_repo.find_by_id(&spot_id).await?.is_none() {
+            return Err(SdzApiError::NotFound);
+        }
+        // 1件多く取得して次ページの有無を判定する
+        let mut revisions = revision_repo
+            .list_by_spot(&spot_id, page.limit + 1, page.after.as_ref())
+            .await?;
+        let has_more = revisions.len() > page.limit;
+        revisions.truncate(page.limit);
+        let next_page_token = revisions.last().filter(|_| has_more).map(|revision| {
+            SdzPageCursor::new(revision.created_at, &revision.sdz_revision_id).encode()
+        });
+        Ok(SdzPage {
+            items: revisions,
+            next_page_token,
+            truncated: false,
+        })
+    }
+}
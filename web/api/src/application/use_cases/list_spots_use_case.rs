@@ -1,12 +1,33 @@
 use std::sync::Arc;
 
 use crate::{
-    application::use_cases::spot_repository::SdzSpotRepository, domain::models::SdzSpot,
+    application::use_cases::{
+        pagination::{
+            sdz_cmp_sort_keys, SdzPage, SdzPageCursor, SdzPageRequest, SdzSortCursor, SdzSortKey,
+            SDZ_PAGE_MAX_LIMIT,
+        },
+        spot_repository::{
+            sdz_fetch_recent_spots, SdzSpotOrder, SdzSpotQuery, SdzSpotRepository,
+            SDZ_QUERY_MAX_TAGS,
+        },
+        spot_view::SdzSpotView,
+    },
+    domain::{
+        business_hours::sdz_spot_open_status,
+        geo::{sdz_bounds_around, sdz_distance_m},
+        models::{sdz_now_jst, SdzSpot, SdzSpotBounds, SdzSpotLocation},
+        search::SdzSearchQuery,
+    },
     presentation::error::SdzApiError,
 };
 
-const SDZ_PARK_TAGS: [&str; 3] = ["パーク", "スケートパーク", "スケートボードパーク"];
-const SDZ_STREET_TAGS: [&str; 1] = ["ストリート"];
+/// 近隣検索の半径の既定値（メートル）
+pub const SDZ_NEARBY_DEFAULT_RADIUS_M: f64 = 5_000.0;
+/// 近隣検索の半径の上限（メートル）
+pub const SDZ_NEARBY_MAX_RADIUS_M: f64 = 50_000.0;
+/// 関連度順（`q=`）でメモリ上で並べ替える候補の走査上限。超えた分は対象外とし`truncated`で知らせる。
+/// ページごとに新着順で走査し直すため、1リクエストあたりのrunQueryは上限÷500回になる
+const SDZ_RANKED_SCAN_LIMIT: usize = 1_000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SdzSpotTypeFilter {
@@ -24,40 +45,218 @@ impl SdzSpotTypeFilter {
     }
 }
 
+/// 一覧の並び順。未指定時は近隣検索なら距離順、キーワード検索なら関連度順、それ以外は新着順
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdzSpotSort {
+    Newest,
+    Relevance,
+    Name,
+    Rating,
+    Updated,
+    Distance,
+}
+
+impl SdzSpotSort {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "newest" => Some(Self::Newest),
+            "relevance" => Some(Self::Relevance),
+            "name" => Some(Self::Name),
+            "rating" => Some(Self::Rating),
+            "updated" => Some(Self::Updated),
+            "distance" => Some(Self::Distance),
+            _ => None,
+        }
+    }
+}
+
+/// 指定地点からの半径検索条件
+#[derive(Debug, Clone)]
+pub struct SdzSpotNearby {
+    pub center: SdzSpotLocation,
+    pub radius_m: f64,
+}
+
+impl SdzSpotNearby {
+    /// 座標が範囲外、または半径が正でなければNone。半径は上限に丸める。
+    pub fn new(lat: f64, lng: f64, radius_m: Option<f64>) -> Option<Self> {
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+            return None;
+        }
+        let radius_m = radius_m.unwrap_or(SDZ_NEARBY_DEFAULT_RADIUS_M);
+        if !radius_m.is_finite() || radius_m <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            center: SdzSpotLocation { lat, lng },
+            radius_m: radius_m.min(SDZ_NEARBY_MAX_RADIUS_M),
+        })
+    }
+
+    /// 範囲内ならスポットまでの距離を返す
+    fn distance_to(&self, spot: &SdzSpot) -> Option<f64> {
+        let location = spot.location.as_ref()?;
+        let distance = sdz_distance_m(&self.center, location);
+        (distance <= self.radius_m).then_some(distance)
+    }
+}
+
+/// ストリートのセクション・路面などの条件。いずれかを指定するとストリート属性を持つスポットに限る
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SdzStreetFilter {
+    pub section_type: Option<String>,
+    pub min_height_cm: Option<u16>,
+    pub max_height_cm: Option<u16>,
+    pub difficulty: Option<String>,
+    pub surface_material: Option<String>,
+}
+
+impl SdzStreetFilter {
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    pub fn matches(&self, spot: &SdzSpot) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        let Some(attrs) = spot.sdz_street_attributes.as_ref() else {
+            return false;
+        };
+        if !matches_label(self.difficulty.as_deref(), attrs.difficulty.as_deref())
+            || !matches_label(
+                self.surface_material.as_deref(),
+                attrs.surface_material.as_deref(),
+            )
+        {
+            return false;
+        }
+        if self.section_type.is_none()
+            && self.min_height_cm.is_none()
+            && self.max_height_cm.is_none()
+        {
+            return true;
+        }
+        // 種類と高さは同じセクションで満たす必要がある（例: 40cm未満のレッジ）
+        attrs.sections.iter().flatten().any(|section| {
+            matches_label(self.section_type.as_deref(), Some(&section.section_type))
+                && self.matches_height(section.height_cm)
+        })
+    }
+
+    fn matches_height(&self, height_cm: Option<u16>) -> bool {
+        if self.min_height_cm.is_none() && self.max_height_cm.is_none() {
+            return true;
+        }
+        let Some(height_cm) = height_cm else {
+            return false;
+        };
+        self.min_height_cm
+            .map(|min| height_cm >= min)
+            .unwrap_or(true)
+            && self
+                .max_height_cm
+                .map(|max| height_cm <= max)
+                .unwrap_or(true)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SdzSpotSearchFilter {
     pub query: Option<String>,
     pub spot_type: Option<SdzSpotTypeFilter>,
     pub tags: Vec<String>,
+    /// 地図の表示範囲。指定時はリポジトリ側で範囲検索する
+    pub bounds: Option<SdzSpotBounds>,
+    /// 近隣検索。指定時は距離の近い順に並べる
+    pub nearby: Option<SdzSpotNearby>,
+    pub sort: Option<SdzSpotSort>,
+    /// 営業中と判定できるスポットのみ（判定不能は含めない）
+    pub open_now: bool,
+    pub street: SdzStreetFilter,
 }
 
 impl SdzSpotSearchFilter {
-    pub fn is_active(&self) -> bool {
-        self.query
-            .as_ref()
-            .map(|q| !q.trim().is_empty())
-            .unwrap_or(false)
-            || self.spot_type.is_some()
-            || !self.tags.is_empty()
+    pub fn effective_sort(&self) -> SdzSpotSort {
+        self.sort.unwrap_or(if self.nearby.is_some() {
+            SdzSpotSort::Distance
+        } else if self.search_query().is_some() {
+            SdzSpotSort::Relevance
+        } else {
+            SdzSpotSort::Newest
+        })
     }
 
+    /// 新着順以外は取得した候補をメモリ上で並べ替え、ページの続きは`SdzSortCursor`で指す
+    pub fn is_ranked(&self) -> bool {
+        self.effective_sort() != SdzSpotSort::Newest
+    }
+
+    /// リポジトリ側で評価できる条件。匿名の閲覧者には承認済みのみを返す。
+    /// アーカイブ済みは閲覧者によらず検索対象外
+    pub fn repository_query(&self, viewer_user_id: Option<&str>) -> SdzSpotQuery {
+        SdzSpotQuery {
+            archived: false,
+            approved_only: viewer_user_id.is_none(),
+            spot_type: self.spot_type,
+            tags_any: if self.pushes_down_tags() {
+                self.tags.clone()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// リポジトリ側で並べ替えられる並び順。範囲検索では候補をメモリ上で並べ替えるためNone
+    pub fn repository_order(&self) -> Option<SdzSpotOrder> {
+        if self.nearby.is_some() || self.bounds.is_some() {
+            return None;
+        }
+        match self.effective_sort() {
+            SdzSpotSort::Name => Some(SdzSpotOrder::Name),
+            SdzSpotSort::Rating => Some(SdzSpotOrder::Rating),
+            SdzSpotSort::Updated => Some(SdzSpotOrder::Updated),
+            SdzSpotSort::Newest | SdzSpotSort::Relevance | SdzSpotSort::Distance => None,
+        }
+    }
+
+    /// リポジトリで評価できず、取得後にメモリ上で絞り込む条件があるか
+    pub fn has_in_memory_predicates(&self) -> bool {
+        self.search_query().is_some()
+            || self.open_now
+            || self.street.is_active()
+            || !self.pushes_down_tags() && !self.tags.is_empty()
+    }
+
+    /// `repository_query`で表現できない条件だけを適用する
     pub fn apply(&self, spots: Vec<SdzSpot>) -> Vec<SdzSpot> {
-        let normalized_query = self
-            .query
-            .as_ref()
-            .map(|q| q.trim())
-            .filter(|q| !q.is_empty())
-            .map(|q| q.to_lowercase());
+        let search = self.search_query();
+        let tags: &[String] = if self.pushes_down_tags() {
+            &[]
+        } else {
+            &self.tags
+        };
 
         spots
             .into_iter()
             .filter(|spot| {
-                matches_query(spot, normalized_query.as_deref())
-                    && matches_type(spot, self.spot_type)
-                    && matches_tags(spot, &self.tags)
+                search
+                    .as_ref()
+                    .map(|search| search.score(spot).is_some())
+                    .unwrap_or(true)
+                    && matches_tags(spot, tags)
+                    && self.street.matches(spot)
             })
             .collect()
     }
+
+    fn search_query(&self) -> Option<SdzSearchQuery> {
+        self.query.as_deref().and_then(SdzSearchQuery::parse)
+    }
+
+    fn pushes_down_tags(&self) -> bool {
+        self.tags.len() <= SDZ_QUERY_MAX_TAGS
+    }
 }
 
 pub struct SdzListSpotsUseCase;
@@ -70,61 +269,298 @@ impl SdzListSpotsUseCase {
     pub async fn execute(
         &self,
         repo: Arc<dyn SdzSpotRepository>,
-        limit: usize,
+        page: SdzPageRequest,
         viewer_user_id: Option<String>,
         filter: SdzSpotSearchFilter,
-    ) -> Result<Vec<SdzSpot>, SdzApiError> {
-        let capped = limit.min(100); // 念のため上限
-        let fetch_limit = if filter.is_active() {
+    ) -> Result<SdzPage<SdzSpotView>, SdzApiError> {
+        let capped = page.limit.min(SDZ_PAGE_MAX_LIMIT); // 念のため上限
+        let now = sdz_now_jst();
+        let sort = filter.effective_sort();
+        if let Some(cursor) = &page.sort_after {
+            if !sort_key_matches(&cursor.key, sort) {
+                return Err(SdzApiError::BadRequest(
+                    "pageToken does not match sort".into(),
+                ));
+            }
+        }
+        let fetch_limit = if filter.has_in_memory_predicates() {
             (capped.saturating_mul(4)).min(200)
         } else {
             capped
         };
-        let mut spots = repo.list_recent(fetch_limit).await?;
-        if let Some(user_id) = viewer_user_id {
-            spots.retain(|spot| spot.is_approved() || spot.sdz_user_id == user_id);
+        if let Some(order) = filter.repository_order() {
+            return self
+                .list_in_order(
+                    repo,
+                    &page,
+                    viewer_user_id.as_deref(),
+                    &filter,
+                    order,
+                    fetch_limit,
+                )
+                .await;
+        }
+        let fetch_limit =
+            if filter.is_ranked() && (filter.nearby.is_some() || filter.bounds.is_some()) {
+                // 範囲内の候補は全件を並べ替える。件数の上限はリポジトリの走査上限に任せ、超えればtruncatedになる
+                usize::MAX
+            } else if filter.is_ranked() {
+                SDZ_RANKED_SCAN_LIMIT
+            } else {
+                fetch_limit
+            };
+        // 並べ替える場合は毎回候補全体を取得し、並べ替えた後に`sort_after`の続きを切り出す
+        let after = if filter.is_ranked() {
+            None
         } else {
-            spots.retain(|spot| spot.is_approved());
+            page.after.as_ref()
+        };
+        let matched =
+            sdz_fetch_matching_spots(repo, &filter, viewer_user_id.as_deref(), fetch_limit, after)
+                .await?;
+        let scanned_all = matched.scanned_all;
+        let last_scanned = matched.last_scanned;
+        let truncated = matched.truncated;
+        let spots = matched.spots;
+
+        if filter.is_ranked() {
+            let search = filter.search_query();
+            let mut ranked: Vec<RankedSpot> = spots
+                .into_iter()
+                .map(|spot| {
+                    let distance_m = filter
+                        .nearby
+                        .as_ref()
+                        .and_then(|nearby| nearby.distance_to(&spot));
+                    let score = search
+                        .as_ref()
+                        .and_then(|search| search.score(&spot))
+                        .unwrap_or(0);
+                    RankedSpot {
+                        key: sdz_sort_key(&spot, sort, distance_m, score),
+                        spot,
+                        distance_m,
+                    }
+                })
+                .collect();
+            if let Some(cursor) = &page.sort_after {
+                ranked.retain(|ranked| cursor.comes_after(&ranked.key, &ranked.spot.sdz_spot_id));
+            }
+            ranked.sort_by(|a, b| {
+                sdz_cmp_sort_keys((&a.key, &a.spot.sdz_spot_id), (&b.key, &b.spot.sdz_spot_id))
+            });
+            let next_cursor = if ranked.len() > capped {
+                ranked.truncate(capped);
+                ranked
+                    .last()
+                    .map(|last| SdzSortCursor::new(last.key.clone(), &last.spot.sdz_spot_id))
+            } else {
+                None
+            };
+            let items = ranked
+                .into_iter()
+                .map(|ranked| {
+                    SdzSpotView::new(ranked.spot, now)
+                        .with_distance(ranked.distance_m.map(f64::round))
+                })
+                .collect();
+            return Ok(SdzPage {
+                items,
+                next_page_token: next_cursor.map(|cursor| cursor.encode()),
+                truncated: truncated || !scanned_all,
+            });
         }
-        let mut spots = filter.apply(spots);
-        spots.truncate(capped);
-        Ok(spots)
+
+        let mut items: Vec<SdzSpotView> = spots
+            .into_iter()
+            .map(|spot| SdzSpotView::new(spot, now))
+            .collect();
+        let next_cursor = if items.len() > capped {
+            items.truncate(capped);
+            items
+                .last()
+                .map(|item| SdzPageCursor::from_spot(&item.spot))
+        } else if scanned_all {
+            None
+        } else {
+            last_scanned
+        };
+        Ok(SdzPage {
+            items,
+            next_page_token: next_cursor.map(|cursor| cursor.encode()),
+            truncated,
+        })
+    }
+
+    /// 名前順・評価順・更新順はリポジトリ側で並べ替え、新着順と同じく取得範囲の続きから読む
+    async fn list_in_order(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        page: &SdzPageRequest,
+        viewer_user_id: Option<&str>,
+        filter: &SdzSpotSearchFilter,
+        order: SdzSpotOrder,
+        fetch_limit: usize,
+    ) -> Result<SdzPage<SdzSpotView>, SdzApiError> {
+        let capped = page.limit.min(SDZ_PAGE_MAX_LIMIT);
+        let now = sdz_now_jst();
+        let query = filter.repository_query(viewer_user_id);
+        let spots = repo
+            .list_sorted(&query, order, fetch_limit, page.sort_after.as_ref())
+            .await?;
+        let scanned_all = spots.len() < fetch_limit;
+        let last_scanned = spots
+            .last()
+            .map(|spot| SdzSortCursor::new(order.sort_key(spot), &spot.sdz_spot_id));
+
+        let mut items: Vec<SdzSpotView> = sdz_retain_visible_matches(filter, viewer_user_id, spots)
+            .into_iter()
+            .map(|spot| SdzSpotView::new(spot, now))
+            .collect();
+        let next_cursor = if items.len() > capped {
+            items.truncate(capped);
+            items
+                .last()
+                .map(|item| SdzSortCursor::new(order.sort_key(&item.spot), &item.spot.sdz_spot_id))
+        } else if scanned_all {
+            None
+        } else {
+            last_scanned
+        };
+        Ok(SdzPage {
+            items,
+            next_page_token: next_cursor.map(|cursor| cursor.encode()),
+            truncated: false,
+        })
     }
 }
 
-fn matches_query(spot: &SdzSpot, normalized_query: Option<&str>) -> bool {
-    let Some(query) = normalized_query else {
-        return true;
-    };
-    let name_match = spot.name.to_lowercase().contains(query);
-    let desc_match = spot
-        .description
-        .as_ref()
-        .map(|desc| desc.to_lowercase().contains(query))
-        .unwrap_or(false);
-    let tag_match = spot
-        .tags
-        .iter()
-        .any(|tag| tag.to_lowercase().contains(query));
-
-    name_match || desc_match || tag_match
+/// 検索条件に合い、閲覧者に見えるスポット（並べ替え前）
+pub struct SdzMatchedSpots {
+    pub spots: Vec<SdzSpot>,
+    /// リポジトリから取得上限未満しか返らなかった（続きがない）
+    pub scanned_all: bool,
+    /// 絞り込み前の末尾。ページが埋まらなかった場合の続きの起点にする
+    pub last_scanned: Option<SdzPageCursor>,
+    /// 矩形検索が走査件数の上限に達した
+    pub truncated: bool,
 }
 
-fn matches_type(spot: &SdzSpot, spot_type: Option<SdzSpotTypeFilter>) -> bool {
-    let Some(spot_type) = spot_type else {
-        return true;
+/// 一覧とファセット集計で共通の取得・可視性判定・絞り込みを行う
+pub async fn sdz_fetch_matching_spots(
+    repo: Arc<dyn SdzSpotRepository>,
+    filter: &SdzSpotSearchFilter,
+    viewer_user_id: Option<&str>,
+    fetch_limit: usize,
+    after: Option<&SdzPageCursor>,
+) -> Result<SdzMatchedSpots, SdzApiError> {
+    let fetch_bounds = filter
+        .nearby
+        .as_ref()
+        .map(|nearby| sdz_bounds_around(&nearby.center, nearby.radius_m))
+        .or_else(|| filter.bounds.clone());
+    let query = filter.repository_query(viewer_user_id);
+    let (spots, truncated) = match &fetch_bounds {
+        Some(bounds) => {
+            let found = repo
+                .list_in_bounds(bounds, &query, fetch_limit, after)
+                .await?;
+            (found.spots, found.truncated)
+        }
+        None => (
+            sdz_fetch_recent_spots(repo.as_ref(), &query, fetch_limit, after).await?,
+            false,
+        ),
     };
+    let scanned_all = spots.len() < fetch_limit;
+    let last_scanned = spots.last().map(SdzPageCursor::from_spot);
+    Ok(SdzMatchedSpots {
+        spots: sdz_retain_visible_matches(filter, viewer_user_id, spots),
+        scanned_all,
+        last_scanned,
+        truncated,
+    })
+}
 
-    match spot_type {
-        SdzSpotTypeFilter::Park => {
-            spot.sdz_park_attributes.is_some() || has_any_tag(spot, &SDZ_PARK_TAGS)
-        }
-        SdzSpotTypeFilter::Street => {
-            spot.sdz_street_attributes.is_some() || has_any_tag(spot, &SDZ_STREET_TAGS)
-        }
+/// 取得した候補から、閲覧者に見えて検索条件に合うものを残す
+fn sdz_retain_visible_matches(
+    filter: &SdzSpotSearchFilter,
+    viewer_user_id: Option<&str>,
+    mut spots: Vec<SdzSpot>,
+) -> Vec<SdzSpot> {
+    // 未承認でも投稿者本人には見せる（OR条件はクエリで表現しにくいためここで判定）
+    if let Some(user_id) = viewer_user_id {
+        spots.retain(|spot| spot.is_approved() || spot.sdz_user_id == user_id);
+    }
+    let mut spots = filter.apply(spots);
+    if let Some(nearby) = &filter.nearby {
+        spots.retain(|spot| nearby.distance_to(spot).is_some());
+    }
+    if filter.open_now {
+        let now = sdz_now_jst();
+        spots.retain(|spot| sdz_spot_open_status(spot, now).is_open_now == Some(true));
+    }
+    spots
+}
+
+/// 並べ替え用にキーと距離を添えた候補
+struct RankedSpot {
+    spot: SdzSpot,
+    distance_m: Option<f64>,
+    key: SdzSortKey,
+}
+
+fn sdz_sort_key(
+    spot: &SdzSpot,
+    sort: SdzSpotSort,
+    distance_m: Option<f64>,
+    score: u32,
+) -> SdzSortKey {
+    match sort {
+        // 新着順はここを通らないが、関連度なしの関連度順と同じ並びになる
+        SdzSpotSort::Newest | SdzSpotSort::Relevance => SdzSortKey::Relevance {
+            score,
+            created_at: spot.created_at,
+        },
+        SdzSpotSort::Name => SdzSpotOrder::Name.sort_key(spot),
+        SdzSpotSort::Rating => SdzSpotOrder::Rating.sort_key(spot),
+        SdzSpotSort::Updated => SdzSpotOrder::Updated.sort_key(spot),
+        // 距離順は近隣検索の範囲内のスポットだけが候補になるため、距離は常にある
+        SdzSpotSort::Distance => SdzSortKey::Distance {
+            distance_m: distance_m.unwrap_or(f64::MAX),
+        },
     }
 }
 
+/// ページトークンが別の並び順で発行されたものでないか
+fn sort_key_matches(key: &SdzSortKey, sort: SdzSpotSort) -> bool {
+    matches!(
+        (key, sort),
+        (
+            SdzSortKey::Relevance { .. },
+            SdzSpotSort::Newest | SdzSpotSort::Relevance
+        ) | (SdzSortKey::Name { .. }, SdzSpotSort::Name)
+            | (SdzSortKey::Rating { .. }, SdzSpotSort::Rating)
+            | (SdzSortKey::Updated { .. }, SdzSpotSort::Updated)
+            | (SdzSortKey::Distance { .. }, SdzSpotSort::Distance)
+    )
+}
+
+/// セクション種別・難易度などのラベルの比較用の形。大文字小文字と前後の空白を区別しない
+pub fn sdz_normalize_label(value: &str) -> String {
+    value.trim().to_ascii_lowercase()
+}
+
+/// 指定がなければ一致扱い。`sdz_normalize_label`した値で比べる
+fn matches_label(expected: Option<&str>, actual: Option<&str>) -> bool {
+    let Some(expected) = expected else {
+        return true;
+    };
+    actual
+        .map(|actual| sdz_normalize_label(actual) == sdz_normalize_label(expected))
+        .unwrap_or(false)
+}
+
 fn matches_tags(spot: &SdzSpot, tags: &[String]) -> bool {
     if tags.is_empty() {
         return true;
@@ -133,28 +569,61 @@ fn matches_tags(spot: &SdzSpot, tags: &[String]) -> bool {
         .any(|tag| spot.tags.iter().any(|spot_tag| spot_tag == tag))
 }
 
-fn has_any_tag(spot: &SdzSpot, tags: &[&str]) -> bool {
-    spot.tags.iter().any(|tag| tags.contains(&tag.as_str()))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        domain::models::{SdzSpotApprovalStatus, SdzSpotParkAttributes, SdzStreetAttributes},
+        domain::models::{
+            SdzSpotApprovalStatus, SdzSpotLocation, SdzSpotParkAttributes, SdzStreetAttributes,
+        },
         infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository,
     };
     use chrono::{FixedOffset, TimeZone};
 
+    fn sample_spot(id: &str, day: u32) -> SdzSpot {
+        let tz = FixedOffset::east_opt(9 * 3600).unwrap();
+        SdzSpot {
+            sdz_spot_id: id.into(),
+            name: id.into(),
+            description: None,
+            location: None,
+            tags: vec![],
+            images: vec![],
+            sdz_approval_status: Some(SdzSpotApprovalStatus::Approved),
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user".into(),
+            created_at: tz.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+            updated_at: tz.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
+        }
+    }
+
     #[tokio::test]
     async fn list_spots_returns_empty() {
         let repo = Arc::new(SdzInMemorySpotRepository::default());
         let use_case = SdzListSpotsUseCase::new();
         let list = use_case
-            .execute(repo, 10, None, SdzSpotSearchFilter::default())
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter::default(),
+            )
             .await
             .unwrap();
-        assert!(list.is_empty());
+        assert!(list.items.is_empty());
     }
 
     #[tokio::test]
@@ -185,17 +654,24 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
 
         let use_case = SdzListSpotsUseCase::new();
         let list = use_case
-            .execute(repo, 10, None, SdzSpotSearchFilter::default())
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter::default(),
+            )
             .await
             .unwrap();
-        assert_eq!(list.len(), 1);
-        assert_eq!(list[0].sdz_spot_id, "spot-1");
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].spot.sdz_spot_id, "spot-1");
     }
 
     #[tokio::test]
@@ -225,6 +701,8 @@ mod tests {
             sdz_user_id: "user-1".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -251,6 +729,8 @@ mod tests {
             sdz_user_id: "user-2".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -259,14 +739,14 @@ mod tests {
         let list = use_case
             .execute(
                 repo,
-                10,
+                SdzPageRequest::first(10),
                 Some("user-1".into()),
                 SdzSpotSearchFilter::default(),
             )
             .await
             .unwrap();
-        assert_eq!(list.len(), 1);
-        assert_eq!(list[0].sdz_spot_id, "spot-owner");
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].spot.sdz_spot_id, "spot-owner");
     }
 
     #[tokio::test]
@@ -296,6 +776,8 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -322,6 +804,8 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -330,18 +814,17 @@ mod tests {
         let list = use_case
             .execute(
                 repo,
-                10,
+                SdzPageRequest::first(10),
                 None,
                 SdzSpotSearchFilter {
                     query: Some("ledge".into()),
-                    spot_type: None,
-                    tags: vec![],
+                    ..Default::default()
                 },
             )
             .await
             .unwrap();
-        assert_eq!(list.len(), 1);
-        assert_eq!(list[0].sdz_spot_id, "spot-1");
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].spot.sdz_spot_id, "spot-1");
     }
 
     #[tokio::test]
@@ -376,6 +859,8 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -408,6 +893,8 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -416,18 +903,18 @@ mod tests {
         let list = use_case
             .execute(
                 repo,
-                10,
+                SdzPageRequest::first(10),
                 None,
                 SdzSpotSearchFilter {
-                    query: None,
                     spot_type: Some(SdzSpotTypeFilter::Street),
                     tags: vec!["夜".into()],
+                    ..Default::default()
                 },
             )
             .await
             .unwrap();
-        assert_eq!(list.len(), 1);
-        assert_eq!(list[0].sdz_spot_id, "spot-street");
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].spot.sdz_spot_id, "spot-street");
     }
 
     #[tokio::test]
@@ -457,6 +944,8 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -483,6 +972,8 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -509,6 +1000,8 @@ mod tests {
             sdz_user_id: "user".into(),
             created_at: tz.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(),
             updated_at: tz.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
         .await
         .unwrap();
@@ -517,22 +1010,608 @@ mod tests {
         let list = use_case
             .execute(
                 repo,
-                10,
+                SdzPageRequest::first(10),
                 None,
                 SdzSpotSearchFilter {
-                    query: None,
-                    spot_type: None,
                     tags: vec!["夜".into(), "朝".into()],
+                    ..Default::default()
                 },
             )
             .await
             .unwrap();
         let ids = list
+            .items
             .iter()
-            .map(|spot| spot.sdz_spot_id.as_str())
+            .map(|item| item.spot.sdz_spot_id.as_str())
             .collect::<Vec<_>>();
-        assert_eq!(list.len(), 2);
+        assert_eq!(list.items.len(), 2);
         assert!(ids.contains(&"spot-night"));
         assert!(ids.contains(&"spot-morning"));
     }
+
+    #[tokio::test]
+    async fn list_spots_filters_by_bounds() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        let mut tokyo = sample_spot("spot-tokyo", 1);
+        tokyo.location = Some(SdzSpotLocation {
+            lat: 35.68,
+            lng: 139.76,
+        });
+        let mut osaka = sample_spot("spot-osaka", 2);
+        osaka.location = Some(SdzSpotLocation {
+            lat: 34.69,
+            lng: 135.50,
+        });
+        repo.create(tokyo).await.unwrap();
+        repo.create(osaka).await.unwrap();
+        repo.create(sample_spot("spot-unlocated", 3)).await.unwrap();
+
+        let use_case = SdzListSpotsUseCase::new();
+        let list = use_case
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter {
+                    bounds: SdzSpotBounds::parse("34.0,135.0,35.0,136.0"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].spot.sdz_spot_id, "spot-osaka");
+    }
+
+    #[tokio::test]
+    async fn list_spots_nearby_sorted_by_distance() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        let mut far = sample_spot("spot-far", 1);
+        far.location = Some(SdzSpotLocation {
+            lat: 35.70,
+            lng: 139.70,
+        });
+        let mut near = sample_spot("spot-near", 2);
+        near.location = Some(SdzSpotLocation {
+            lat: 35.681,
+            lng: 139.767,
+        });
+        let mut outside = sample_spot("spot-outside", 3);
+        outside.location = Some(SdzSpotLocation {
+            lat: 34.69,
+            lng: 135.50,
+        });
+        repo.create(far).await.unwrap();
+        repo.create(near).await.unwrap();
+        repo.create(outside).await.unwrap();
+        repo.create(sample_spot("spot-unlocated", 4)).await.unwrap();
+
+        let use_case = SdzListSpotsUseCase::new();
+        let list = use_case
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter {
+                    nearby: SdzSpotNearby::new(35.681236, 139.767125, Some(10_000.0)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let ids = list
+            .items
+            .iter()
+            .map(|item| item.spot.sdz_spot_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["spot-near", "spot-far"]);
+        assert!(list.items[0].distance_m.unwrap() < list.items[1].distance_m.unwrap());
+    }
+
+    #[tokio::test]
+    async fn list_spots_nearby_sorts_all_candidates_in_range() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        // 最も近いスポットが最も古く、新着順の上位には入らない
+        let mut nearest = sample_spot("spot-nearest", 1);
+        nearest.location = Some(SdzSpotLocation {
+            lat: 35.6812,
+            lng: 139.7671,
+        });
+        repo.create(nearest).await.unwrap();
+        for i in 0..250 {
+            let mut spot = sample_spot(&format!("spot-{i}"), 2);
+            spot.created_at += chrono::Duration::minutes(i);
+            spot.location = Some(SdzSpotLocation {
+                lat: 35.70,
+                lng: 139.70,
+            });
+            repo.create(spot).await.unwrap();
+        }
+
+        let use_case = SdzListSpotsUseCase::new();
+        let list = use_case
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter {
+                    nearby: SdzSpotNearby::new(35.681236, 139.767125, Some(10_000.0)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(list.items[0].spot.sdz_spot_id, "spot-nearest");
+        assert!(!list.truncated);
+    }
+
+    #[test]
+    fn nearby_caps_radius_and_rejects_invalid() {
+        let nearby = SdzSpotNearby::new(35.0, 139.0, Some(1_000_000.0)).unwrap();
+        assert_eq!(nearby.radius_m, SDZ_NEARBY_MAX_RADIUS_M);
+        let nearby = SdzSpotNearby::new(35.0, 139.0, None).unwrap();
+        assert_eq!(nearby.radius_m, SDZ_NEARBY_DEFAULT_RADIUS_M);
+        assert!(SdzSpotNearby::new(95.0, 139.0, None).is_none());
+        assert!(SdzSpotNearby::new(35.0, 139.0, Some(0.0)).is_none());
+    }
+
+    #[tokio::test]
+    async fn list_spots_paginates_with_token() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        for day in 1..=5 {
+            repo.create(sample_spot(&format!("spot-{day}"), day))
+                .await
+                .unwrap();
+        }
+        let mut hidden = sample_spot("spot-hidden", 3);
+        hidden.sdz_approval_status = None;
+        repo.create(hidden).await.unwrap();
+
+        let use_case = SdzListSpotsUseCase::new();
+        let mut page = SdzPageRequest::first(2);
+        let mut ids = Vec::new();
+        loop {
+            let result = use_case
+                .execute(
+                    repo.clone(),
+                    page.clone(),
+                    None,
+                    SdzSpotSearchFilter::default(),
+                )
+                .await
+                .unwrap();
+            assert!(result.items.len() <= 2);
+            ids.extend(result.items.into_iter().map(|item| item.spot.sdz_spot_id));
+            let Some(token) = result.next_page_token else {
+                break;
+            };
+            page = SdzPageRequest::from_query(Some(2), Some(&token)).unwrap();
+        }
+        assert_eq!(ids, vec!["spot-5", "spot-4", "spot-3", "spot-2", "spot-1"]);
+    }
+
+    #[tokio::test]
+    async fn list_spots_orders_query_results_by_relevance() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        let mut tagged = sample_spot("tagged", 3);
+        tagged.tags = vec!["スケート".into()];
+        repo.create(tagged).await.unwrap();
+        let mut described = sample_spot("described", 2);
+        described.description = Some("すけーとできる広場".into());
+        repo.create(described).await.unwrap();
+        let mut named = sample_spot("named", 1);
+        named.name = "スケートパーク".into();
+        repo.create(named).await.unwrap();
+        repo.create(sample_spot("other", 4)).await.unwrap();
+
+        let list = SdzListSpotsUseCase::new()
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter {
+                    query: Some("ｽｹｰﾄ".into()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let ids: Vec<_> = list
+            .items
+            .iter()
+            .map(|item| item.spot.sdz_spot_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["named", "tagged", "described"]);
+        assert!(list.next_page_token.is_none());
+    }
+
+    #[tokio::test]
+    async fn list_spots_paginates_relevance_results() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        for day in 1..=5 {
+            let mut spot = sample_spot(&format!("rail-{day}"), day);
+            spot.name = if day % 2 == 0 {
+                "レール".into()
+            } else {
+                "公園のレール".into()
+            };
+            repo.create(spot).await.unwrap();
+        }
+        repo.create(sample_spot("other", 6)).await.unwrap();
+
+        let use_case = SdzListSpotsUseCase::new();
+        let filter = SdzSpotSearchFilter {
+            query: Some("レール".into()),
+            ..Default::default()
+        };
+        let mut page = SdzPageRequest::first(2);
+        let mut ids = Vec::new();
+        loop {
+            let result = use_case
+                .execute(repo.clone(), page.clone(), None, filter.clone())
+                .await
+                .unwrap();
+            assert!(result.items.len() <= 2);
+            ids.extend(result.items.into_iter().map(|item| item.spot.sdz_spot_id));
+            let Some(token) = result.next_page_token else {
+                break;
+            };
+            page = SdzPageRequest::from_sorted_query(Some(2), Some(&token)).unwrap();
+        }
+        let full = use_case
+            .execute(
+                repo.clone(),
+                SdzPageRequest::first(10),
+                None,
+                filter.clone(),
+            )
+            .await
+            .unwrap();
+        let expected: Vec<_> = full
+            .items
+            .into_iter()
+            .map(|item| item.spot.sdz_spot_id)
+            .collect();
+        assert_eq!(expected.len(), 5);
+        assert_eq!(ids, expected);
+
+        // 別の並び順で発行されたトークンは受け付けない
+        let name_token = SdzSortCursor::new(SdzSortKey::Name { name: "a".into() }, "x").encode();
+        let err = use_case
+            .execute(
+                repo,
+                SdzPageRequest::from_sorted_query(Some(2), Some(&name_token)).unwrap(),
+                None,
+                filter,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdzApiError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn list_spots_bounds_relevance_scan() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        for i in 0..=SDZ_RANKED_SCAN_LIMIT {
+            let mut spot = sample_spot(&format!("park-{i:04}"), 2);
+            spot.created_at -= chrono::Duration::minutes(i as i64);
+            spot.name = "パーク".into();
+            repo.create(spot).await.unwrap();
+        }
+
+        let list = SdzListSpotsUseCase::new()
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter {
+                    query: Some("パーク".into()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        // 走査上限より古い候補は対象外になり、truncatedで知らせる
+        assert!(list.truncated);
+        assert_eq!(list.items.len(), 10);
+        assert!(list
+            .items
+            .iter()
+            .all(|item| item.spot.sdz_spot_id != format!("park-{SDZ_RANKED_SCAN_LIMIT:04}")));
+    }
+
+    #[tokio::test]
+    async fn list_spots_sorts_by_rating_and_updated() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        let tz = FixedOffset::east_opt(9 * 3600).unwrap();
+        let mut unrated = sample_spot("unrated", 4);
+        unrated.updated_at = tz.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+        repo.create(unrated).await.unwrap();
+        let mut low = sample_spot("low", 3);
+        low.sdz_google_rating = Some(3.9);
+        repo.create(low).await.unwrap();
+        let mut high = sample_spot("high", 1);
+        high.sdz_google_rating = Some(4.6);
+        high.sdz_google_rating_count = Some(10);
+        repo.create(high).await.unwrap();
+        let mut high_popular = sample_spot("high-popular", 2);
+        high_popular.sdz_google_rating = Some(4.6);
+        high_popular.sdz_google_rating_count = Some(200);
+        repo.create(high_popular).await.unwrap();
+
+        let use_case = SdzListSpotsUseCase::new();
+        let ids = |page: SdzPage<SdzSpotView>| -> Vec<String> {
+            page.items
+                .into_iter()
+                .map(|item| item.spot.sdz_spot_id)
+                .collect()
+        };
+        let by_rating = use_case
+            .execute(
+                repo.clone(),
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter {
+                    sort: Some(SdzSpotSort::Rating),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(by_rating.next_page_token.is_none());
+        assert_eq!(
+            ids(by_rating),
+            vec!["high-popular", "high", "low", "unrated"]
+        );
+
+        let by_updated = use_case
+            .execute(
+                repo,
+                SdzPageRequest::first(2),
+                None,
+                SdzSpotSearchFilter {
+                    sort: Some(SdzSpotSort::Updated),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids(by_updated), vec!["unrated", "low"]);
+    }
+
+    #[tokio::test]
+    async fn list_spots_paginates_repository_sorted_results() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        for (id, name, day) in [
+            ("c", "Cパーク", 1),
+            ("a", "aパーク", 2),
+            ("b", "Ｂパーク", 3),
+            ("d", "Dパーク", 4),
+        ] {
+            let mut spot = sample_spot(id, day);
+            spot.name = name.into();
+            repo.create(spot).await.unwrap();
+        }
+        let mut hidden = sample_spot("hidden", 5);
+        hidden.name = "Aパーク".into();
+        hidden.sdz_approval_status = None;
+        repo.create(hidden).await.unwrap();
+
+        let use_case = SdzListSpotsUseCase::new();
+        let filter = SdzSpotSearchFilter {
+            sort: Some(SdzSpotSort::Name),
+            ..Default::default()
+        };
+        assert_eq!(filter.repository_order(), Some(SdzSpotOrder::Name));
+        let mut page = SdzPageRequest::first(3);
+        let mut pages = Vec::new();
+        loop {
+            let result = use_case
+                .execute(repo.clone(), page.clone(), None, filter.clone())
+                .await
+                .unwrap();
+            pages.push(
+                result
+                    .items
+                    .into_iter()
+                    .map(|item| item.spot.sdz_spot_id)
+                    .collect::<Vec<_>>(),
+            );
+            let Some(token) = result.next_page_token else {
+                break;
+            };
+            page = SdzPageRequest::from_sorted_query(Some(3), Some(&token)).unwrap();
+        }
+        assert_eq!(pages, vec![vec!["a", "b", "c"], vec!["d"]]);
+    }
+
+    #[test]
+    fn sort_parse_and_defaults() {
+        assert_eq!(SdzSpotSort::parse("name"), Some(SdzSpotSort::Name));
+        assert_eq!(SdzSpotSort::parse("popular"), None);
+        assert_eq!(
+            SdzSpotSearchFilter::default().effective_sort(),
+            SdzSpotSort::Newest
+        );
+        let search = SdzSpotSearchFilter {
+            query: Some("レール".into()),
+            ..Default::default()
+        };
+        assert_eq!(search.effective_sort(), SdzSpotSort::Relevance);
+        let nearby = SdzSpotSearchFilter {
+            nearby: SdzSpotNearby::new(35.0, 139.0, None),
+            sort: Some(SdzSpotSort::Name),
+            ..Default::default()
+        };
+        assert_eq!(nearby.effective_sort(), SdzSpotSort::Name);
+        assert!(nearby.is_ranked());
+    }
+
+    #[tokio::test]
+    async fn list_spots_filters_open_now() {
+        use crate::domain::models::{SdzSpotBusinessHours, SdzSpotBusinessScheduleType};
+
+        let park = |schedule_type| SdzSpotParkAttributes {
+            official_url: None,
+            business_hours: Some(SdzSpotBusinessHours {
+                schedule_type: Some(schedule_type),
+                is_24_hours: true,
+                same_as_weekday: true,
+                weekday: None,
+                weekend: None,
+                note: Some("要確認".into()),
+            }),
+            access_info: None,
+            phone_number: None,
+        };
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        let mut always = sample_spot("always", 1);
+        always.sdz_park_attributes = Some(park(SdzSpotBusinessScheduleType::Regular));
+        repo.create(always).await.unwrap();
+        let mut manual = sample_spot("manual", 2);
+        manual.sdz_park_attributes = Some(park(SdzSpotBusinessScheduleType::Manual));
+        repo.create(manual).await.unwrap();
+        repo.create(sample_spot("street", 3)).await.unwrap();
+
+        let list = SdzListSpotsUseCase::new()
+            .execute(
+                repo,
+                SdzPageRequest::first(10),
+                None,
+                SdzSpotSearchFilter {
+                    open_now: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].spot.sdz_spot_id, "always");
+        assert_eq!(list.items[0].open_status.is_open_now, Some(true));
+        assert!(list.items[0].open_status.next_change_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn list_spots_filters_by_street_sections() {
+        use crate::domain::models::SdzStreetSection;
+
+        let street = |sections: Vec<(&str, Option<u16>)>, difficulty: &str| SdzStreetAttributes {
+            surface_material: Some("concrete".into()),
+            surface_condition: None,
+            sections: Some(
+                sections
+                    .into_iter()
+                    .map(|(section_type, height_cm)| SdzStreetSection {
+                        section_type: section_type.into(),
+                        count: None,
+                        height_cm,
+                        width_cm: None,
+                        notes: None,
+                    })
+                    .collect(),
+            ),
+            difficulty: Some(difficulty.into()),
+            notes: None,
+        };
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        let mut low_ledge = sample_spot("low-ledge", 1);
+        low_ledge.sdz_street_attributes = Some(street(vec![("ledge", Some(30))], "2"));
+        repo.create(low_ledge).await.unwrap();
+        let mut high_ledge = sample_spot("high-ledge", 2);
+        high_ledge.sdz_street_attributes = Some(street(
+            vec![("ledge", Some(60)), ("handrail", Some(20))],
+            "2",
+        ));
+        repo.create(high_ledge).await.unwrap();
+        let mut unknown_height = sample_spot("unknown-height", 3);
+        unknown_height.sdz_street_attributes = Some(street(vec![("ledge", None)], "4"));
+        repo.create(unknown_height).await.unwrap();
+        repo.create(sample_spot("park", 4)).await.unwrap();
+
+        let run = |street: SdzStreetFilter| {
+            let repo = repo.clone();
+            async move {
+                SdzListSpotsUseCase::new()
+                    .execute(
+                        repo,
+                        SdzPageRequest::first(10),
+                        None,
+                        SdzSpotSearchFilter {
+                            street,
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .unwrap()
+                    .items
+                    .into_iter()
+                    .map(|item| item.spot.sdz_spot_id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let ids = run(SdzStreetFilter {
+            section_type: Some("ledge".into()),
+            max_height_cm: Some(40),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec!["low-ledge"]);
+
+        let ids = run(SdzStreetFilter {
+            difficulty: Some("2".into()),
+            surface_material: Some("Concrete".into()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec!["high-ledge", "low-ledge"]);
+    }
+
+    #[tokio::test]
+    async fn list_spots_finds_old_tagged_spot_beyond_scan_window() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        for i in 0..300 {
+            repo.create(sample_spot(&format!("new-{i:03}"), 2))
+                .await
+                .unwrap();
+        }
+        let mut old = sample_spot("old", 1);
+        old.tags = vec!["レール".into()];
+        repo.create(old).await.unwrap();
+
+        let filter = SdzSpotSearchFilter {
+            tags: vec!["レール".into()],
+            ..Default::default()
+        };
+        let list = SdzListSpotsUseCase::new()
+            .execute(repo, SdzPageRequest::first(10), None, filter)
+            .await
+            .unwrap();
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].spot.sdz_spot_id, "old");
+        assert!(list.next_page_token.is_none());
+    }
+
+    #[test]
+    fn repository_query_pushes_down_supported_predicates() {
+        let filter = SdzSpotSearchFilter {
+            query: Some("ledge".into()),
+            spot_type: Some(SdzSpotTypeFilter::Street),
+            tags: vec!["レール".into()],
+            ..Default::default()
+        };
+        let query = filter.repository_query(None);
+        assert!(query.approved_only);
+        assert_eq!(query.spot_type, Some(SdzSpotTypeFilter::Street));
+        assert_eq!(query.tags_any, vec!["レール".to_string()]);
+        assert!(!filter.repository_query(Some("user")).approved_only);
+        assert!(filter.has_in_memory_predicates());
+
+        let many_tags = SdzSpotSearchFilter {
+            tags: (0..=SDZ_QUERY_MAX_TAGS).map(|i| i.to_string()).collect(),
+            ..Default::default()
+        };
+        assert!(many_tags.repository_query(None).tags_any.is_empty());
+        assert!(many_tags.has_in_memory_predicates());
+    }
 }
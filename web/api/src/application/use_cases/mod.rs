@@ -1,14 +1,30 @@
 pub mod add_mylist_use_case;
+pub mod archive_spot_use_case;
+pub mod bulk_upsert_spots_use_case;
+pub mod cluster_spots_use_case;
 pub mod create_spot_use_case;
+pub mod delete_spot_use_case;
+pub mod export_mylist_use_case;
+pub mod export_spots_geojson_use_case;
+pub mod find_duplicate_spots_use_case;
 pub mod generate_upload_url_use_case;
 pub mod get_current_user_use_case;
 pub mod get_spot_use_case;
 pub mod health_check_use_case;
+pub mod import_spots_use_case;
+pub mod list_archived_spots_use_case;
 pub mod list_mylist_use_case;
+pub mod list_spot_revisions_use_case;
 pub mod list_spots_use_case;
 pub mod mylist_repository;
+pub mod pagination;
 pub mod remove_mylist_use_case;
+pub mod restore_spot_use_case;
+pub mod revert_spot_use_case;
+pub mod spot_facets_use_case;
 pub mod spot_repository;
+pub mod spot_revision_repository;
+pub mod spot_view;
 pub mod storage_repository;
 pub mod update_spot_use_case;
 pub mod user_repository;
@@ -8,4 +8,6 @@ pub trait SdzMyListRepository: Send + Sync {
     async fn list_by_user(&self, user_id: &str) -> Result<Vec<SdzMyListEntry>, SdzApiError>;
     async fn add(&self, user_id: &str, spot_id: &str) -> Result<(), SdzApiError>;
     async fn remove(&self, user_id: &str, spot_id: &str) -> Result<(), SdzApiError>;
+    /// 全ユーザーのマイリストから指定スポットを取り除き、削除件数を返す
+    async fn remove_spot_from_all(&self, spot_id: &str) -> Result<usize, SdzApiError>;
 }
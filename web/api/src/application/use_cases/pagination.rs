@@ -0,0 +1,314 @@
+use std::cmp::Ordering;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::models::SdzSpot, presentation::error::SdzApiError};
+
+pub const SDZ_PAGE_DEFAULT_LIMIT: usize = 50;
+pub const SDZ_PAGE_MAX_LIMIT: usize = 100;
+
+/// 一覧レスポンスの共通形式
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzPage<T> {
+    pub items: Vec<T>,
+    #[serde(rename = "nextPageToken", skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    /// 走査件数の上限に達し、条件に合う要素を取りこぼしている可能性がある場合にtrue
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// 新着順（createdAt降順・spotId降順）の一覧で、直前ページ末尾の要素を指すカーソル
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdzPageCursor {
+    pub created_at: DateTime<FixedOffset>,
+    pub sdz_spot_id: String,
+}
+
+impl SdzPageCursor {
+    pub fn new(created_at: DateTime<FixedOffset>, sdz_spot_id: &str) -> Self {
+        Self {
+            created_at,
+            sdz_spot_id: sdz_spot_id.to_string(),
+        }
+    }
+
+    pub fn from_spot(spot: &SdzSpot) -> Self {
+        Self::new(spot.created_at, &spot.sdz_spot_id)
+    }
+
+    /// 指定の要素がカーソルより後ろ（次ページ側）に並ぶならtrue
+    pub fn comes_after(&self, created_at: &DateTime<FixedOffset>, sdz_spot_id: &str) -> bool {
+        sdz_cmp_newest_first(
+            (created_at, sdz_spot_id),
+            (&self.created_at, &self.sdz_spot_id),
+        ) == Ordering::Greater
+    }
+
+    /// クライアントには中身を意識させない不透明なトークンとして渡す
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}|{}",
+            self.created_at.to_rfc3339(),
+            self.sdz_spot_id
+        ))
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        let raw = URL_SAFE_NO_PAD.decode(token.trim()).ok()?;
+        let raw = String::from_utf8(raw).ok()?;
+        let (created_at, spot_id) = raw.split_once('|')?;
+        if spot_id.is_empty() {
+            return None;
+        }
+        let created_at = DateTime::parse_from_rfc3339(created_at).ok()?;
+        Some(Self::new(created_at, spot_id))
+    }
+}
+
+/// 新着順以外の並び順で比較するキー
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "sort", rename_all = "camelCase")]
+pub enum SdzSortKey {
+    /// 関連度の高い順、同点は新しい順
+    Relevance {
+        score: u32,
+        created_at: DateTime<FixedOffset>,
+    },
+    /// 検索用に正規化した名前の昇順
+    Name { name: String },
+    /// 評価の高い順（評価なしは末尾）、同評価は件数の多い順
+    Rating { rating: Option<f64>, count: u32 },
+    /// 更新の新しい順
+    Updated { updated_at: DateTime<FixedOffset> },
+    /// 距離の近い順
+    Distance { distance_m: f64 },
+}
+
+impl SdzSortKey {
+    /// 同順位をspotIdの昇順で並べるならtrue（それ以外は降順）
+    fn ascending_ids(&self) -> bool {
+        matches!(self, Self::Name { .. } | Self::Distance { .. })
+    }
+}
+
+/// 並び順のキーで比較し、同順位はspotIdで順序を安定させる
+pub fn sdz_cmp_sort_keys(a: (&SdzSortKey, &str), b: (&SdzSortKey, &str)) -> Ordering {
+    let primary = match (a.0, b.0) {
+        (
+            SdzSortKey::Relevance {
+                score: a_score,
+                created_at: a_created,
+            },
+            SdzSortKey::Relevance {
+                score: b_score,
+                created_at: b_created,
+            },
+        ) => b_score.cmp(a_score).then_with(|| b_created.cmp(a_created)),
+        (SdzSortKey::Name { name: a }, SdzSortKey::Name { name: b }) => a.cmp(b),
+        (
+            SdzSortKey::Rating {
+                rating: a_rating,
+                count: a_count,
+            },
+            SdzSortKey::Rating {
+                rating: b_rating,
+                count: b_count,
+            },
+        ) => cmp_none_last(*a_rating, *b_rating, |a, b| b.total_cmp(&a))
+            .then_with(|| b_count.cmp(a_count)),
+        (SdzSortKey::Updated { updated_at: a }, SdzSortKey::Updated { updated_at: b }) => b.cmp(a),
+        (SdzSortKey::Distance { distance_m: a }, SdzSortKey::Distance { distance_m: b }) => {
+            a.total_cmp(b)
+        }
+        _ => Ordering::Equal,
+    };
+    primary.then_with(|| {
+        if a.0.ascending_ids() {
+            a.1.cmp(b.1)
+        } else {
+            b.1.cmp(a.1)
+        }
+    })
+}
+
+fn cmp_none_last<T: Copy>(a: Option<T>, b: Option<T>, cmp: impl Fn(T, T) -> Ordering) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => cmp(a, b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// 新着順以外の一覧で、直前ページ末尾の要素を指すカーソル
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SdzSortCursor {
+    pub key: SdzSortKey,
+    #[serde(rename = "spotId")]
+    pub sdz_spot_id: String,
+}
+
+impl SdzSortCursor {
+    pub fn new(key: SdzSortKey, sdz_spot_id: &str) -> Self {
+        Self {
+            key,
+            sdz_spot_id: sdz_spot_id.to_string(),
+        }
+    }
+
+    /// 指定の要素がカーソルより後ろ（次ページ側）に並ぶならtrue
+    pub fn comes_after(&self, key: &SdzSortKey, sdz_spot_id: &str) -> bool {
+        sdz_cmp_sort_keys((key, sdz_spot_id), (&self.key, &self.sdz_spot_id)) == Ordering::Greater
+    }
+
+    pub fn encode(&self) -> String {
+        let raw = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        let raw = URL_SAFE_NO_PAD.decode(token.trim()).ok()?;
+        let cursor: Self = serde_json::from_slice(&raw).ok()?;
+        (!cursor.sdz_spot_id.is_empty()).then_some(cursor)
+    }
+}
+
+/// 1ページ分の取得条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdzPageRequest {
+    pub limit: usize,
+    pub after: Option<SdzPageCursor>,
+    /// 新着順以外の一覧の続き
+    pub sort_after: Option<SdzSortCursor>,
+}
+
+impl SdzPageRequest {
+    pub fn first(limit: usize) -> Self {
+        Self {
+            limit,
+            after: None,
+            sort_after: None,
+        }
+    }
+
+    /// クエリパラメータから組み立てる。limitは上限に丸め、0や不正なトークンは400とする。
+    pub fn from_query(limit: Option<usize>, page_token: Option<&str>) -> Result<Self, SdzApiError> {
+        let limit = limit.unwrap_or(SDZ_PAGE_DEFAULT_LIMIT);
+        if limit == 0 {
+            return Err(SdzApiError::BadRequest("limit must be >= 1".into()));
+        }
+        let after = page_token
+            .filter(|token| !token.trim().is_empty())
+            .map(|token| {
+                SdzPageCursor::decode(token)
+                    .ok_or_else(|| SdzApiError::BadRequest("invalid pageToken".into()))
+            })
+            .transpose()?;
+        let mut page = Self::first(limit.min(SDZ_PAGE_MAX_LIMIT));
+        page.after = after;
+        Ok(page)
+    }
+
+    /// 新着順以外の一覧用。pageTokenを`SdzSortCursor`として解釈する
+    pub fn from_sorted_query(
+        limit: Option<usize>,
+        page_token: Option<&str>,
+    ) -> Result<Self, SdzApiError> {
+        let mut page = Self::from_query(limit, None)?;
+        page.sort_after = page_token
+            .filter(|token| !token.trim().is_empty())
+            .map(|token| {
+                SdzSortCursor::decode(token)
+                    .ok_or_else(|| SdzApiError::BadRequest("invalid pageToken".into()))
+            })
+            .transpose()?;
+        Ok(page)
+    }
+}
+
+/// 新着順の比較。createdAtが同じ場合はspotIdの降順で順序を安定させる
+pub fn sdz_cmp_newest_first(
+    a: (&DateTime<FixedOffset>, &str),
+    b: (&DateTime<FixedOffset>, &str),
+) -> Ordering {
+    b.0.cmp(a.0).then_with(|| b.1.cmp(a.1))
+}
+
+pub fn sdz_sort_spots_newest_first(spots: &mut [SdzSpot]) {
+    spots.sort_by(|a, b| {
+        sdz_cmp_newest_first(
+            (&a.created_at, &a.sdz_spot_id),
+            (&b.created_at, &b.sdz_spot_id),
+        )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn jst(day: u32) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, day, 0, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        let cursor = SdzPageCursor::new(jst(2), "spot-1");
+        let decoded = SdzPageCursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(decoded, cursor);
+        assert!(SdzPageCursor::decode("not-a-token").is_none());
+    }
+
+    #[test]
+    fn cursor_orders_by_created_at_then_id() {
+        let cursor = SdzPageCursor::new(jst(2), "spot-b");
+        assert!(cursor.comes_after(&jst(1), "spot-z"));
+        assert!(cursor.comes_after(&jst(2), "spot-a"));
+        assert!(!cursor.comes_after(&jst(2), "spot-b"));
+        assert!(!cursor.comes_after(&jst(2), "spot-c"));
+        assert!(!cursor.comes_after(&jst(3), "spot-a"));
+    }
+
+    #[test]
+    fn sort_cursor_round_trip_and_order() {
+        let cursor = SdzSortCursor::new(
+            SdzSortKey::Rating {
+                rating: Some(4.2),
+                count: 10,
+            },
+            "spot-b",
+        );
+        assert_eq!(SdzSortCursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(SdzSortCursor::decode("@@@").is_none());
+
+        let rating = |rating, count| SdzSortKey::Rating { rating, count };
+        assert!(cursor.comes_after(&rating(Some(4.2), 3), "spot-z"));
+        assert!(cursor.comes_after(&rating(Some(4.2), 10), "spot-a"));
+        assert!(!cursor.comes_after(&rating(Some(4.2), 10), "spot-c"));
+        assert!(cursor.comes_after(&rating(None, 500), "spot-z"));
+        assert!(!cursor.comes_after(&rating(Some(4.5), 0), "spot-z"));
+
+        let name = SdzSortCursor::new(SdzSortKey::Name { name: "b".into() }, "spot-b");
+        assert!(name.comes_after(&SdzSortKey::Name { name: "b".into() }, "spot-c"));
+        assert!(!name.comes_after(&SdzSortKey::Name { name: "a".into() }, "spot-z"));
+    }
+
+    #[test]
+    fn page_request_from_query() {
+        let page = SdzPageRequest::from_query(None, None).unwrap();
+        assert_eq!(page.limit, SDZ_PAGE_DEFAULT_LIMIT);
+        let page = SdzPageRequest::from_query(Some(1000), None).unwrap();
+        assert_eq!(page.limit, SDZ_PAGE_MAX_LIMIT);
+        assert!(SdzPageRequest::from_query(Some(0), None).is_err());
+        assert!(SdzPageRequest::from_query(None, Some("@@@")).is_err());
+        let newest_token = SdzPageCursor::new(jst(1), "spot-1").encode();
+        assert!(SdzPageRequest::from_sorted_query(None, Some(&newest_token)).is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::spot_repository::SdzSpotRepository,
+    domain::models::{SdzSpot, SdzSpotFieldMask},
+    presentation::error::SdzApiError,
+};
+
+pub struct SdzRestoreSpotUseCase;
+
+impl SdzRestoreSpotUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        spot_id: String,
+    ) -> Result<SdzSpot, SdzApiError> {
+        let existing = repo
+            .find_by_id(&spot_id)
+            .await?
+            .ok_or(SdzApiError::NotFound)?;
+        if !existing.is_archived() {
+            return Ok(existing);
+        }
+        repo.update_if_unchanged(
+            existing.restore(),
+            &existing,
+            &SdzSpotFieldMask::archive_state(),
+        )
+        .await
+    }
+}
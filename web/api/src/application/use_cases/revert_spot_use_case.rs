@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use crate::{
+    application::use_cases::{
+        spot_repository::SdzSpotRepository,
+        spot_revision_repository::SdzSpotRevisionRepository,
+        update_spot_use_case::{SdzUpdateSpotUseCase, UpdateSpotInput},
+    },
+    domain::models::SdzSpot,
+    presentation::error::SdzApiError,
+};
+
+/// 過去の版を通常の更新と同じ検証を通して適用する。差し戻し自体も新しい版として残る
+pub struct SdzRevertSpotUseCase;
+
+impl SdzRevertSpotUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        spot_repo: Arc<dyn SdzSpotRepository>,
+        revision_repo: Arc<dyn SdzSpotRevisionRepository>,
+        admin_user_id: &str,
+        spot_id: String,
+        revision_id: &str,
+    ) -> Result<SdzSpot, SdzApiError> {
+        let revision = revision_repo
+            .find(&spot_id, revision_id)
+            .await?
+            .ok_or(SdzApiError::NotFound)?;
+        let input = UpdateSpotInput::from_snapshot(&revision.snapshot);
+        SdzUpdateSpotUseCase::for_admin()
+            .execute(
+                spot_repo,
+                revision_repo,
+                admin_user_id,
+                spot_id,
+                input,
+                None,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::use_cases::{
+            list_spot_revisions_use_case::SdzListSpotRevisionsUseCase, pagination::SdzPageRequest,
+        },
+        domain::models::{
+            SdzCreateSpotParams, SdzSpotBusinessHours, SdzSpotParkAttributes, SdzSpotTimeRange,
+        },
+        infrastructure::{
+            in_memory_spot_repository::SdzInMemorySpotRepository,
+            in_memory_spot_revision_repository::SdzInMemorySpotRevisionRepository,
+        },
+    };
+
+    fn hours(is_24_hours: bool) -> SdzSpotParkAttributes {
+        SdzSpotParkAttributes {
+            official_url: None,
+            business_hours: Some(SdzSpotBusinessHours {
+                schedule_type: None,
+                is_24_hours,
+                same_as_weekday: true,
+                weekday: (!is_24_hours).then_some(SdzSpotTimeRange {
+                    start_minutes: 9 * 60,
+                    end_minutes: 21 * 60,
+                }),
+                weekend: None,
+                note: None,
+            }),
+            access_info: None,
+            phone_number: None,
+        }
+    }
+
+    fn update_hours(is_24_hours: bool) -> UpdateSpotInput {
+        UpdateSpotInput {
+            name: None,
+            description: None,
+            location: None,
+            tags: None,
+            images: None,
+            approval_status: None,
+            park_attributes: Some(hours(is_24_hours)),
+            street_attributes: None,
+            instagram_tag: None,
+            instagram_location_url: None,
+            instagram_profile_url: None,
+            google_place_id: None,
+            google_maps_url: None,
+            address: None,
+            phone_number: None,
+            google_rating: None,
+            google_rating_count: None,
+            google_types: None,
+            clear_fields: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn revert_reapplies_old_revision_and_records_history() {
+        let spot_repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let revision_repo: Arc<dyn SdzSpotRevisionRepository> =
+            Arc::new(SdzInMemorySpotRevisionRepository::default());
+        let spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: "spot-1".into(),
+            name: "park".into(),
+            description: None,
+            location: None,
+            tags: vec!["park".into()],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap();
+        spot_repo.create(spot).await.unwrap();
+
+        for (editor, is_24_hours) in [("curator-1", true), ("curator-2", false)] {
+            SdzUpdateSpotUseCase::new()
+                .execute(
+                    spot_repo.clone(),
+                    revision_repo.clone(),
+                    editor,
+                    "spot-1".into(),
+                    update_hours(is_24_hours),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        let list_use_case = SdzListSpotRevisionsUseCase::new();
+        let list_page = |page: SdzPageRequest| {
+            list_use_case.execute(
+                spot_repo.clone(),
+                revision_repo.clone(),
+                "spot-1".into(),
+                page,
+            )
+        };
+        let latest = list_page(SdzPageRequest::first(1)).await.unwrap();
+        assert_eq!(latest.items.len(), 1);
+        assert_eq!(latest.items[0].sdz_editor_user_id, "curator-2");
+        assert_eq!(
+            latest.items[0].changed_fields,
+            vec!["parkAttributes.businessHours".to_string()]
+        );
+        let token = latest.next_page_token.unwrap();
+        let older = list_page(SdzPageRequest::from_query(Some(1), Some(&token)).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(older.items.len(), 1);
+        assert_eq!(
+            older.items[0].changed_fields,
+            vec!["parkAttributes".to_string()]
+        );
+        assert!(older.next_page_token.is_none());
+
+        let first = older.items[0].sdz_revision_id.clone();
+        let reverted = SdzRevertSpotUseCase::new()
+            .execute(
+                spot_repo,
+                revision_repo.clone(),
+                "admin-1",
+                "spot-1".into(),
+                &first,
+            )
+            .await
+            .unwrap();
+        let restored_hours = reverted
+            .sdz_park_attributes
+            .and_then(|attrs| attrs.business_hours)
+            .unwrap();
+        assert!(restored_hours.is_24_hours);
+
+        let history = revision_repo
+            .list_by_spot("spot-1", usize::MAX, None)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].sdz_editor_user_id, "admin-1");
+    }
+}
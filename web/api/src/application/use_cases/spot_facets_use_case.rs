@@ -0,0 +1,277 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde::Serialize;
+
+use crate::{
+    application::use_cases::{
+        list_spots_use_case::{sdz_fetch_matching_spots, sdz_normalize_label, SdzSpotSearchFilter},
+        spot_repository::SdzSpotRepository,
+    },
+    domain::models::{SdzSpot, SdzStreetAttributes},
+    presentation::error::SdzApiError,
+};
+
+/// ファセット集計の対象にする最大件数
+const SDZ_FACET_FETCH_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SdzFacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// 絞り込み条件に合うスポットの、値ごとの件数（1スポットは各値につき1件と数える）
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzSpotFacets {
+    pub total: usize,
+    pub tags: Vec<SdzFacetCount>,
+    #[serde(rename = "type")]
+    pub spot_type: Vec<SdzFacetCount>,
+    #[serde(rename = "sectionType")]
+    pub section_type: Vec<SdzFacetCount>,
+    pub difficulty: Vec<SdzFacetCount>,
+    /// 取得上限に達し、一部のスポットが集計されていない可能性がある
+    pub truncated: bool,
+}
+
+pub struct SdzSpotFacetsUseCase;
+
+impl SdzSpotFacetsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn execute(
+        &self,
+        repo: Arc<dyn SdzSpotRepository>,
+        viewer_user_id: Option<String>,
+        filter: SdzSpotSearchFilter,
+    ) -> Result<SdzSpotFacets, SdzApiError> {
+        let matched = sdz_fetch_matching_spots(
+            repo,
+            &filter,
+            viewer_user_id.as_deref(),
+            SDZ_FACET_FETCH_LIMIT,
+            None,
+        )
+        .await?;
+        let spots = matched.spots;
+
+        Ok(SdzSpotFacets {
+            total: spots.len(),
+            tags: count_values(&spots, |spot| spot.tags.clone()),
+            spot_type: count_values(&spots, |spot| {
+                let mut types = Vec::new();
+                if spot.is_park() {
+                    types.push("park".to_string());
+                }
+                if spot.is_street() {
+                    types.push("street".to_string());
+                }
+                types
+            }),
+            section_type: count_values(&spots, |spot| {
+                street_values(spot, |attrs| {
+                    attrs
+                        .sections
+                        .iter()
+                        .flatten()
+                        .map(|section| sdz_normalize_label(&section.section_type))
+                        .collect()
+                })
+            }),
+            difficulty: count_values(&spots, |spot| {
+                street_values(spot, |attrs| {
+                    attrs
+                        .difficulty
+                        .iter()
+                        .map(|difficulty| sdz_normalize_label(difficulty))
+                        .collect()
+                })
+            }),
+            truncated: !matched.scanned_all || matched.truncated,
+        })
+    }
+}
+
+fn street_values(
+    spot: &SdzSpot,
+    values: impl Fn(&SdzStreetAttributes) -> Vec<String>,
+) -> Vec<String> {
+    spot.sdz_street_attributes
+        .as_ref()
+        .map(values)
+        .unwrap_or_default()
+}
+
+/// 件数の多い順、同数は値の昇順
+fn count_values(spots: &[SdzSpot], values: impl Fn(&SdzSpot) -> Vec<String>) -> Vec<SdzFacetCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for spot in spots {
+        let mut spot_values: Vec<String> = values(spot)
+            .into_iter()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect();
+        spot_values.sort();
+        spot_values.dedup();
+        for value in spot_values {
+            *counts.entry(value).or_default() += 1;
+        }
+    }
+    let mut facets: Vec<SdzFacetCount> = counts
+        .into_iter()
+        .map(|(value, count)| SdzFacetCount { value, count })
+        .collect();
+    facets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::models::{
+            SdzCreateSpotParams, SdzSpotApprovalStatus, SdzStreetAttributes, SdzStreetSection,
+        },
+        infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository,
+    };
+
+    fn sample_spot(id: &str, tags: &[&str], sections: &[&str]) -> SdzSpot {
+        let mut spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            location: None,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user".into(),
+        })
+        .unwrap();
+        spot.sdz_approval_status = Some(SdzSpotApprovalStatus::Approved);
+        if !sections.is_empty() {
+            spot.sdz_street_attributes = Some(SdzStreetAttributes {
+                surface_material: None,
+                surface_condition: None,
+                sections: Some(
+                    sections
+                        .iter()
+                        .map(|section_type| SdzStreetSection {
+                            section_type: section_type.to_string(),
+                            count: None,
+                            height_cm: None,
+                            width_cm: None,
+                            notes: None,
+                        })
+                        .collect(),
+                ),
+                difficulty: Some("2".into()),
+                notes: None,
+            });
+        }
+        spot
+    }
+
+    #[tokio::test]
+    async fn facets_count_visible_spots() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        repo.create(sample_spot("a", &["パーク", "夜間"], &[]))
+            .await
+            .unwrap();
+        repo.create(sample_spot("b", &["夜間"], &["ledge", "ledge", "handrail"]))
+            .await
+            .unwrap();
+        repo.create(sample_spot("c", &[], &["ledge"]))
+            .await
+            .unwrap();
+        let mut pending = sample_spot("pending", &["夜間"], &["ledge"]);
+        pending.sdz_approval_status = Some(SdzSpotApprovalStatus::Pending);
+        repo.create(pending).await.unwrap();
+
+        let facets = SdzSpotFacetsUseCase::new()
+            .execute(repo, None, SdzSpotSearchFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(facets.total, 3);
+        assert!(!facets.truncated);
+        assert_eq!(
+            facets.tags,
+            vec![
+                SdzFacetCount {
+                    value: "夜間".into(),
+                    count: 2
+                },
+                SdzFacetCount {
+                    value: "パーク".into(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            facets.spot_type,
+            vec![
+                SdzFacetCount {
+                    value: "street".into(),
+                    count: 2
+                },
+                SdzFacetCount {
+                    value: "park".into(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(facets.section_type[0].value, "ledge");
+        assert_eq!(facets.section_type[0].count, 2);
+        assert_eq!(facets.difficulty[0].count, 2);
+    }
+
+    #[tokio::test]
+    async fn facets_group_labels_like_the_filter() {
+        let repo = Arc::new(SdzInMemorySpotRepository::default());
+        for (id, difficulty) in [("a", "Beginner"), ("b", "beginner "), ("c", "advanced")] {
+            let mut spot = sample_spot(id, &[], &["Ledge "]);
+            if let Some(attrs) = spot.sdz_street_attributes.as_mut() {
+                attrs.difficulty = Some(difficulty.into());
+            }
+            repo.create(spot).await.unwrap();
+        }
+
+        let facets = SdzSpotFacetsUseCase::new()
+            .execute(repo, None, SdzSpotSearchFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            facets.difficulty,
+            vec![
+                SdzFacetCount {
+                    value: "beginner".into(),
+                    count: 2
+                },
+                SdzFacetCount {
+                    value: "advanced".into(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            facets.section_type,
+            vec![SdzFacetCount {
+                value: "ledge".into(),
+                count: 3
+            }]
+        );
+    }
+}
@@ -1,13 +1,208 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
 
-use crate::{domain::models::SdzSpot, presentation::error::SdzApiError};
+use crate::{
+    application::use_cases::{
+        list_spots_use_case::SdzSpotTypeFilter,
+        pagination::{SdzPageCursor, SdzSortCursor, SdzSortKey},
+    },
+    domain::{
+        models::{SdzSpot, SdzSpotBounds, SdzSpotFieldMask},
+        search::sdz_normalize_search_text,
+    },
+    presentation::error::SdzApiError,
+};
+
+/// Firestoreの`array-contains-any`に渡せる値の上限
+pub const SDZ_QUERY_MAX_TAGS: usize = 30;
+/// 1回の一括保存で書き込める件数（Firestore commitの上限）
+pub const SDZ_BATCH_UPSERT_LIMIT: usize = 500;
+/// 全件を読み切るまでリポジトリから1回に取得する件数
+const SDZ_FETCH_ALL_PAGE_SIZE: usize = 500;
+
+/// リポジトリ側（Firestoreのwhere句）で評価する絞り込み条件
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SdzSpotQuery {
+    /// 承認済みのスポットのみ
+    pub approved_only: bool,
+    pub spot_type: Option<SdzSpotTypeFilter>,
+    /// いずれかのタグを含む（空なら条件なし）
+    pub tags_any: Vec<String>,
+    /// trueならアーカイブ済みのみ、falseなら公開中のみ
+    pub archived: bool,
+}
+
+impl SdzSpotQuery {
+    /// インメモリ実装や、クエリで表現できない経路での判定に使う
+    pub fn matches(&self, spot: &SdzSpot) -> bool {
+        spot.is_archived() == self.archived
+            && (!self.approved_only || spot.is_approved())
+            && match self.spot_type {
+                Some(SdzSpotTypeFilter::Park) => spot.is_park(),
+                Some(SdzSpotTypeFilter::Street) => spot.is_street(),
+                None => true,
+            }
+            && (self.tags_any.is_empty() || spot.tags.iter().any(|tag| self.tags_any.contains(tag)))
+    }
+}
+
+/// 一括保存の入力チェック。書き込みを始める前に呼び、途中で失敗しないようにする
+pub fn sdz_validate_batch_upsert(spots: &[SdzSpot]) -> Result<(), SdzApiError> {
+    if spots.len() > SDZ_BATCH_UPSERT_LIMIT {
+        return Err(SdzApiError::BadRequest(format!(
+            "batch must be <= {} spots",
+            SDZ_BATCH_UPSERT_LIMIT
+        )));
+    }
+    let mut seen = HashSet::new();
+    for spot in spots {
+        if spot.sdz_spot_id.trim().is_empty() {
+            return Err(SdzApiError::BadRequest("spotId is required".into()));
+        }
+        if !seen.insert(spot.sdz_spot_id.as_str()) {
+            return Err(SdzApiError::BadRequest(format!(
+                "duplicate spotId in batch: {}",
+                spot.sdz_spot_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 条件に合うスポットを新しい順に全件取得する（エクスポートや重複検出など、件数の上限を設けない管理系の処理用）
+pub async fn sdz_fetch_all_spots(
+    repo: &dyn SdzSpotRepository,
+    query: &SdzSpotQuery,
+) -> Result<Vec<SdzSpot>, SdzApiError> {
+    sdz_fetch_recent_spots(repo, query, usize::MAX, None).await
+}
+
+/// 条件に合うスポットを新しい順に最大`limit`件取得する。1回の取得で返る件数を超える分はページングして集める
+pub async fn sdz_fetch_recent_spots(
+    repo: &dyn SdzSpotRepository,
+    query: &SdzSpotQuery,
+    limit: usize,
+    after: Option<&SdzPageCursor>,
+) -> Result<Vec<SdzSpot>, SdzApiError> {
+    let mut spots = Vec::new();
+    let mut after = after.cloned();
+    while spots.len() < limit {
+        let page_size = (limit - spots.len()).min(SDZ_FETCH_ALL_PAGE_SIZE);
+        let page = repo.list_recent(query, page_size, after.as_ref()).await?;
+        let done = page.len() < page_size;
+        after = page.last().map(SdzPageCursor::from_spot);
+        spots.extend(page);
+        if done {
+            break;
+        }
+    }
+    Ok(spots)
+}
+
+/// リポジトリ側で並べ替える一覧の並び順。同順位はspotIdで決める
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdzSpotOrder {
+    Name,
+    Rating,
+    Updated,
+}
+
+impl SdzSpotOrder {
+    /// この並び順で比較するキー。続きのページのカーソルにも使う
+    pub fn sort_key(&self, spot: &SdzSpot) -> SdzSortKey {
+        match self {
+            Self::Name => SdzSortKey::Name {
+                name: sdz_normalize_search_text(&spot.name),
+            },
+            Self::Rating => SdzSortKey::Rating {
+                rating: spot.sdz_google_rating,
+                count: spot.sdz_google_rating_count.unwrap_or(0),
+            },
+            Self::Updated => SdzSortKey::Updated {
+                updated_at: spot.updated_at,
+            },
+        }
+    }
+}
+
+/// 読み取り時点の版を前提にした一括保存の1件
+#[derive(Debug, Clone)]
+pub struct SdzSpotVersionedWrite {
+    pub spot: SdzSpot,
+    /// `find_versioned`で読んだ版。Noneならまだ存在しないことを前提にする
+    pub expected_version: Option<String>,
+}
+
+/// 矩形検索の結果
+#[derive(Debug, Clone, Default)]
+pub struct SdzSpotsInBounds {
+    pub spots: Vec<SdzSpot>,
+    /// 走査件数の上限に達したため、矩形内の条件に合うスポットを取りこぼしている可能性がある
+    pub truncated: bool,
+}
 
 #[allow(dead_code)]
 #[async_trait]
 pub trait SdzSpotRepository: Send + Sync {
     async fn create(&self, spot: SdzSpot) -> Result<SdzSpot, SdzApiError>;
-    async fn update(&self, spot: SdzSpot) -> Result<SdzSpot, SdzApiError>;
+    /// `mask`に含まれるフィールドだけを書き込む（永続化側で管理外のフィールドは保持される）
+    async fn update(&self, spot: SdzSpot, mask: &SdzSpotFieldMask) -> Result<SdzSpot, SdzApiError>;
+    /// 複数のスポットを作成または上書きする。全件が保存されるか、1件も保存されないかのどちらか。
+    /// 件数は`SDZ_BATCH_UPSERT_LIMIT`まで
+    async fn upsert_many(&self, spots: Vec<SdzSpot>) -> Result<(), SdzApiError>;
+    /// `upsert_many`と同じく作成または上書きするが、読み取り時点の版から変わった行は書き込まない。
+    /// 書き込まなかった行のspotIdを返し、それ以外の行は保存する。件数は`SDZ_BATCH_UPSERT_LIMIT`まで
+    async fn upsert_many_if_unchanged(
+        &self,
+        writes: Vec<SdzSpotVersionedWrite>,
+    ) -> Result<Vec<String>, SdzApiError>;
+    /// 保存済みのupdated_atが`expected`と一致する場合のみ更新する。
+    /// 読み取り後に他の更新が入っていれば PreconditionFailed を返す
+    async fn update_if_unchanged(
+        &self,
+        spot: SdzSpot,
+        expected: &SdzSpot,
+        mask: &SdzSpotFieldMask,
+    ) -> Result<SdzSpot, SdzApiError>;
     async fn find_by_id(&self, spot_id: &str) -> Result<Option<SdzSpot>, SdzApiError>;
-    async fn list_recent(&self, limit: usize) -> Result<Vec<SdzSpot>, SdzApiError>;
+    /// 保存済みの版（Firestoreではドキュメントの`updateTime`）と合わせて取得する
+    async fn find_versioned(&self, spot_id: &str)
+        -> Result<Option<(SdzSpot, String)>, SdzApiError>;
+    /// アーカイブ済みを除いて取得する。管理者以外の経路で使う
+    async fn find_active_by_id(&self, spot_id: &str) -> Result<Option<SdzSpot>, SdzApiError> {
+        Ok(self
+            .find_by_id(spot_id)
+            .await?
+            .filter(|spot| !spot.is_archived()))
+    }
+    /// 存在しないIDは NotFound を返す
+    async fn delete(&self, spot_id: &str) -> Result<(), SdzApiError>;
+    /// 条件に合うスポットを新しい順（createdAt降順・spotId降順）に返す。`after`指定時はその続きから
+    async fn list_recent(
+        &self,
+        query: &SdzSpotQuery,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError>;
+    /// 条件に合うスポットを`order`の順に返す。`after`指定時はその続きから
+    async fn list_sorted(
+        &self,
+        query: &SdzSpotQuery,
+        order: SdzSpotOrder,
+        limit: usize,
+        after: Option<&SdzSortCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError>;
+    /// 矩形内に位置し条件に合うスポットを新しい順に返す（位置情報なしは含めない）
+    async fn list_in_bounds(
+        &self,
+        bounds: &SdzSpotBounds,
+        query: &SdzSpotQuery,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<SdzSpotsInBounds, SdzApiError>;
+    /// 画像URLを持つスポットが存在するか（アーカイブ済みも含む）
+    async fn has_spot_with_image(&self, image_url: &str) -> Result<bool, SdzApiError>;
+    /// 画像付きスポットの件数（アーカイブ済みは数えない）
     async fn count_image_spots_by_user(&self, user_id: &str) -> Result<usize, SdzApiError>;
 }
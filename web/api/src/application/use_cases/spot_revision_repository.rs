@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+use crate::application::use_cases::pagination::SdzPageCursor;
+use crate::domain::revision::SdzSpotRevision;
+use crate::presentation::error::SdzApiError;
+
+#[async_trait]
+pub trait SdzSpotRevisionRepository: Send + Sync {
+    async fn append(&self, revision: SdzSpotRevision) -> Result<(), SdzApiError>;
+    /// 新しい順で返す。`after`はcreatedAtと版IDのカーソルで、指定時はその続きから
+    async fn list_by_spot(
+        &self,
+        spot_id: &str,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<Vec<SdzSpotRevision>, SdzApiError>;
+    /// スポットの全版を削除し、削除件数を返す（スポットの完全削除時に使う）
+    async fn delete_by_spot(&self, spot_id: &str) -> Result<usize, SdzApiError>;
+    async fn find(
+        &self,
+        spot_id: &str,
+        revision_id: &str,
+    ) -> Result<Option<SdzSpotRevision>, SdzApiError>;
+}
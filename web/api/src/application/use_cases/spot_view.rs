@@ -0,0 +1,34 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+
+use crate::domain::{
+    business_hours::{sdz_spot_open_status, SdzOpenStatus},
+    models::SdzSpot,
+};
+
+/// 閲覧用のスポット表現。保存値に閲覧時点で算出する情報（距離・営業状況）を添える
+#[derive(Debug, Clone, Serialize)]
+pub struct SdzSpotView {
+    #[serde(flatten)]
+    pub spot: SdzSpot,
+    #[serde(rename = "distanceM", skip_serializing_if = "Option::is_none")]
+    pub distance_m: Option<f64>,
+    #[serde(flatten)]
+    pub open_status: SdzOpenStatus,
+}
+
+impl SdzSpotView {
+    pub fn new(spot: SdzSpot, at: DateTime<FixedOffset>) -> Self {
+        let open_status = sdz_spot_open_status(&spot, at);
+        Self {
+            spot,
+            distance_m: None,
+            open_status,
+        }
+    }
+
+    pub fn with_distance(mut self, distance_m: Option<f64>) -> Self {
+        self.distance_m = distance_m;
+        self
+    }
+}
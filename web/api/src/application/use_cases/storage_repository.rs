@@ -28,4 +28,8 @@ pub trait SdzStorageRepository: Send + Sync {
         &self,
         request: SdzUploadUrlRequest,
     ) -> Result<SdzUploadUrlResult, SdzApiError>;
+    /// create_upload_urlで発行したobjectUrlからオブジェクト名を取り出す。管理対象外のURLはNone
+    fn object_name_from_url(&self, object_url: &str) -> Option<String>;
+    /// オブジェクトを削除する。既に存在しないオブジェクトは何もしない
+    async fn delete_object(&self, object_name: &str) -> Result<(), SdzApiError>;
 }
@@ -1,63 +1,153 @@
 use std::sync::Arc;
 
 use crate::{
-    application::use_cases::spot_repository::SdzSpotRepository,
+    application::use_cases::{
+        create_spot_use_case::sdz_ensure_image_spot_quota, spot_repository::SdzSpotRepository,
+        spot_revision_repository::SdzSpotRevisionRepository,
+    },
     domain::models::{
-        SdzSpot, SdzSpotApprovalStatus, SdzSpotLocation, SdzSpotParkAttributes,
-        SdzSpotValidationError, SdzStreetAttributes, SdzUpdateSpotParams,
+        SdzSpot, SdzSpotApprovalStatus, SdzSpotClearableField, SdzSpotLocation,
+        SdzSpotParkAttributes, SdzSpotValidationError, SdzStreetAttributes, SdzUpdateSpotParams,
     },
+    domain::revision::SdzSpotRevision,
     presentation::error::SdzApiError,
 };
 
-pub struct SdzUpdateSpotUseCase;
+pub struct SdzUpdateSpotUseCase {
+    enforce_image_quota: bool,
+    include_archived: bool,
+}
 
 impl SdzUpdateSpotUseCase {
     pub fn new() -> Self {
-        Self
+        Self {
+            enforce_image_quota: true,
+            include_archived: false,
+        }
+    }
+
+    /// 管理者による更新。画像付きスポットの件数制限を適用せず、アーカイブ済みも編集できる
+    pub fn for_admin() -> Self {
+        Self {
+            enforce_image_quota: false,
+            include_archived: true,
+        }
     }
 
     pub async fn execute(
         &self,
         repo: Arc<dyn SdzSpotRepository>,
+        revision_repo: Arc<dyn SdzSpotRevisionRepository>,
+        editor_user_id: &str,
         spot_id: String,
         input: UpdateSpotInput,
+        if_match: Option<&str>,
     ) -> Result<SdzSpot, SdzApiError> {
-        let existing = repo
-            .find_by_id(&spot_id)
-            .await?
-            .ok_or(SdzApiError::NotFound)?;
-
-        let updated = existing
-            .update(SdzUpdateSpotParams {
-                name: input.name,
-                description: input.description,
-                location: input.location.map(|loc| SdzSpotLocation {
-                    lat: loc.lat,
-                    lng: loc.lng,
-                }),
-                tags: input.tags,
-                images: input.images,
-                sdz_approval_status: input.approval_status,
-                sdz_park_attributes: input.park_attributes,
-                sdz_street_attributes: input.street_attributes,
-                sdz_instagram_tag: input.instagram_tag,
-                sdz_instagram_location_url: input.instagram_location_url,
-                sdz_instagram_profile_url: input.instagram_profile_url,
-                sdz_google_place_id: input.google_place_id,
-                sdz_google_maps_url: input.google_maps_url,
-                sdz_address: input.address,
-                sdz_phone_number: input.phone_number,
-                sdz_google_rating: input.google_rating,
-                sdz_google_rating_count: input.google_rating_count,
-                sdz_google_types: input.google_types,
-            })
-            .map_err(map_validation_error)?;
-
-        repo.update(updated.clone()).await?;
+        let existing = if self.include_archived {
+            repo.find_by_id(&spot_id).await?
+        } else {
+            repo.find_active_by_id(&spot_id).await?
+        }
+        .ok_or(SdzApiError::NotFound)?;
+        if let Some(if_match) = if_match {
+            if !sdz_if_match_satisfied(if_match, &existing.etag()) {
+                return Err(SdzApiError::PreconditionFailed(
+                    "If-Match does not match the current spot".into(),
+                ));
+            }
+        }
+
+        let had_images = !existing.images.is_empty();
+        let params = SdzUpdateSpotParams {
+            name: input.name,
+            description: input.description,
+            location: input.location.map(|loc| SdzSpotLocation {
+                lat: loc.lat,
+                lng: loc.lng,
+            }),
+            tags: input.tags,
+            images: input.images,
+            sdz_approval_status: input.approval_status,
+            sdz_park_attributes: input.park_attributes,
+            sdz_street_attributes: input.street_attributes,
+            sdz_instagram_tag: input.instagram_tag,
+            sdz_instagram_location_url: input.instagram_location_url,
+            sdz_instagram_profile_url: input.instagram_profile_url,
+            sdz_google_place_id: input.google_place_id,
+            sdz_google_maps_url: input.google_maps_url,
+            sdz_address: input.address,
+            sdz_phone_number: input.phone_number,
+            sdz_google_rating: input.google_rating,
+            sdz_google_rating_count: input.google_rating_count,
+            sdz_google_types: input.google_types,
+            clear_fields: input.clear_fields,
+        };
+        let mask = params.field_mask();
+        let updated = existing.update(params).map_err(map_validation_error)?;
+
+        // 画像なし→画像ありに変わる場合のみ、投稿者の画像付きスポットが1件増える
+        if self.enforce_image_quota && !had_images && !updated.images.is_empty() {
+            sdz_ensure_image_spot_quota(repo.as_ref(), &updated.sdz_user_id).await?;
+        }
+        let updated = repo.update_if_unchanged(updated, &existing, &mask).await?;
+
+        // 版の記録に失敗しても更新自体は確定しているため、ログに残して成功を返す
+        let revision = SdzSpotRevision::new(&existing, &updated, editor_user_id);
+        if let Err(err) = revision_repo.append(revision).await {
+            tracing::error!(
+                spot_id = %updated.sdz_spot_id,
+                error = ?err,
+                "failed to record spot revision"
+            );
+        }
         Ok(updated)
     }
 }
 
+/// If-Matchヘッダーの判定。`*`または列挙されたETagのいずれかと一致すればよい
+fn sdz_if_match_satisfied(if_match: &str, etag: &str) -> bool {
+    if_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate == etag)
+}
+
+impl UpdateSpotInput {
+    /// 過去の版を差し戻すための入力。版の時点で未設定だった任意項目は削除指定にする
+    pub fn from_snapshot(spot: &SdzSpot) -> Self {
+        // APIのJSON表現は未設定の任意項目を出力しないため、キーの有無で判定できる
+        let snapshot = serde_json::to_value(spot).unwrap_or_default();
+        let clear_fields = SdzSpotClearableField::ALL
+            .into_iter()
+            .filter(|field| snapshot.get(field.field_name()).is_none())
+            .collect();
+        Self {
+            name: Some(spot.name.clone()),
+            description: spot.description.clone(),
+            location: spot.location.as_ref().map(|loc| UpdateSpotLocation {
+                lat: loc.lat,
+                lng: loc.lng,
+            }),
+            tags: Some(spot.tags.clone()),
+            images: Some(spot.images.clone()),
+            approval_status: spot.sdz_approval_status.clone(),
+            park_attributes: spot.sdz_park_attributes.clone(),
+            street_attributes: spot.sdz_street_attributes.clone(),
+            instagram_tag: spot.sdz_instagram_tag.clone(),
+            instagram_location_url: spot.sdz_instagram_location_url.clone(),
+            instagram_profile_url: spot.sdz_instagram_profile_url.clone(),
+            google_place_id: spot.sdz_google_place_id.clone(),
+            google_maps_url: spot.sdz_google_maps_url.clone(),
+            address: spot.sdz_address.clone(),
+            phone_number: spot.sdz_phone_number.clone(),
+            google_rating: spot.sdz_google_rating,
+            google_rating_count: spot.sdz_google_rating_count,
+            google_types: Some(spot.sdz_google_types.clone()),
+            clear_fields,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct UpdateSpotInput {
     pub name: Option<String>,
@@ -90,6 +180,9 @@ pub struct UpdateSpotInput {
     pub google_rating_count: Option<u32>,
     #[serde(rename = "googleTypes")]
     pub google_types: Option<Vec<String>>,
+    /// 削除する任意項目（例: `["description"]`）
+    #[serde(rename = "clearFields", default)]
+    pub clear_fields: Vec<SdzSpotClearableField>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -106,7 +199,14 @@ fn map_validation_error(err: SdzSpotValidationError) -> SdzApiError {
 mod tests {
     use super::*;
     use crate::domain::models::SdzCreateSpotParams;
-    use crate::infrastructure::in_memory_spot_repository::SdzInMemorySpotRepository;
+    use crate::infrastructure::{
+        in_memory_spot_repository::SdzInMemorySpotRepository,
+        in_memory_spot_revision_repository::SdzInMemorySpotRevisionRepository,
+    };
+
+    fn revisions() -> Arc<dyn SdzSpotRevisionRepository> {
+        Arc::new(SdzInMemorySpotRevisionRepository::default())
+    }
 
     async fn seed_spot(repo: &Arc<dyn SdzSpotRepository>) -> SdzSpot {
         let spot = SdzSpot::new_with_id(SdzCreateSpotParams {
@@ -160,16 +260,130 @@ mod tests {
             google_rating: None,
             google_rating_count: None,
             google_types: None,
+            clear_fields: vec![],
         };
 
         let result = use_case
-            .execute(repo.clone(), "spot-1".into(), input)
+            .execute(
+                repo.clone(),
+                revisions(),
+                "user-1",
+                "spot-1".into(),
+                input,
+                None,
+            )
             .await
             .unwrap();
         assert_eq!(result.name, "updated name");
         assert_eq!(result.description, Some("desc".into()));
     }
 
+    #[tokio::test]
+    async fn update_spot_adding_images_enforces_quota() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let seeded = seed_spot(&repo).await;
+        for i in 0..crate::domain::models::SDZ_MAX_IMAGE_SPOTS_PER_USER {
+            let mut spot = seeded.clone();
+            spot.sdz_spot_id = format!("image-{i}");
+            spot.images = vec!["https://example.com/a.jpg".into()];
+            repo.create(spot).await.unwrap();
+        }
+
+        let input = UpdateSpotInput {
+            name: None,
+            description: None,
+            location: None,
+            tags: None,
+            images: Some(vec!["https://example.com/b.jpg".into()]),
+            approval_status: None,
+            park_attributes: None,
+            street_attributes: None,
+            instagram_tag: None,
+            instagram_location_url: None,
+            instagram_profile_url: None,
+            google_place_id: None,
+            google_maps_url: None,
+            address: None,
+            phone_number: None,
+            google_rating: None,
+            google_rating_count: None,
+            google_types: None,
+            clear_fields: vec![],
+        };
+        let err = SdzUpdateSpotUseCase::new()
+            .execute(
+                repo.clone(),
+                revisions(),
+                "user-1",
+                "spot-1".into(),
+                input.clone(),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdzApiError::QuotaExceeded(_)));
+
+        // 既に画像付きのスポットの差し替えは件数が増えないので許可する
+        SdzUpdateSpotUseCase::new()
+            .execute(repo, revisions(), "user-1", "image-0".into(), input, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn update_spot_rejects_stale_if_match() {
+        let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
+        let seeded = seed_spot(&repo).await;
+
+        let input = UpdateSpotInput {
+            name: Some("curator edit".into()),
+            description: None,
+            location: None,
+            tags: None,
+            images: None,
+            approval_status: None,
+            park_attributes: None,
+            street_attributes: None,
+            instagram_tag: None,
+            instagram_location_url: None,
+            instagram_profile_url: None,
+            google_place_id: None,
+            google_maps_url: None,
+            address: None,
+            phone_number: None,
+            google_rating: None,
+            google_rating_count: None,
+            google_types: None,
+            clear_fields: vec![],
+        };
+        let err = SdzUpdateSpotUseCase::new()
+            .execute(
+                repo.clone(),
+                revisions(),
+                "user-1",
+                "spot-1".into(),
+                input.clone(),
+                Some("\"1\""),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdzApiError::PreconditionFailed(_)));
+
+        let current = format!("\"0\", {}", seeded.etag());
+        let updated = SdzUpdateSpotUseCase::new()
+            .execute(
+                repo,
+                revisions(),
+                "user-1",
+                "spot-1".into(),
+                input,
+                Some(&current),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.name, "curator edit");
+    }
+
     #[tokio::test]
     async fn update_spot_not_found() {
         let repo: Arc<dyn SdzSpotRepository> = Arc::new(SdzInMemorySpotRepository::default());
@@ -194,10 +408,18 @@ mod tests {
             google_rating: None,
             google_rating_count: None,
             google_types: None,
+            clear_fields: vec![],
         };
 
         let err = use_case
-            .execute(repo, "nonexistent".into(), input)
+            .execute(
+                repo,
+                revisions(),
+                "user-1",
+                "nonexistent".into(),
+                input,
+                None,
+            )
             .await
             .unwrap_err();
         assert!(matches!(err, SdzApiError::NotFound));
@@ -0,0 +1,55 @@
+//! sdz-import / sdz-export / sdz-backfill で共通の初期化処理
+
+// バイナリごとに使う関数が異なるため、使わない関数の警告は抑止する
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use sdz_api::{
+    application::use_cases::{
+        spot_repository::SdzSpotRepository, spot_revision_repository::SdzSpotRevisionRepository,
+    },
+    infrastructure::{
+        firestore_client::SdzFirestoreClient,
+        firestore_spot_repository::SdzFirestoreSpotRepository,
+        firestore_spot_revision_repository::SdzFirestoreSpotRevisionRepository,
+    },
+};
+
+/// `.env`を読み込み、ログは標準エラー出力に出す（標準出力は結果の出力に使う）
+pub fn sdz_cli_init() {
+    let _ = dotenvy::dotenv();
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "warn".into()),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+}
+
+/// APIサーバーと同じ環境変数でFirestoreに接続する（インメモリへのフォールバックはしない）
+pub fn sdz_cli_spot_repo() -> Result<Arc<dyn SdzSpotRepository>, String> {
+    Ok(Arc::new(sdz_cli_firestore_spot_repo()?))
+}
+
+/// Firestore固有の保守操作（非正規化フィールドの補完など）用
+pub fn sdz_cli_firestore_spot_repo() -> Result<SdzFirestoreSpotRepository, String> {
+    Ok(SdzFirestoreSpotRepository::new(sdz_cli_firestore_client()?))
+}
+
+/// 取り込みで上書きしたスポットの版を記録する
+pub fn sdz_cli_spot_revision_repo() -> Result<Arc<dyn SdzSpotRevisionRepository>, String> {
+    Ok(Arc::new(SdzFirestoreSpotRevisionRepository::new(
+        sdz_cli_firestore_client()?,
+    )))
+}
+
+fn sdz_cli_firestore_client() -> Result<Arc<SdzFirestoreClient>, String> {
+    let project_id = std::env::var("SDZ_FIRESTORE_PROJECT_ID")
+        .or_else(|_| std::env::var("SDZ_AUTH_PROJECT_ID"))
+        .map_err(|_| "SDZ_FIRESTORE_PROJECT_ID or SDZ_AUTH_PROJECT_ID is required".to_string())?;
+    let token = std::env::var("SDZ_FIRESTORE_TOKEN").ok();
+    let client = SdzFirestoreClient::new(project_id, token)
+        .map_err(|e| format!("failed to init Firestore client: {}", e))?;
+    Ok(Arc::new(client))
+}
@@ -0,0 +1,77 @@
+//! Firestoreの`spots`に保存している非正規化フィールド（geohashなど検索用の導出値）を、
+//! それらの導入前に保存されたドキュメントへ後付けする。手順は`docs/spot_backfill.md`を参照。
+//!
+//! ```text
+//! sdz-backfill [--dry-run]
+//! ```
+
+mod common;
+
+use std::process::ExitCode;
+
+use common::{sdz_cli_firestore_spot_repo, sdz_cli_init};
+
+const SDZ_BACKFILL_USAGE: &str = "usage: sdz-backfill [--dry-run]";
+
+fn parse_args() -> Result<bool, String> {
+    let mut dry_run = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "-h" | "--help" => return Err(SDZ_BACKFILL_USAGE.to_string()),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    Ok(dry_run)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    sdz_cli_init();
+
+    let dry_run = match parse_args() {
+        Ok(dry_run) => dry_run,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+    match run(dry_run).await {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(dry_run: bool) -> Result<ExitCode, String> {
+    let repo = sdz_cli_firestore_spot_repo()?;
+    let report = repo
+        .backfill_derived_fields(dry_run)
+        .await
+        .map_err(|e| e.to_string())?;
+    for name in &report.skipped {
+        eprintln!("skipped (unreadable): {}", name);
+    }
+    for name in &report.conflicted {
+        eprintln!("conflicted (modified during backfill): {}", name);
+    }
+    println!(
+        "scanned: {}, {}: {}, skipped: {}, conflicted: {}",
+        report.scanned,
+        if dry_run { "to update" } else { "updated" },
+        report.updated,
+        report.skipped.len(),
+        report.conflicted.len()
+    );
+    if dry_run {
+        println!("dry run: nothing was written");
+    }
+    // 読み取れなかった・競合したドキュメントが残っていれば、再実行や手当てが要ることを終了コードで知らせる
+    if report.skipped.is_empty() && report.conflicted.is_empty() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
@@ -0,0 +1,98 @@
+//! Firestoreのスポットを外部向けの形式で書き出す。
+//!
+//! ```text
+//! sdz-export geojson [--output <PATH>]
+//! ```
+//!
+//! `geojson`は`GET /sdz/spots.geojson`と同じ内容を出力し、位置情報のないスポットを標準エラー出力に列挙する。
+
+mod common;
+
+use std::{io::Write, process::ExitCode};
+
+use common::{sdz_cli_init, sdz_cli_spot_repo};
+use sdz_api::application::use_cases::export_spots_geojson_use_case::SdzExportSpotsGeoJsonUseCase;
+
+const SDZ_EXPORT_USAGE: &str = "usage: sdz-export geojson [--output <PATH>]";
+
+enum SdzExportCommand {
+    GeoJson { output: Option<String> },
+}
+
+fn parse_args() -> Result<SdzExportCommand, String> {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("geojson") => {
+            let mut output = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--output" | "-o" => {
+                        output = Some(
+                            args.next()
+                                .ok_or_else(|| "--output requires a value".to_string())?,
+                        )
+                    }
+                    _ => return Err(format!("unexpected argument: {}", arg)),
+                }
+            }
+            Ok(SdzExportCommand::GeoJson { output })
+        }
+        Some(other) if other != "-h" && other != "--help" => Err(format!(
+            "unknown subcommand: {}\n{}",
+            other, SDZ_EXPORT_USAGE
+        )),
+        _ => Err(SDZ_EXPORT_USAGE.to_string()),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    sdz_cli_init();
+    let command = match parse_args() {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+    let result = match command {
+        SdzExportCommand::GeoJson { output } => export_geojson(output).await,
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn export_geojson(output: Option<String>) -> Result<(), String> {
+    let repo = sdz_cli_spot_repo()?;
+    let collection = SdzExportSpotsGeoJsonUseCase::new()
+        .execute(repo)
+        .await
+        .map_err(|e| e.to_string())?;
+    let body = serde_json::to_vec_pretty(&collection).map_err(|e| e.to_string())?;
+    match &output {
+        Some(path) => {
+            std::fs::write(path, &body).map_err(|e| format!("failed to write {}: {}", path, e))?
+        }
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout
+                .write_all(&body)
+                .and_then(|_| stdout.write_all(b"\n"))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    for spot in &collection.unlocated {
+        eprintln!("unlocated: {}\t{}", spot.sdz_spot_id, spot.name);
+    }
+    eprintln!(
+        "features: {}, unlocated: {}",
+        collection.features.len(),
+        collection.unlocated.len()
+    );
+    Ok(())
+}
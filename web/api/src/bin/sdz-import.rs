@@ -0,0 +1,126 @@
+//! Tier 1 マスターデータ（CSV）をFirestoreの`spots`に取り込む。
+//! レイアウトと手順は`docs/spot_csv_import.md`を参照。
+//!
+//! ```text
+//! sdz-import <CSV_PATH> [--dry-run] [--user-id <USER_ID>]
+//! ```
+
+mod common;
+
+use std::process::ExitCode;
+
+use common::{sdz_cli_init, sdz_cli_spot_repo, sdz_cli_spot_revision_repo};
+use sdz_api::{
+    application::use_cases::import_spots_use_case::{SdzImportSpotsUseCase, SdzSpotImportChange},
+    infrastructure::spot_csv::sdz_parse_spot_csv,
+};
+
+/// `userId`列が空の新規スポットの登録者
+const SDZ_IMPORT_DEFAULT_USER_ID: &str = "sdz-master-import";
+
+const SDZ_IMPORT_USAGE: &str = "usage: sdz-import <CSV_PATH> [--dry-run] [--user-id <USER_ID>]";
+
+struct SdzImportArgs {
+    csv_path: String,
+    dry_run: bool,
+    user_id: String,
+}
+
+fn parse_args() -> Result<SdzImportArgs, String> {
+    let mut csv_path = None;
+    let mut dry_run = false;
+    let mut user_id = SDZ_IMPORT_DEFAULT_USER_ID.to_string();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--user-id" => {
+                user_id = args
+                    .next()
+                    .ok_or_else(|| "--user-id requires a value".to_string())?
+            }
+            "-h" | "--help" => return Err(SDZ_IMPORT_USAGE.to_string()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option: {}", arg)),
+            _ if csv_path.is_none() => csv_path = Some(arg),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    Ok(SdzImportArgs {
+        csv_path: csv_path.ok_or_else(|| SDZ_IMPORT_USAGE.to_string())?,
+        dry_run,
+        user_id,
+    })
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    sdz_cli_init();
+
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+    match run(args).await {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(args: SdzImportArgs) -> Result<ExitCode, String> {
+    let file = std::fs::File::open(&args.csv_path)
+        .map_err(|e| format!("failed to open {}: {}", args.csv_path, e))?;
+    let import = sdz_parse_spot_csv(file, &args.user_id).map_err(|e| e.to_string())?;
+    for error in &import.errors {
+        eprintln!("{}", error);
+    }
+
+    let repo = sdz_cli_spot_repo()?;
+    let use_case = SdzImportSpotsUseCase::new();
+    let plan = use_case
+        .plan(repo.clone(), import.spots)
+        .await
+        .map_err(|e| e.to_string())?;
+    for entry in &plan.entries {
+        let spot = &entry.spot;
+        match &entry.change {
+            SdzSpotImportChange::Added => println!("+ {}\t{}", spot.sdz_spot_id, spot.name),
+            SdzSpotImportChange::Changed(fields) => println!(
+                "~ {}\t{}\t({})",
+                spot.sdz_spot_id,
+                spot.name,
+                fields.join(", ")
+            ),
+            SdzSpotImportChange::Unchanged => println!("= {}\t{}", spot.sdz_spot_id, spot.name),
+        }
+    }
+    println!(
+        "added: {}, changed: {}, unchanged: {}, errors: {}",
+        plan.count(|c| matches!(c, SdzSpotImportChange::Added)),
+        plan.count(|c| matches!(c, SdzSpotImportChange::Changed(_))),
+        plan.count(|c| matches!(c, SdzSpotImportChange::Unchanged)),
+        import.errors.len()
+    );
+
+    // 一部の行だけ反映されるとCSVとストアの対応が追えなくなるため、エラーが1件でもあれば書き込まない
+    if !import.errors.is_empty() {
+        eprintln!("not writing: fix the rows above and re-run");
+        return Ok(ExitCode::FAILURE);
+    }
+    if args.dry_run {
+        println!("dry run: nothing was written");
+        return Ok(ExitCode::SUCCESS);
+    }
+    let revision_repo = sdz_cli_spot_revision_repo()?;
+    let written = use_case
+        .apply(repo, revision_repo, &args.user_id, &plan)
+        .await
+        .map_err(|e| e.to_string())?;
+    println!("wrote {} spots", written);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,263 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Weekday};
+use serde::Serialize;
+
+use crate::domain::models::{
+    SdzSpot, SdzSpotBusinessHours, SdzSpotBusinessScheduleType, SdzSpotTimeRange,
+};
+
+const SDZ_MINUTES_PER_DAY: u16 = 24 * 60;
+/// 次の切り替わり時刻を探す日数
+const SDZ_NEXT_CHANGE_HORIZON_DAYS: i64 = 8;
+
+/// 営業状況。`is_open_now`がNoneなら判定不能（不定期・学校開放・手動管理、営業時間未登録）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SdzOpenStatus {
+    #[serde(rename = "isOpenNow")]
+    pub is_open_now: Option<bool>,
+    #[serde(rename = "nextChangeAt", skip_serializing_if = "Option::is_none")]
+    pub next_change_at: Option<DateTime<FixedOffset>>,
+}
+
+impl SdzOpenStatus {
+    pub fn unknown() -> Self {
+        Self {
+            is_open_now: None,
+            next_change_at: None,
+        }
+    }
+}
+
+enum DayHours {
+    Open(u16, u16),
+    Closed,
+    Unknown,
+}
+
+/// スポットの営業状況を評価する（パークの営業時間のみが対象）
+pub fn sdz_spot_open_status(spot: &SdzSpot, at: DateTime<FixedOffset>) -> SdzOpenStatus {
+    spot.sdz_park_attributes
+        .as_ref()
+        .and_then(|attrs| attrs.business_hours.as_ref())
+        .map(|hours| sdz_evaluate_business_hours(hours, at))
+        .unwrap_or_else(SdzOpenStatus::unknown)
+}
+
+/// 指定時刻の営業状況と、次に開閉が切り替わる時刻をJSTで評価する。
+/// 土日を週末として扱い、祝日は考慮しない。
+pub fn sdz_evaluate_business_hours(
+    hours: &SdzSpotBusinessHours,
+    at: DateTime<FixedOffset>,
+) -> SdzOpenStatus {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let at = at.with_timezone(&jst);
+    let today = at.date_naive();
+
+    // 日をまたいで連続する営業時間（24時間営業など）は1つの区間にまとめる。
+    // 営業時間が不明な日（週末の時間が未登録など）があれば、その前日までで切り替わりを探す
+    let mut intervals: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)> = Vec::new();
+    let mut horizon_days = SDZ_NEXT_CHANGE_HORIZON_DAYS;
+    for offset in 0..SDZ_NEXT_CHANGE_HORIZON_DAYS {
+        let date = today + Duration::days(offset);
+        let (start, end) = match day_hours(hours, date) {
+            DayHours::Unknown if offset == 0 => return SdzOpenStatus::unknown(),
+            DayHours::Unknown => {
+                horizon_days = offset;
+                break;
+            }
+            DayHours::Closed => continue,
+            DayHours::Open(start, end) => {
+                (at_minutes(&jst, date, start), at_minutes(&jst, date, end))
+            }
+        };
+        match intervals.last_mut() {
+            Some(last) if last.1 == start => last.1 = end,
+            _ => intervals.push((start, end)),
+        }
+    }
+    let horizon_end = at_minutes(&jst, today + Duration::days(horizon_days), 0);
+
+    if let Some(&(_, end)) = intervals
+        .iter()
+        .find(|(start, end)| *start <= at && at < *end)
+    {
+        return SdzOpenStatus {
+            is_open_now: Some(true),
+            next_change_at: (end < horizon_end).then_some(end),
+        };
+    }
+    SdzOpenStatus {
+        is_open_now: Some(false),
+        next_change_at: intervals
+            .iter()
+            .map(|(start, _)| *start)
+            .find(|start| *start > at),
+    }
+}
+
+fn day_hours(hours: &SdzSpotBusinessHours, date: NaiveDate) -> DayHours {
+    let is_weekend = matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+    let range = |range: Option<&SdzSpotTimeRange>| match range {
+        Some(range) if range.start_minutes < range.end_minutes => DayHours::Open(
+            range.start_minutes,
+            range.end_minutes.min(SDZ_MINUTES_PER_DAY),
+        ),
+        _ => DayHours::Unknown,
+    };
+    let schedule_type = hours
+        .schedule_type
+        .clone()
+        .unwrap_or(SdzSpotBusinessScheduleType::Regular);
+
+    match schedule_type {
+        SdzSpotBusinessScheduleType::Regular => {
+            if hours.is_24_hours {
+                DayHours::Open(0, SDZ_MINUTES_PER_DAY)
+            } else if !is_weekend || hours.same_as_weekday {
+                range(hours.weekday.as_ref())
+            } else {
+                range(hours.weekend.as_ref())
+            }
+        }
+        SdzSpotBusinessScheduleType::WeekdayOnly => {
+            if is_weekend {
+                DayHours::Closed
+            } else if hours.is_24_hours {
+                DayHours::Open(0, SDZ_MINUTES_PER_DAY)
+            } else {
+                range(hours.weekday.as_ref())
+            }
+        }
+        SdzSpotBusinessScheduleType::WeekendOnly => {
+            if !is_weekend {
+                DayHours::Closed
+            } else if hours.is_24_hours {
+                DayHours::Open(0, SDZ_MINUTES_PER_DAY)
+            } else {
+                range(hours.weekend.as_ref())
+            }
+        }
+        SdzSpotBusinessScheduleType::Irregular
+        | SdzSpotBusinessScheduleType::SchoolOnly
+        | SdzSpotBusinessScheduleType::Manual => DayHours::Unknown,
+    }
+}
+
+fn at_minutes(jst: &FixedOffset, date: NaiveDate, minutes: u16) -> DateTime<FixedOffset> {
+    jst.from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+        .unwrap()
+        + Duration::minutes(minutes as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jst(day: u32, hour: u32, minute: u32) -> DateTime<FixedOffset> {
+        // 2024-01-01は月曜日
+        FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn hours(
+        schedule_type: SdzSpotBusinessScheduleType,
+        is_24_hours: bool,
+        weekday: Option<(u16, u16)>,
+        weekend: Option<(u16, u16)>,
+    ) -> SdzSpotBusinessHours {
+        let to_range = |(start_minutes, end_minutes)| SdzSpotTimeRange {
+            start_minutes,
+            end_minutes,
+        };
+        SdzSpotBusinessHours {
+            schedule_type: Some(schedule_type),
+            is_24_hours,
+            same_as_weekday: weekend.is_none(),
+            weekday: weekday.map(to_range),
+            weekend: weekend.map(to_range),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn regular_hours_open_and_closed() {
+        let hours = hours(
+            SdzSpotBusinessScheduleType::Regular,
+            false,
+            Some((9 * 60, 21 * 60)),
+            Some((10 * 60, 18 * 60)),
+        );
+        let status = sdz_evaluate_business_hours(&hours, jst(1, 12, 0));
+        assert_eq!(status.is_open_now, Some(true));
+        assert_eq!(status.next_change_at, Some(jst(1, 21, 0)));
+
+        let status = sdz_evaluate_business_hours(&hours, jst(1, 22, 0));
+        assert_eq!(status.is_open_now, Some(false));
+        assert_eq!(status.next_change_at, Some(jst(2, 9, 0)));
+
+        // 金曜の閉店後は土曜の週末時間で開く
+        let status = sdz_evaluate_business_hours(&hours, jst(5, 21, 30));
+        assert_eq!(status.next_change_at, Some(jst(6, 10, 0)));
+    }
+
+    #[test]
+    fn missing_weekend_hours_only_affect_weekends() {
+        let mut hours = hours(
+            SdzSpotBusinessScheduleType::Regular,
+            false,
+            Some((9 * 60, 21 * 60)),
+            None,
+        );
+        hours.same_as_weekday = false;
+
+        // 金曜の営業中は判定でき、閉店時刻も返す
+        let status = sdz_evaluate_business_hours(&hours, jst(5, 12, 0));
+        assert_eq!(status.is_open_now, Some(true));
+        assert_eq!(status.next_change_at, Some(jst(5, 21, 0)));
+        // 金曜の閉店後は、次の開店が不明な週末をまたぐため切り替わり時刻を返さない
+        let status = sdz_evaluate_business_hours(&hours, jst(5, 22, 0));
+        assert_eq!(status.is_open_now, Some(false));
+        assert_eq!(status.next_change_at, None);
+        // 土曜は判定不能
+        assert_eq!(
+            sdz_evaluate_business_hours(&hours, jst(6, 12, 0)),
+            SdzOpenStatus::unknown()
+        );
+    }
+
+    #[test]
+    fn weekday_only_24_hours_closes_on_saturday() {
+        let hours = hours(SdzSpotBusinessScheduleType::WeekdayOnly, true, None, None);
+        let status = sdz_evaluate_business_hours(&hours, jst(3, 3, 0));
+        assert_eq!(status.is_open_now, Some(true));
+        assert_eq!(status.next_change_at, Some(jst(6, 0, 0)));
+
+        let status = sdz_evaluate_business_hours(&hours, jst(7, 12, 0));
+        assert_eq!(status.is_open_now, Some(false));
+        assert_eq!(status.next_change_at, Some(jst(8, 0, 0)));
+    }
+
+    #[test]
+    fn always_open_has_no_next_change() {
+        let hours = hours(SdzSpotBusinessScheduleType::Regular, true, None, None);
+        let status = sdz_evaluate_business_hours(&hours, jst(1, 0, 0));
+        assert_eq!(status.is_open_now, Some(true));
+        assert!(status.next_change_at.is_none());
+    }
+
+    #[test]
+    fn manual_schedules_are_unknown() {
+        for schedule_type in [
+            SdzSpotBusinessScheduleType::Manual,
+            SdzSpotBusinessScheduleType::Irregular,
+            SdzSpotBusinessScheduleType::SchoolOnly,
+        ] {
+            let hours = hours(schedule_type, false, Some((540, 1260)), None);
+            assert_eq!(
+                sdz_evaluate_business_hours(&hours, jst(1, 12, 0)),
+                SdzOpenStatus::unknown()
+            );
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::domain::{geo::sdz_distance_m, models::SdzSpot, search::sdz_normalize_search_text};
+
+/// これより離れた2地点は、Place IDか住所が一致しない限り候補にしない
+pub const SDZ_DEDUP_MAX_DISTANCE_M: f64 = 300.0;
+const SDZ_METERS_PER_DEGREE_LAT: f64 = 111_320.0;
+
+/// 各シグナルの重み。両方のスポットが値を持つシグナルだけで加重平均する
+const SDZ_DEDUP_WEIGHT_DISTANCE: f64 = 0.3;
+const SDZ_DEDUP_WEIGHT_NAME: f64 = 0.3;
+const SDZ_DEDUP_WEIGHT_PLACE_ID: f64 = 0.3;
+const SDZ_DEDUP_WEIGHT_ADDRESS: f64 = 0.1;
+
+/// 重複候補ペアの評価。`score`は0.0〜1.0で、1.0に近いほど同じスポットらしい
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SdzDuplicateScore {
+    pub score: f64,
+    #[serde(rename = "distanceM", skip_serializing_if = "Option::is_none")]
+    pub distance_m: Option<f64>,
+    #[serde(rename = "nameSimilarity")]
+    pub name_similarity: f64,
+    /// 両方にPlace IDがあるときのみ。異なるPlace IDは別スポットの強い根拠になる
+    #[serde(rename = "samePlaceId", skip_serializing_if = "Option::is_none")]
+    pub same_place_id: Option<bool>,
+    #[serde(rename = "sameAddress", skip_serializing_if = "Option::is_none")]
+    pub same_address: Option<bool>,
+}
+
+/// 2スポットを採点する。名前だけが似ているペアは無関係なことが多いため、
+/// 近接・Place ID一致・住所一致のいずれも満たさなければ対象外（None）
+pub fn sdz_score_duplicate(a: &SdzSpot, b: &SdzSpot) -> Option<SdzDuplicateScore> {
+    let distance_m = match (&a.location, &b.location) {
+        (Some(a), Some(b)) => Some(sdz_distance_m(a, b)),
+        _ => None,
+    };
+    let same_place_id = match (
+        non_empty(a.sdz_google_place_id.as_deref()),
+        non_empty(b.sdz_google_place_id.as_deref()),
+    ) {
+        (Some(a), Some(b)) => Some(a == b),
+        _ => None,
+    };
+    let same_address = match (
+        a.sdz_address.as_deref().and_then(normalize_address),
+        b.sdz_address.as_deref().and_then(normalize_address),
+    ) {
+        (Some(a), Some(b)) => Some(a == b),
+        _ => None,
+    };
+    let nearby = distance_m.is_some_and(|d| d <= SDZ_DEDUP_MAX_DISTANCE_M);
+    if !nearby && same_place_id != Some(true) && same_address != Some(true) {
+        return None;
+    }
+
+    let name_similarity = sdz_name_similarity(&a.name, &b.name);
+    let mut weighted = SDZ_DEDUP_WEIGHT_NAME * name_similarity;
+    let mut total_weight = SDZ_DEDUP_WEIGHT_NAME;
+    if let Some(distance_m) = distance_m {
+        weighted +=
+            SDZ_DEDUP_WEIGHT_DISTANCE * (1.0 - distance_m / SDZ_DEDUP_MAX_DISTANCE_M).max(0.0);
+        total_weight += SDZ_DEDUP_WEIGHT_DISTANCE;
+    }
+    if let Some(same) = same_place_id {
+        weighted += SDZ_DEDUP_WEIGHT_PLACE_ID * if same { 1.0 } else { 0.0 };
+        total_weight += SDZ_DEDUP_WEIGHT_PLACE_ID;
+    }
+    if let Some(same) = same_address {
+        weighted += SDZ_DEDUP_WEIGHT_ADDRESS * if same { 1.0 } else { 0.0 };
+        total_weight += SDZ_DEDUP_WEIGHT_ADDRESS;
+    }
+    Some(SdzDuplicateScore {
+        score: weighted / total_weight,
+        distance_m,
+        name_similarity,
+        same_place_id,
+        same_address,
+    })
+}
+
+/// `threshold`以上のペアを、`spots`の添字とともにスコアの高い順で返す。
+/// 全ペアの総当たりは避け、緯度順の近傍とPlace ID・住所の一致から候補を作る
+pub fn sdz_find_duplicate_pairs(
+    spots: &[SdzSpot],
+    threshold: f64,
+) -> Vec<(usize, usize, SdzDuplicateScore)> {
+    let mut candidates = HashSet::new();
+
+    let mut located: Vec<(usize, f64)> = spots
+        .iter()
+        .enumerate()
+        .filter_map(|(index, spot)| spot.location.as_ref().map(|loc| (index, loc.lat)))
+        .collect();
+    located.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let max_lat_delta = SDZ_DEDUP_MAX_DISTANCE_M / SDZ_METERS_PER_DEGREE_LAT;
+    for (i, (a, lat)) in located.iter().enumerate() {
+        for (b, other_lat) in &located[i + 1..] {
+            if other_lat - lat > max_lat_delta {
+                break;
+            }
+            candidates.insert(ordered_pair(*a, *b));
+        }
+    }
+
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, spot) in spots.iter().enumerate() {
+        if let Some(place_id) = non_empty(spot.sdz_google_place_id.as_deref()) {
+            groups
+                .entry(format!("place:{}", place_id))
+                .or_default()
+                .push(index);
+        }
+        if let Some(address) = spot.sdz_address.as_deref().and_then(normalize_address) {
+            groups
+                .entry(format!("address:{}", address))
+                .or_default()
+                .push(index);
+        }
+    }
+    for members in groups.values() {
+        for (i, a) in members.iter().enumerate() {
+            for b in &members[i + 1..] {
+                candidates.insert(ordered_pair(*a, *b));
+            }
+        }
+    }
+
+    let mut pairs: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|(a, b)| {
+            sdz_score_duplicate(&spots[a], &spots[b])
+                .filter(|score| score.score >= threshold)
+                .map(|score| (a, b, score))
+        })
+        .collect();
+    pairs.sort_by(|x, y| {
+        y.2.score
+            .total_cmp(&x.2.score)
+            .then_with(|| (x.0, x.1).cmp(&(y.0, y.1)))
+    });
+    pairs
+}
+
+/// 正規化した名前の文字bigramのDice係数（0.0〜1.0）。空白は無視する
+pub fn sdz_name_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = compact(&sdz_normalize_search_text(a)).chars().collect();
+    let b: Vec<char> = compact(&sdz_normalize_search_text(b)).chars().collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+    let bigrams = |chars: &[char]| -> HashMap<(char, char), usize> {
+        let mut counts = HashMap::new();
+        for pair in chars.windows(2) {
+            *counts.entry((pair[0], pair[1])).or_insert(0) += 1;
+        }
+        counts
+    };
+    let a_bigrams = bigrams(&a);
+    let b_bigrams = bigrams(&b);
+    let shared: usize = a_bigrams
+        .iter()
+        .map(|(bigram, count)| (*count).min(b_bigrams.get(bigram).copied().unwrap_or(0)))
+        .sum();
+    2.0 * shared as f64 / (a.len() - 1 + b.len() - 1) as f64
+}
+
+fn ordered_pair(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// 表記ゆれを吸収するため、空白・区切り記号・先頭の「日本」と郵便番号を除いて比較する
+fn normalize_address(raw: &str) -> Option<String> {
+    let normalized: String = compact(&sdz_normalize_search_text(raw))
+        .chars()
+        .filter(|c| !matches!(c, ',' | '、' | '，'))
+        .collect();
+    let normalized = normalized.strip_prefix("日本").unwrap_or(&normalized);
+    let normalized = match normalized.strip_prefix('〒') {
+        Some(rest) => rest.trim_start_matches(|c: char| c.is_ascii_digit() || c == '-'),
+        None => normalized,
+    };
+    (!normalized.is_empty()).then(|| normalized.to_string())
+}
+
+fn compact(raw: &str) -> String {
+    raw.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{SdzCreateSpotParams, SdzSpotLocation};
+
+    fn spot(
+        id: &str,
+        name: &str,
+        location: Option<(f64, f64)>,
+        place_id: Option<&str>,
+        address: Option<&str>,
+    ) -> SdzSpot {
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: id.into(),
+            name: name.into(),
+            description: None,
+            location: location.map(|(lat, lng)| SdzSpotLocation { lat, lng }),
+            tags: vec![],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: place_id.map(str::to_string),
+            sdz_google_maps_url: None,
+            sdz_address: address.map(str::to_string),
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn name_similarity_ignores_width_case_and_spaces() {
+        assert_eq!(
+            sdz_name_similarity("宮下公園 スケート場", "宮下公園スケート場"),
+            1.0
+        );
+        assert_eq!(sdz_name_similarity("ＡＢＣ Park", "abc park"), 1.0);
+        let similar = sdz_name_similarity("渋谷スケートパーク", "渋谷区スケートパーク");
+        assert!(similar > 0.7 && similar < 1.0, "{}", similar);
+        assert!(sdz_name_similarity("渋谷スケートパーク", "駒沢公園") < 0.2);
+    }
+
+    #[test]
+    fn scores_nearby_similar_spots_and_skips_unrelated_pairs() {
+        let a = spot(
+            "a",
+            "渋谷スケートパーク",
+            Some((35.6600, 139.7000)),
+            None,
+            None,
+        );
+        let b = spot(
+            "b",
+            "渋谷区スケートパーク",
+            Some((35.6601, 139.7001)),
+            None,
+            None,
+        );
+        let score = sdz_score_duplicate(&a, &b).unwrap();
+        assert!(score.score > 0.8, "{:?}", score);
+        assert!(score.distance_m.unwrap() < 20.0);
+        assert_eq!(score.same_place_id, None);
+
+        // 同名でも遠く離れていて、Place IDも住所もなければ候補にしない
+        let far = spot(
+            "far",
+            "渋谷スケートパーク",
+            Some((34.70, 135.50)),
+            None,
+            None,
+        );
+        assert!(sdz_score_duplicate(&a, &far).is_none());
+
+        // 位置がなくても、Place IDと住所が一致すれば候補になる
+        let c = spot(
+            "c",
+            "Miyashita Park",
+            None,
+            Some("place-1"),
+            Some("日本、〒150-0001 東京都渋谷区神宮前6-20-10"),
+        );
+        let d = spot(
+            "d",
+            "MIYASHITA PARK skate",
+            None,
+            Some("place-1"),
+            Some("東京都渋谷区神宮前６－２０－１０"),
+        );
+        let score = sdz_score_duplicate(&c, &d).unwrap();
+        assert_eq!(score.same_place_id, Some(true));
+        assert_eq!(score.same_address, Some(true));
+        assert!(score.score > 0.8, "{:?}", score);
+    }
+
+    #[test]
+    fn find_pairs_applies_threshold_and_sorts_by_score() {
+        let spots = vec![
+            spot(
+                "a",
+                "駒沢公園スケート場",
+                Some((35.6250, 139.6600)),
+                None,
+                None,
+            ),
+            spot(
+                "b",
+                "駒沢公園 スケート場",
+                Some((35.6251, 139.6600)),
+                None,
+                None,
+            ),
+            spot(
+                "c",
+                "駒沢テニスコート",
+                Some((35.6260, 139.6600)),
+                None,
+                None,
+            ),
+            spot("d", "大阪スケートパーク", Some((34.70, 135.50)), None, None),
+        ];
+        let pairs = sdz_find_duplicate_pairs(&spots, 0.0);
+        let ids: Vec<_> = pairs
+            .iter()
+            .map(|(a, b, _)| {
+                (
+                    spots[*a].sdz_spot_id.as_str(),
+                    spots[*b].sdz_spot_id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(ids[0], ("a", "b"));
+        assert_eq!(ids.len(), 3);
+        assert!(!ids.iter().any(|(a, b)| *a == "d" || *b == "d"));
+
+        let pairs = sdz_find_duplicate_pairs(&spots, 0.9);
+        assert_eq!(pairs.len(), 1);
+    }
+}
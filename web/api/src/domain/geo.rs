@@ -0,0 +1,231 @@
+use crate::domain::models::{SdzSpotBounds, SdzSpotLocation};
+
+const SDZ_GEOHASH_BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+const SDZ_EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Firestoreに保存するgeohashの桁数（約5m四方）
+pub const SDZ_GEOHASH_PRECISION: usize = 9;
+
+/// 緯度経度をgeohash文字列に変換する。
+pub fn sdz_encode_geohash(lat: f64, lng: f64, precision: usize) -> String {
+    let mut lat_range = (-90.0_f64, 90.0_f64);
+    let mut lng_range = (-180.0_f64, 180.0_f64);
+    let mut hash = String::with_capacity(precision);
+    let mut is_lng = true;
+    let mut bit = 0;
+    let mut index = 0usize;
+
+    while hash.len() < precision {
+        let (range, value) = if is_lng {
+            (&mut lng_range, lng)
+        } else {
+            (&mut lat_range, lat)
+        };
+        let mid = (range.0 + range.1) / 2.0;
+        if value >= mid {
+            index = (index << 1) | 1;
+            range.0 = mid;
+        } else {
+            index <<= 1;
+            range.1 = mid;
+        }
+        is_lng = !is_lng;
+        bit += 1;
+        if bit == 5 {
+            hash.push(SDZ_GEOHASH_BASE32[index] as char);
+            bit = 0;
+            index = 0;
+        }
+    }
+    hash
+}
+
+/// 矩形を覆うgeohashセルを返す。`max_cells`以内に収まる最も細かい桁数を選ぶ。
+/// 1桁でも収まらない（ほぼ全世界の）場合は空を返すので、呼び出し側で全件検索にフォールバックする。
+pub fn sdz_geohash_cells_for_bounds(bounds: &SdzSpotBounds, max_cells: usize) -> Vec<String> {
+    for precision in (1..=SDZ_GEOHASH_PRECISION).rev() {
+        let grid = GeohashGrid::new(precision);
+        let (lat_min, lat_max) = grid.lat_index_range(bounds.min_lat, bounds.max_lat);
+        let (lng_min, lng_max) = grid.lng_index_range(bounds.min_lng, bounds.max_lng);
+        let count = (lat_max - lat_min + 1) * (lng_max - lng_min + 1);
+        if count > max_cells as u64 {
+            continue;
+        }
+
+        let mut cells = Vec::with_capacity(count as usize);
+        for lat_index in lat_min..=lat_max {
+            for lng_index in lng_min..=lng_max {
+                let (lat, lng) = grid.cell_center(lat_index, lng_index);
+                cells.push(sdz_encode_geohash(lat, lng, precision));
+            }
+        }
+        cells.sort();
+        cells.dedup();
+        return cells;
+    }
+    Vec::new()
+}
+
+/// 2点間の大円距離（メートル）をハバーサイン公式で求める。
+pub fn sdz_distance_m(from: &SdzSpotLocation, to: &SdzSpotLocation) -> f64 {
+    let lat1 = from.lat.to_radians();
+    let lat2 = to.lat.to_radians();
+    let d_lat = (to.lat - from.lat).to_radians();
+    let d_lng = (to.lng - from.lng).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+    2.0 * SDZ_EARTH_RADIUS_M * a.sqrt().min(1.0).asin()
+}
+
+/// 中心から半径`radius_m`の円を内包する矩形を返す（日付変更線をまたぐ場合は経度を全域にする）。
+pub fn sdz_bounds_around(center: &SdzSpotLocation, radius_m: f64) -> SdzSpotBounds {
+    let d_lat = (radius_m / SDZ_EARTH_RADIUS_M).to_degrees();
+    let min_lat = (center.lat - d_lat).max(-90.0);
+    let max_lat = (center.lat + d_lat).min(90.0);
+    let max_abs_lat = min_lat.abs().max(max_lat.abs());
+    let cos_lat = max_abs_lat.to_radians().cos();
+    let (min_lng, max_lng) = if cos_lat <= f64::EPSILON {
+        (-180.0, 180.0)
+    } else {
+        let d_lng = d_lat / cos_lat;
+        let min_lng = center.lng - d_lng;
+        let max_lng = center.lng + d_lng;
+        if min_lng < -180.0 || max_lng > 180.0 {
+            (-180.0, 180.0)
+        } else {
+            (min_lng, max_lng)
+        }
+    };
+    SdzSpotBounds {
+        min_lat,
+        min_lng,
+        max_lat,
+        max_lng,
+    }
+}
+
+struct GeohashGrid {
+    lat_cells: u64,
+    lng_cells: u64,
+    cell_height: f64,
+    cell_width: f64,
+}
+
+impl GeohashGrid {
+    fn new(precision: usize) -> Self {
+        let bits = (precision * 5) as u32;
+        let lng_bits = bits.div_ceil(2);
+        let lat_bits = bits / 2;
+        let lat_cells = 1u64 << lat_bits;
+        let lng_cells = 1u64 << lng_bits;
+        Self {
+            lat_cells,
+            lng_cells,
+            cell_height: 180.0 / lat_cells as f64,
+            cell_width: 360.0 / lng_cells as f64,
+        }
+    }
+
+    fn lat_index_range(&self, min: f64, max: f64) -> (u64, u64) {
+        (
+            index_of(min + 90.0, self.cell_height, self.lat_cells),
+            index_of(max + 90.0, self.cell_height, self.lat_cells),
+        )
+    }
+
+    fn lng_index_range(&self, min: f64, max: f64) -> (u64, u64) {
+        (
+            index_of(min + 180.0, self.cell_width, self.lng_cells),
+            index_of(max + 180.0, self.cell_width, self.lng_cells),
+        )
+    }
+
+    fn cell_center(&self, lat_index: u64, lng_index: u64) -> (f64, f64) {
+        (
+            -90.0 + (lat_index as f64 + 0.5) * self.cell_height,
+            -180.0 + (lng_index as f64 + 0.5) * self.cell_width,
+        )
+    }
+}
+
+fn index_of(offset: f64, cell_size: f64, cells: u64) -> u64 {
+    let index = (offset / cell_size).floor().max(0.0) as u64;
+    index.min(cells - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_geohash_matches_reference() {
+        assert_eq!(sdz_encode_geohash(57.64911, 10.40744, 11), "u4pruydqqvj");
+        assert_eq!(sdz_encode_geohash(35.681236, 139.767125, 5), "xn76u");
+    }
+
+    #[test]
+    fn cells_cover_bounds_corners() {
+        let bounds = SdzSpotBounds {
+            min_lat: 35.60,
+            min_lng: 139.60,
+            max_lat: 35.75,
+            max_lng: 139.85,
+        };
+        let cells = sdz_geohash_cells_for_bounds(&bounds, 16);
+        assert!(!cells.is_empty());
+        assert!(cells.len() <= 16);
+        for (lat, lng) in [
+            (bounds.min_lat, bounds.min_lng),
+            (bounds.min_lat, bounds.max_lng),
+            (bounds.max_lat, bounds.min_lng),
+            (bounds.max_lat, bounds.max_lng),
+        ] {
+            let hash = sdz_encode_geohash(lat, lng, SDZ_GEOHASH_PRECISION);
+            assert!(cells.iter().any(|cell| hash.starts_with(cell.as_str())));
+        }
+    }
+
+    #[test]
+    fn distance_between_tokyo_and_osaka() {
+        let tokyo = SdzSpotLocation {
+            lat: 35.681236,
+            lng: 139.767125,
+        };
+        let osaka = SdzSpotLocation {
+            lat: 34.702485,
+            lng: 135.495951,
+        };
+        let distance = sdz_distance_m(&tokyo, &osaka);
+        assert!((distance - 403_000.0).abs() < 2_000.0, "{distance}");
+        assert_eq!(sdz_distance_m(&tokyo, &tokyo), 0.0);
+    }
+
+    #[test]
+    fn bounds_around_contains_circle() {
+        let center = SdzSpotLocation {
+            lat: 35.0,
+            lng: 139.0,
+        };
+        let bounds = sdz_bounds_around(&center, 1_000.0);
+        for (lat, lng) in [
+            (35.0089, 139.0),
+            (34.9911, 139.0),
+            (35.0, 139.0108),
+            (35.0, 138.9892),
+        ] {
+            let point = SdzSpotLocation { lat, lng };
+            assert!(sdz_distance_m(&center, &point) <= 1_000.0);
+            assert!(bounds.contains(&point));
+        }
+    }
+
+    #[test]
+    fn cells_empty_for_whole_world() {
+        let bounds = SdzSpotBounds {
+            min_lat: -90.0,
+            min_lng: -180.0,
+            max_lat: 90.0,
+            max_lng: 180.0,
+        };
+        assert!(sdz_geohash_cells_for_bounds(&bounds, 16).is_empty());
+    }
+}
@@ -1 +1,6 @@
+pub mod business_hours;
+pub mod dedup;
+pub mod geo;
 pub mod models;
+pub mod revision;
+pub mod search;
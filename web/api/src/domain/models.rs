@@ -13,6 +13,8 @@ impl HealthStatus {
     }
 }
 
+use std::collections::BTreeSet;
+
 use chrono::{DateTime, FixedOffset};
 use serde::Deserialize;
 use serde::Serialize;
@@ -34,6 +36,52 @@ pub struct SdzSpotLocation {
     pub lng: f64,
 }
 
+/// 地図の表示範囲などを表す緯度経度の矩形
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdzSpotBounds {
+    pub min_lat: f64,
+    pub min_lng: f64,
+    pub max_lat: f64,
+    pub max_lng: f64,
+}
+
+impl SdzSpotBounds {
+    /// `minLat,minLng,maxLat,maxLng`形式の文字列を解釈する。範囲外や逆転は不正として扱う。
+    pub fn parse(raw: &str) -> Option<Self> {
+        let values = raw
+            .split(',')
+            .map(|v| v.trim().parse::<f64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let [min_lat, min_lng, max_lat, max_lng] = values.as_slice() else {
+            return None;
+        };
+        let bounds = Self {
+            min_lat: *min_lat,
+            min_lng: *min_lng,
+            max_lat: *max_lat,
+            max_lng: *max_lng,
+        };
+        let lat_ok = (-90.0..=90.0).contains(&bounds.min_lat)
+            && (-90.0..=90.0).contains(&bounds.max_lat)
+            && bounds.min_lat <= bounds.max_lat;
+        let lng_ok = (-180.0..=180.0).contains(&bounds.min_lng)
+            && (-180.0..=180.0).contains(&bounds.max_lng)
+            && bounds.min_lng <= bounds.max_lng;
+        if lat_ok && lng_ok {
+            Some(bounds)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, location: &SdzSpotLocation) -> bool {
+        location.lat >= self.min_lat
+            && location.lat <= self.max_lat
+            && location.lng >= self.min_lng
+            && location.lng <= self.max_lng
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SdzSpotTimeRange {
     #[serde(rename = "startMinutes")]
@@ -154,6 +202,12 @@ pub struct SdzSpot {
     pub created_at: DateTime<FixedOffset>,
     #[serde(rename = "updatedAt")]
     pub updated_at: DateTime<FixedOffset>,
+    /// アーカイブ（論理削除）日時。Noneなら公開中
+    #[serde(rename = "deletedAt", default, skip_serializing_if = "Option::is_none")]
+    pub sdz_deleted_at: Option<DateTime<FixedOffset>>,
+    /// アーカイブした管理者のユーザーID
+    #[serde(rename = "deletedBy", default, skip_serializing_if = "Option::is_none")]
+    pub sdz_deleted_by: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -215,6 +269,175 @@ pub struct SdzUpdateSpotParams {
     pub sdz_google_rating: Option<f64>,
     pub sdz_google_rating_count: Option<u32>,
     pub sdz_google_types: Option<Vec<String>>,
+    /// 値を削除する任意項目。Noneのフィールドは「変更なし」のため、消す場合はここで指定する
+    pub clear_fields: Vec<SdzSpotClearableField>,
+}
+
+/// 更新で明示的に削除できる任意項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SdzSpotClearableField {
+    Description,
+    Location,
+    ApprovalStatus,
+    ParkAttributes,
+    StreetAttributes,
+    InstagramTag,
+    InstagramLocationUrl,
+    InstagramProfileUrl,
+    GooglePlaceId,
+    GoogleMapsUrl,
+    Address,
+    PhoneNumber,
+    GoogleRating,
+    GoogleRatingCount,
+}
+
+impl SdzSpotClearableField {
+    pub const ALL: [SdzSpotClearableField; 14] = [
+        Self::Description,
+        Self::Location,
+        Self::ApprovalStatus,
+        Self::ParkAttributes,
+        Self::StreetAttributes,
+        Self::InstagramTag,
+        Self::InstagramLocationUrl,
+        Self::InstagramProfileUrl,
+        Self::GooglePlaceId,
+        Self::GoogleMapsUrl,
+        Self::Address,
+        Self::PhoneNumber,
+        Self::GoogleRating,
+        Self::GoogleRatingCount,
+    ];
+
+    /// APIのJSON名（Firestoreのフィールド名と同じ）
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            Self::Description => "description",
+            Self::Location => "location",
+            Self::ApprovalStatus => "approvalStatus",
+            Self::ParkAttributes => "parkAttributes",
+            Self::StreetAttributes => "streetAttributes",
+            Self::InstagramTag => "instagramTag",
+            Self::InstagramLocationUrl => "instagramLocationUrl",
+            Self::InstagramProfileUrl => "instagramProfileUrl",
+            Self::GooglePlaceId => "googlePlaceId",
+            Self::GoogleMapsUrl => "googleMapsUrl",
+            Self::Address => "address",
+            Self::PhoneNumber => "phoneNumber",
+            Self::GoogleRating => "googleRating",
+            Self::GoogleRatingCount => "googleRatingCount",
+        }
+    }
+}
+
+/// 部分更新で書き込むフィールドの集合（APIのJSON名）。
+/// 永続化側はここに含まれないフィールドに触れない
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SdzSpotFieldMask {
+    fields: BTreeSet<&'static str>,
+}
+
+impl SdzSpotFieldMask {
+    pub fn from_fields(fields: &[&'static str]) -> Self {
+        Self {
+            fields: fields.iter().copied().collect(),
+        }
+    }
+
+    /// APIが管理する全フィールド。丸ごと上書きする場合もこれ以外のフィールドは保持する
+    pub fn all() -> Self {
+        let mut mask = Self::from_fields(&[
+            "name",
+            "userId",
+            "tags",
+            "images",
+            "googleTypes",
+            "createdAt",
+            "updatedAt",
+            "deletedAt",
+            "deletedBy",
+        ]);
+        mask.fields.extend(
+            SdzSpotClearableField::ALL
+                .iter()
+                .map(SdzSpotClearableField::field_name),
+        );
+        mask
+    }
+
+    /// アーカイブ・復元で変わるフィールド
+    pub fn archive_state() -> Self {
+        Self::from_fields(&["deletedAt", "deletedBy", "updatedAt"])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields.iter().copied()
+    }
+}
+
+impl SdzUpdateSpotParams {
+    /// 指定された（値あり、または削除指定の）フィールドとupdatedAtのマスク
+    pub fn field_mask(&self) -> SdzSpotFieldMask {
+        let present = [
+            ("name", self.name.is_some()),
+            ("description", self.description.is_some()),
+            ("location", self.location.is_some()),
+            ("tags", self.tags.is_some()),
+            ("images", self.images.is_some()),
+            ("approvalStatus", self.sdz_approval_status.is_some()),
+            ("parkAttributes", self.sdz_park_attributes.is_some()),
+            ("streetAttributes", self.sdz_street_attributes.is_some()),
+            ("instagramTag", self.sdz_instagram_tag.is_some()),
+            (
+                "instagramLocationUrl",
+                self.sdz_instagram_location_url.is_some(),
+            ),
+            (
+                "instagramProfileUrl",
+                self.sdz_instagram_profile_url.is_some(),
+            ),
+            ("googlePlaceId", self.sdz_google_place_id.is_some()),
+            ("googleMapsUrl", self.sdz_google_maps_url.is_some()),
+            ("address", self.sdz_address.is_some()),
+            ("phoneNumber", self.sdz_phone_number.is_some()),
+            ("googleRating", self.sdz_google_rating.is_some()),
+            ("googleRatingCount", self.sdz_google_rating_count.is_some()),
+            ("googleTypes", self.sdz_google_types.is_some()),
+        ];
+        let mut mask = SdzSpotFieldMask::from_fields(&["updatedAt"]);
+        mask.fields.extend(
+            present
+                .into_iter()
+                .filter(|(_, present)| *present)
+                .map(|(field, _)| field),
+        );
+        mask.fields
+            .extend(self.clear_fields.iter().map(|field| field.field_name()));
+        mask
+    }
+
+    fn has_value(&self, field: SdzSpotClearableField) -> bool {
+        match field {
+            SdzSpotClearableField::Description => self.description.is_some(),
+            SdzSpotClearableField::Location => self.location.is_some(),
+            SdzSpotClearableField::ApprovalStatus => self.sdz_approval_status.is_some(),
+            SdzSpotClearableField::ParkAttributes => self.sdz_park_attributes.is_some(),
+            SdzSpotClearableField::StreetAttributes => self.sdz_street_attributes.is_some(),
+            SdzSpotClearableField::InstagramTag => self.sdz_instagram_tag.is_some(),
+            SdzSpotClearableField::InstagramLocationUrl => {
+                self.sdz_instagram_location_url.is_some()
+            }
+            SdzSpotClearableField::InstagramProfileUrl => self.sdz_instagram_profile_url.is_some(),
+            SdzSpotClearableField::GooglePlaceId => self.sdz_google_place_id.is_some(),
+            SdzSpotClearableField::GoogleMapsUrl => self.sdz_google_maps_url.is_some(),
+            SdzSpotClearableField::Address => self.sdz_address.is_some(),
+            SdzSpotClearableField::PhoneNumber => self.sdz_phone_number.is_some(),
+            SdzSpotClearableField::GoogleRating => self.sdz_google_rating.is_some(),
+            SdzSpotClearableField::GoogleRatingCount => self.sdz_google_rating_count.is_some(),
+        }
+    }
 }
 
 impl SdzSpot {
@@ -253,6 +476,8 @@ impl SdzSpot {
             sdz_user_id: params.sdz_user_id,
             created_at: sdz_now_jst(),
             updated_at: sdz_now_jst(),
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
         })
     }
 
@@ -263,44 +488,100 @@ impl SdzSpot {
         )
     }
 
+    /// パーク扱いか（parkAttributesを持つか、パーク系タグが付いている）
+    pub fn is_park(&self) -> bool {
+        self.sdz_park_attributes.is_some() || self.has_any_tag(&SDZ_PARK_TAGS)
+    }
+
+    /// ストリート扱いか（streetAttributesを持つか、ストリート系タグが付いている）
+    pub fn is_street(&self) -> bool {
+        self.sdz_street_attributes.is_some() || self.has_any_tag(&SDZ_STREET_TAGS)
+    }
+
+    fn has_any_tag(&self, tags: &[&str]) -> bool {
+        self.tags.iter().any(|tag| tags.contains(&tag.as_str()))
+    }
+
     pub fn update(&self, params: SdzUpdateSpotParams) -> Result<Self, SdzSpotValidationError> {
+        // 値の指定と削除指定が同時にあれば、どちらを優先すべきか決められない
+        if params
+            .clear_fields
+            .iter()
+            .any(|field| params.has_value(*field))
+        {
+            return Err(SdzSpotValidationError::ConflictingClearField);
+        }
+        let clear_fields = params.clear_fields;
+        let keep = |field: SdzSpotClearableField| !clear_fields.contains(&field);
+        let current = |value: &Option<String>, field: SdzSpotClearableField| {
+            value.clone().filter(|_| keep(field))
+        };
+
         let name = params.name.unwrap_or_else(|| self.name.clone());
-        let description = params.description.or_else(|| self.description.clone());
-        let location = params.location.or_else(|| self.location.clone());
+        let description = params
+            .description
+            .or_else(|| current(&self.description, SdzSpotClearableField::Description));
+        let location = params.location.or_else(|| {
+            self.location
+                .clone()
+                .filter(|_| keep(SdzSpotClearableField::Location))
+        });
         let tags = params.tags.unwrap_or_else(|| self.tags.clone());
         let images = params.images.unwrap_or_else(|| self.images.clone());
-        let approval_status = params
-            .sdz_approval_status
-            .or_else(|| self.sdz_approval_status.clone());
-        let park_attributes = params
-            .sdz_park_attributes
-            .or_else(|| self.sdz_park_attributes.clone());
-        let street_attributes = params
-            .sdz_street_attributes
-            .or_else(|| self.sdz_street_attributes.clone());
+        let approval_status = params.sdz_approval_status.or_else(|| {
+            self.sdz_approval_status
+                .clone()
+                .filter(|_| keep(SdzSpotClearableField::ApprovalStatus))
+        });
+        let park_attributes = params.sdz_park_attributes.or_else(|| {
+            self.sdz_park_attributes
+                .clone()
+                .filter(|_| keep(SdzSpotClearableField::ParkAttributes))
+        });
+        let street_attributes = params.sdz_street_attributes.or_else(|| {
+            self.sdz_street_attributes
+                .clone()
+                .filter(|_| keep(SdzSpotClearableField::StreetAttributes))
+        });
         let instagram_tag = params
             .sdz_instagram_tag
-            .or_else(|| self.sdz_instagram_tag.clone());
-        let instagram_location_url = params
-            .sdz_instagram_location_url
-            .or_else(|| self.sdz_instagram_location_url.clone());
-        let instagram_profile_url = params
-            .sdz_instagram_profile_url
-            .or_else(|| self.sdz_instagram_profile_url.clone());
-        let google_place_id = params
-            .sdz_google_place_id
-            .or_else(|| self.sdz_google_place_id.clone());
-        let google_maps_url = params
-            .sdz_google_maps_url
-            .or_else(|| self.sdz_google_maps_url.clone());
-        let address = params.sdz_address.or_else(|| self.sdz_address.clone());
+            .or_else(|| current(&self.sdz_instagram_tag, SdzSpotClearableField::InstagramTag));
+        let instagram_location_url = params.sdz_instagram_location_url.or_else(|| {
+            current(
+                &self.sdz_instagram_location_url,
+                SdzSpotClearableField::InstagramLocationUrl,
+            )
+        });
+        let instagram_profile_url = params.sdz_instagram_profile_url.or_else(|| {
+            current(
+                &self.sdz_instagram_profile_url,
+                SdzSpotClearableField::InstagramProfileUrl,
+            )
+        });
+        let google_place_id = params.sdz_google_place_id.or_else(|| {
+            current(
+                &self.sdz_google_place_id,
+                SdzSpotClearableField::GooglePlaceId,
+            )
+        });
+        let google_maps_url = params.sdz_google_maps_url.or_else(|| {
+            current(
+                &self.sdz_google_maps_url,
+                SdzSpotClearableField::GoogleMapsUrl,
+            )
+        });
+        let address = params
+            .sdz_address
+            .or_else(|| current(&self.sdz_address, SdzSpotClearableField::Address));
         let phone_number = params
             .sdz_phone_number
-            .or_else(|| self.sdz_phone_number.clone());
-        let google_rating = params.sdz_google_rating.or(self.sdz_google_rating);
-        let google_rating_count = params
+            .or_else(|| current(&self.sdz_phone_number, SdzSpotClearableField::PhoneNumber));
+        let google_rating = params.sdz_google_rating.or(self
+            .sdz_google_rating
+            .filter(|_| keep(SdzSpotClearableField::GoogleRating)));
+        let google_rating_count = params.sdz_google_rating_count.or(self
             .sdz_google_rating_count
-            .or(self.sdz_google_rating_count);
+            .filter(|_| keep(SdzSpotClearableField::GoogleRatingCount)));
         let google_types = params
             .sdz_google_types
             .unwrap_or_else(|| self.sdz_google_types.clone());
@@ -340,8 +621,43 @@ impl SdzSpot {
             sdz_user_id: self.sdz_user_id.clone(),
             created_at: self.created_at,
             updated_at: sdz_now_jst(),
+            sdz_deleted_at: self.sdz_deleted_at,
+            sdz_deleted_by: self.sdz_deleted_by.clone(),
         })
     }
+
+    /// 楽観的排他制御用のETag。Firestoreの保存精度に合わせてマイクロ秒で表す
+    pub fn etag(&self) -> String {
+        format!("\"{}\"", self.updated_at.timestamp_micros())
+    }
+
+    /// 撤去されたスポットなど、検索から外したスポットか
+    pub fn is_archived(&self) -> bool {
+        self.sdz_deleted_at.is_some()
+    }
+
+    /// アーカイブ状態にする。既にアーカイブ済みなら元の日時・実行者を保つ
+    pub fn archive(&self, deleted_by: &str) -> Self {
+        if self.is_archived() {
+            return self.clone();
+        }
+        let now = sdz_now_jst();
+        Self {
+            sdz_deleted_at: Some(now),
+            sdz_deleted_by: Some(deleted_by.to_string()),
+            updated_at: now,
+            ..self.clone()
+        }
+    }
+
+    pub fn restore(&self) -> Self {
+        Self {
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
+            updated_at: sdz_now_jst(),
+            ..self.clone()
+        }
+    }
 }
 
 pub fn sdz_validate_spot(
@@ -416,9 +732,15 @@ pub enum SdzSpotValidationError {
     InvalidStreetSection,
     #[error("google rating must be between 1.0 and 5.0")]
     InvalidGoogleRating,
+    #[error("a field cannot be both set and listed in clearFields")]
+    ConflictingClearField,
 }
 
 const SDZ_MAX_IMAGES_PER_SPOT: usize = 3;
+/// 画像付きスポットの1ユーザーあたりの上限
+pub const SDZ_MAX_IMAGE_SPOTS_PER_USER: usize = 10;
+const SDZ_PARK_TAGS: [&str; 3] = ["パーク", "スケートパーク", "スケートボードパーク"];
+const SDZ_STREET_TAGS: [&str; 1] = ["ストリート"];
 
 fn validate_business_hours(hours: &SdzSpotBusinessHours) -> Result<(), SdzSpotValidationError> {
     let schedule_type = hours
@@ -558,6 +880,24 @@ mod tests {
         assert!(err.to_string().contains("google rating"));
     }
 
+    #[test]
+    fn parse_bounds() {
+        let bounds = SdzSpotBounds::parse("35.5, 139.5,35.9,139.9").unwrap();
+        assert_eq!(bounds.min_lat, 35.5);
+        assert_eq!(bounds.max_lng, 139.9);
+        assert!(bounds.contains(&SdzSpotLocation {
+            lat: 35.7,
+            lng: 139.7,
+        }));
+        assert!(!bounds.contains(&SdzSpotLocation {
+            lat: 34.7,
+            lng: 135.5,
+        }));
+        assert!(SdzSpotBounds::parse("35.9,139.5,35.5,139.9").is_none());
+        assert!(SdzSpotBounds::parse("35.5,139.5,35.9").is_none());
+        assert!(SdzSpotBounds::parse("a,b,c,d").is_none());
+    }
+
     #[test]
     fn update_spot_with_params() {
         let spot = SdzSpot::new_with_id(build_create_params()).unwrap();
@@ -572,4 +912,30 @@ mod tests {
         assert_eq!(updated.sdz_google_place_id, Some("ChIJ_new".into()));
         assert_eq!(updated.description, Some("desc".into()));
     }
+
+    #[test]
+    fn update_clears_listed_fields_and_masks_present_ones() {
+        let spot = SdzSpot::new_with_id(build_create_params()).unwrap();
+        let params = SdzUpdateSpotParams {
+            name: Some("updated".into()),
+            clear_fields: vec![SdzSpotClearableField::Description],
+            ..Default::default()
+        };
+        assert_eq!(
+            params.field_mask().iter().collect::<Vec<_>>(),
+            vec!["description", "name", "updatedAt"]
+        );
+        let updated = spot.update(params).unwrap();
+        assert_eq!(updated.name, "updated");
+        assert_eq!(updated.description, None);
+
+        let err = spot
+            .update(SdzUpdateSpotParams {
+                description: Some("new".into()),
+                clear_fields: vec![SdzSpotClearableField::Description],
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(matches!(err, SdzSpotValidationError::ConflictingClearField));
+    }
 }
@@ -0,0 +1,141 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::domain::models::{sdz_now_jst, SdzSpot};
+
+/// 変更フィールドを辿る深さ。`parkAttributes.businessHours` の粒度まで見る
+const SDZ_CHANGED_FIELD_DEPTH: usize = 2;
+
+/// 更新のたびに残すスポットの版。snapshotは更新後の状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdzSpotRevision {
+    #[serde(rename = "revisionId")]
+    pub sdz_revision_id: String,
+    #[serde(rename = "spotId")]
+    pub sdz_spot_id: String,
+    #[serde(rename = "editorUserId")]
+    pub sdz_editor_user_id: String,
+    #[serde(rename = "changedFields")]
+    pub changed_fields: Vec<String>,
+    pub snapshot: SdzSpot,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl SdzSpotRevision {
+    pub fn new(before: &SdzSpot, after: &SdzSpot, editor_user_id: &str) -> Self {
+        Self {
+            sdz_revision_id: Uuid::new_v4().to_string(),
+            sdz_spot_id: after.sdz_spot_id.clone(),
+            sdz_editor_user_id: editor_user_id.to_string(),
+            changed_fields: sdz_spot_changed_fields(before, after),
+            snapshot: after.clone(),
+            created_at: sdz_now_jst(),
+        }
+    }
+}
+
+/// APIのJSON表現で比較し、変わったフィールドをドット区切りで返す（updatedAtは除く）
+pub fn sdz_spot_changed_fields(before: &SdzSpot, after: &SdzSpot) -> Vec<String> {
+    let before = serde_json::to_value(before).unwrap_or(Value::Null);
+    let after = serde_json::to_value(after).unwrap_or(Value::Null);
+    let mut changed = Vec::new();
+    if let (Value::Object(before), Value::Object(after)) = (&before, &after) {
+        collect_changed(before, after, "", 1, &mut changed);
+    }
+    changed.retain(|field| field != "updatedAt");
+    changed.sort();
+    changed
+}
+
+fn collect_changed(
+    before: &Map<String, Value>,
+    after: &Map<String, Value>,
+    prefix: &str,
+    depth: usize,
+    changed: &mut Vec<String>,
+) {
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let path = format!("{}{}", prefix, key);
+        match (before.get(key), after.get(key)) {
+            (Some(Value::Object(b)), Some(Value::Object(a))) if depth < SDZ_CHANGED_FIELD_DEPTH => {
+                collect_changed(b, a, &format!("{}.", path), depth + 1, changed)
+            }
+            (b, a) if b != a => changed.push(path),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{
+        SdzCreateSpotParams, SdzSpotBusinessHours, SdzSpotParkAttributes, SdzUpdateSpotParams,
+    };
+
+    #[test]
+    fn changed_fields_reports_nested_business_hours() {
+        let before = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: "spot-1".into(),
+            name: "park".into(),
+            description: None,
+            location: None,
+            tags: vec!["park".into()],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: Some(SdzSpotParkAttributes {
+                official_url: Some("https://example.com".into()),
+                business_hours: None,
+                access_info: None,
+                phone_number: None,
+            }),
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap();
+        let after = before
+            .update(SdzUpdateSpotParams {
+                name: Some("renamed park".into()),
+                sdz_park_attributes: Some(SdzSpotParkAttributes {
+                    official_url: Some("https://example.com".into()),
+                    business_hours: Some(SdzSpotBusinessHours {
+                        schedule_type: None,
+                        is_24_hours: true,
+                        same_as_weekday: true,
+                        weekday: None,
+                        weekend: None,
+                        note: None,
+                    }),
+                    access_info: None,
+                    phone_number: None,
+                }),
+                ..SdzUpdateSpotParams::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            sdz_spot_changed_fields(&before, &after),
+            vec!["name", "parkAttributes.businessHours"]
+        );
+        // Firestoreには snapshot をJSON文字列で保存するため、往復で同じ内容に戻ること
+        let restored: SdzSpot =
+            serde_json::from_str(&serde_json::to_string(&after).unwrap()).unwrap();
+        assert!(sdz_spot_changed_fields(&after, &restored).is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::domain::models::SdzSpot;
+
+const SDZ_SCORE_NAME_EXACT: u32 = 100;
+const SDZ_SCORE_NAME_PREFIX: u32 = 80;
+const SDZ_SCORE_NAME_CONTAINS: u32 = 60;
+const SDZ_SCORE_TAG_EXACT: u32 = 50;
+const SDZ_SCORE_TAG_CONTAINS: u32 = 30;
+const SDZ_SCORE_DESCRIPTION_CONTAINS: u32 = 10;
+
+/// 検索用に文字列を正規化する。
+/// NFKCで全角英数・半角カナを揃え、小文字化し、ひらがなをカタカナに寄せる。
+pub fn sdz_normalize_search_text(raw: &str) -> String {
+    raw.nfkc()
+        .flat_map(char::to_lowercase)
+        .map(fold_hiragana)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 正規化済みの検索語（空白区切り）
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdzSearchQuery {
+    terms: Vec<String>,
+}
+
+impl SdzSearchQuery {
+    /// 空白のみならNone
+    pub fn parse(raw: &str) -> Option<Self> {
+        let terms: Vec<String> = sdz_normalize_search_text(raw)
+            .split(' ')
+            .filter(|term| !term.is_empty())
+            .map(str::to_string)
+            .collect();
+        (!terms.is_empty()).then_some(Self { terms })
+    }
+
+    /// 全ての語がいずれかの項目に一致すればスコアを返す。名前 > タグ > 説明の順に重い
+    pub fn score(&self, spot: &SdzSpot) -> Option<u32> {
+        let name = sdz_normalize_search_text(&spot.name);
+        let tags: Vec<String> = spot
+            .tags
+            .iter()
+            .map(|tag| sdz_normalize_search_text(tag))
+            .collect();
+        let description = spot
+            .description
+            .as_deref()
+            .map(sdz_normalize_search_text)
+            .unwrap_or_default();
+
+        let mut total = 0;
+        for term in &self.terms {
+            let term = term.as_str();
+            let name_score = if name == term {
+                SDZ_SCORE_NAME_EXACT
+            } else if name.starts_with(term) {
+                SDZ_SCORE_NAME_PREFIX
+            } else if name.contains(term) {
+                SDZ_SCORE_NAME_CONTAINS
+            } else {
+                0
+            };
+            let tag_score = tags
+                .iter()
+                .map(|tag| {
+                    if tag == term {
+                        SDZ_SCORE_TAG_EXACT
+                    } else if tag.contains(term) {
+                        SDZ_SCORE_TAG_CONTAINS
+                    } else {
+                        0
+                    }
+                })
+                .max()
+                .unwrap_or(0);
+            let description_score = if description.contains(term) {
+                SDZ_SCORE_DESCRIPTION_CONTAINS
+            } else {
+                0
+            };
+            let term_score = name_score + tag_score + description_score;
+            if term_score == 0 {
+                return None;
+            }
+            total += term_score;
+        }
+        Some(total)
+    }
+}
+
+fn fold_hiragana(c: char) -> char {
+    match c {
+        'ぁ'..='ゖ' | 'ゝ' | 'ゞ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::SdzSpotApprovalStatus;
+    use chrono::Utc;
+
+    fn spot(name: &str, tags: &[&str], description: Option<&str>) -> SdzSpot {
+        let now = Utc::now().fixed_offset();
+        SdzSpot {
+            sdz_spot_id: name.into(),
+            name: name.into(),
+            description: description.map(Into::into),
+            location: None,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            images: vec![],
+            sdz_approval_status: Some(SdzSpotApprovalStatus::Approved),
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user".into(),
+            created_at: now,
+            updated_at: now,
+            sdz_deleted_at: None,
+            sdz_deleted_by: None,
+        }
+    }
+
+    #[test]
+    fn normalize_folds_width_and_kana() {
+        assert_eq!(sdz_normalize_search_text("ｽｹｰﾄ"), "スケート");
+        assert_eq!(sdz_normalize_search_text("すけーと"), "スケート");
+        assert_eq!(sdz_normalize_search_text("ＰＡＲＫ　Ａ"), "park a");
+        assert_eq!(sdz_normalize_search_text("ｶﾞｰﾄﾞﾚｰﾙ"), "ガードレール");
+    }
+
+    #[test]
+    fn score_prefers_name_over_tag_and_description() {
+        let query = SdzSearchQuery::parse("すけーと").unwrap();
+        let name_hit = query.score(&spot("スケートパーク", &[], None)).unwrap();
+        let tag_hit = query.score(&spot("広場", &["スケート"], None)).unwrap();
+        let desc_hit = query.score(&spot("広場", &[], Some("ｽｹｰﾄできる"))).unwrap();
+        assert!(name_hit > tag_hit);
+        assert!(tag_hit > desc_hit);
+        assert!(query.score(&spot("公園", &[], None)).is_none());
+    }
+
+    #[test]
+    fn score_requires_every_term() {
+        let query = SdzSearchQuery::parse("レール 渋谷").unwrap();
+        assert!(query.score(&spot("渋谷レール", &[], None)).is_some());
+        assert!(query.score(&spot("新宿レール", &[], None)).is_none());
+        assert!(SdzSearchQuery::parse("  　").is_none());
+    }
+}
@@ -0,0 +1,424 @@
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, Method, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    infrastructure::{
+        firestore_endpoint::{SdzFirestoreEndpoint, SDZ_FIRESTORE_EMULATOR_TOKEN},
+        retry_policy::{SdzRetryPolicy, SdzRetryableError},
+    },
+    presentation::error::SdzApiError,
+};
+
+const SDZ_METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+/// 期限切れ直前のトークンでリクエストが失敗しないよう、この時間を残して取り直す
+const SDZ_TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Firestore RESTの失敗をステータスごとに分類したもの
+#[derive(Debug, Error, PartialEq)]
+pub enum SdzFirestoreError {
+    #[error("document not found")]
+    NotFound,
+    #[error("document already exists")]
+    AlreadyExists,
+    #[error("precondition failed")]
+    FailedPrecondition,
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("permission denied")]
+    PermissionDenied,
+    #[error("firestore unavailable")]
+    Unavailable,
+    #[error("unexpected firestore error")]
+    Internal,
+}
+
+impl From<SdzFirestoreError> for SdzApiError {
+    fn from(err: SdzFirestoreError) -> Self {
+        match err {
+            SdzFirestoreError::NotFound => SdzApiError::NotFound,
+            SdzFirestoreError::FailedPrecondition => {
+                SdzApiError::PreconditionFailed("document was modified by another request".into())
+            }
+            SdzFirestoreError::InvalidArgument(_) => {
+                SdzApiError::BadRequest("invalid data for Firestore".into())
+            }
+            SdzFirestoreError::Unavailable => {
+                SdzApiError::ServiceUnavailable("Firestore is temporarily unavailable".into())
+            }
+            // Firestore側の認証失敗はサーバー設定の問題なので、クライアントには内部エラーとして返す
+            SdzFirestoreError::AlreadyExists
+            | SdzFirestoreError::PermissionDenied
+            | SdzFirestoreError::Internal => SdzApiError::Internal,
+        }
+    }
+}
+
+impl SdzRetryableError for SdzFirestoreError {
+    fn is_transient(&self) -> bool {
+        matches!(self, SdzFirestoreError::Unavailable)
+    }
+
+    fn circuit_open(_service: &str) -> Self {
+        SdzFirestoreError::Unavailable
+    }
+}
+
+/// 各Firestoreリポジトリで共有するRESTクライアント。
+/// コネクションプールとアクセストークンのキャッシュを1つにまとめる。
+/// トークンは`bearer_token`、環境変数`SDZ_FIRESTORE_TOKEN`、メタデータサーバーの順に解決する
+pub struct SdzFirestoreClient {
+    project_id: String,
+    bearer_token: Option<String>,
+    endpoint: SdzFirestoreEndpoint,
+    http: Client,
+    cached_token: Mutex<Option<SdzCachedToken>>,
+    retry: SdzRetryPolicy,
+}
+
+impl SdzFirestoreClient {
+    pub fn new(project_id: String, bearer_token: Option<String>) -> Result<Self, SdzApiError> {
+        let http = Client::builder().build().map_err(|e| {
+            tracing::error!("Failed to build reqwest client: {:?}", e);
+            SdzApiError::Internal
+        })?;
+        Ok(Self {
+            project_id,
+            bearer_token: bearer_token.filter(|token| !token.trim().is_empty()),
+            endpoint: SdzFirestoreEndpoint::from_env(),
+            http,
+            cached_token: Mutex::new(None),
+            retry: SdzRetryPolicy::new("Firestore"),
+        })
+    }
+
+    /// `.../documents/{path}` のURL。pathが空ならdocumentsのルート
+    pub fn document_url(&self, path: &str) -> String {
+        let root = self.endpoint.documents_url(&self.project_id);
+        if path.is_empty() {
+            root
+        } else {
+            format!("{}/{}", root, path)
+        }
+    }
+
+    /// referenceValueなどで使う `projects/.../documents/{path}` 形式のリソース名
+    pub fn document_name(&self, path: &str) -> String {
+        format!(
+            "projects/{}/databases/(default)/documents/{}",
+            self.project_id, path
+        )
+    }
+
+    /// `projects/...` から始まるリソース名をURLにする
+    pub fn resource_url(&self, name: &str) -> String {
+        self.endpoint.resource_url(name)
+    }
+
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, SdzFirestoreError> {
+        self.send_json(true, Method::GET, url, query, None).await
+    }
+
+    /// runQuery / runAggregationQuery など読み取り専用のPOST
+    pub async fn query<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<T, SdzFirestoreError> {
+        self.send_json(true, Method::POST, url, &[], Some(body))
+            .await
+    }
+
+    pub async fn patch(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+        body: &serde_json::Value,
+    ) -> Result<(), SdzFirestoreError> {
+        self.retry
+            .execute(is_unconditional(query), || async {
+                self.send(Method::PATCH, url, query, Some(body))
+                    .await
+                    .map(|_| ())
+            })
+            .await
+    }
+
+    pub async fn delete(&self, url: &str, query: &[(&str, &str)]) -> Result<(), SdzFirestoreError> {
+        self.retry
+            .execute(is_unconditional(query), || async {
+                self.send(Method::DELETE, url, query, None)
+                    .await
+                    .map(|_| ())
+            })
+            .await
+    }
+
+    /// `documents:commit`で複数の書き込みをアトミックに適用する。
+    /// 前提条件のない上書きだけなら再送しても結果が変わらないため再試行する
+    pub async fn commit(&self, writes: Vec<serde_json::Value>) -> Result<(), SdzFirestoreError> {
+        let idempotent = writes
+            .iter()
+            .all(|write| write.get("currentDocument").is_none());
+        let url = format!("{}:commit", self.document_url(""));
+        let body = serde_json::json!({ "writes": writes });
+        self.retry
+            .execute(idempotent, || async {
+                self.send(Method::POST, &url, &[], Some(&body))
+                    .await
+                    .map(|_| ())
+            })
+            .await
+    }
+
+    /// 一時的な失敗は冪等な操作に限って再試行する。レスポンスの読み取りまでを1回の試行とする
+    async fn send_json<T: DeserializeOwned>(
+        &self,
+        idempotent: bool,
+        method: Method,
+        url: &str,
+        query: &[(&str, &str)],
+        body: Option<&serde_json::Value>,
+    ) -> Result<T, SdzFirestoreError> {
+        self.retry
+            .execute(idempotent, || async {
+                let resp = self.send(method.clone(), url, query, body).await?;
+                parse_json(resp).await
+            })
+            .await
+    }
+
+    /// 成功ステータスのレスポンスのみ返し、それ以外は`SdzFirestoreError`に変換する
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        query: &[(&str, &str)],
+        body: Option<&serde_json::Value>,
+    ) -> Result<Response, SdzFirestoreError> {
+        let token = self.access_token().await?;
+        let mut request = self
+            .http
+            .request(method.clone(), url)
+            .bearer_auth(token)
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let resp = request.send().await.map_err(|e| {
+            tracing::error!("Firestore request error: {} {} {:?}", method, url, e);
+            SdzFirestoreError::Unavailable
+        })?;
+
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
+        }
+        let text = resp.text().await.unwrap_or_default();
+        let err = sdz_classify_firestore_error(status, &text);
+        if err == SdzFirestoreError::NotFound {
+            tracing::debug!("Firestore returned 404: {} {}", method, url);
+        } else {
+            tracing::error!(
+                "Firestore unexpected status {} for {} {} body: {}",
+                status,
+                method,
+                url,
+                text
+            );
+        }
+        Err(err)
+    }
+
+    async fn access_token(&self) -> Result<String, SdzFirestoreError> {
+        if self.endpoint.is_emulator() {
+            return Ok(SDZ_FIRESTORE_EMULATOR_TOKEN.to_string());
+        }
+        if let Some(token) = &self.bearer_token {
+            return Ok(token.clone());
+        }
+        if let Ok(token) = std::env::var("SDZ_FIRESTORE_TOKEN") {
+            if !token.trim().is_empty() {
+                return Ok(token);
+            }
+        }
+        // 取得中はロックを保持し、同時リクエストがメタデータサーバーへ殺到しないようにする
+        let mut cached = self.cached_token.lock().await;
+        if let Some(token) = cached.as_ref().filter(|t| t.is_fresh(Instant::now())) {
+            return Ok(token.access_token.clone());
+        }
+        let token = self.fetch_metadata_token().await?;
+        let access_token = token.access_token.clone();
+        *cached = Some(token);
+        Ok(access_token)
+    }
+
+    async fn fetch_metadata_token(&self) -> Result<SdzCachedToken, SdzFirestoreError> {
+        let resp = self
+            .http
+            .get(SDZ_METADATA_TOKEN_URL)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch metadata token: {:?}", e);
+                SdzFirestoreError::Unavailable
+            })?;
+
+        if !resp.status().is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            tracing::error!("Metadata token error: {}", body);
+            return Err(SdzFirestoreError::PermissionDenied);
+        }
+
+        let token = resp.json::<SdzMetadataToken>().await.map_err(|e| {
+            tracing::error!("Failed to parse metadata token: {:?}", e);
+            SdzFirestoreError::Internal
+        })?;
+        Ok(SdzCachedToken::new(
+            token.access_token,
+            Duration::from_secs(token.expires_in),
+            Instant::now(),
+        ))
+    }
+}
+
+/// `currentDocument`の前提条件付き書き込みは、成功後の再送が前提条件違反になるため再試行しない
+fn is_unconditional(query: &[(&str, &str)]) -> bool {
+    !query
+        .iter()
+        .any(|(key, _)| key.starts_with("currentDocument."))
+}
+
+/// ドキュメント名（`.../spots/{id}`）の末尾からIDを取り出す
+pub fn sdz_firestore_doc_id(name: &str) -> Option<String> {
+    name.split('/')
+        .next_back()
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_string())
+}
+
+/// エラーレスポンスの`error.status`を優先し、なければHTTPステータスで分類する
+fn sdz_classify_firestore_error(status: StatusCode, body: &str) -> SdzFirestoreError {
+    let rpc_status = serde_json::from_str::<FirestoreErrorResponse>(body)
+        .map(|resp| resp.error.status)
+        .unwrap_or_default();
+    match rpc_status.as_str() {
+        "NOT_FOUND" => return SdzFirestoreError::NotFound,
+        "ALREADY_EXISTS" => return SdzFirestoreError::AlreadyExists,
+        "FAILED_PRECONDITION" => return SdzFirestoreError::FailedPrecondition,
+        "INVALID_ARGUMENT" => return SdzFirestoreError::InvalidArgument(body.to_string()),
+        "UNAUTHENTICATED" | "PERMISSION_DENIED" => return SdzFirestoreError::PermissionDenied,
+        // ABORTEDはトランザクションの競合で、前提条件の不一致ではない。再試行すれば解消する
+        "UNAVAILABLE" | "DEADLINE_EXCEEDED" | "RESOURCE_EXHAUSTED" | "ABORTED" => {
+            return SdzFirestoreError::Unavailable
+        }
+        _ => {}
+    }
+    match status {
+        StatusCode::NOT_FOUND => SdzFirestoreError::NotFound,
+        StatusCode::CONFLICT => SdzFirestoreError::AlreadyExists,
+        StatusCode::PRECONDITION_FAILED => SdzFirestoreError::FailedPrecondition,
+        StatusCode::BAD_REQUEST => SdzFirestoreError::InvalidArgument(body.to_string()),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => SdzFirestoreError::PermissionDenied,
+        StatusCode::TOO_MANY_REQUESTS
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => SdzFirestoreError::Unavailable,
+        _ => SdzFirestoreError::Internal,
+    }
+}
+
+async fn parse_json<T: DeserializeOwned>(resp: Response) -> Result<T, SdzFirestoreError> {
+    resp.json::<T>().await.map_err(|e| {
+        tracing::error!("Failed to parse Firestore response: {:?}", e);
+        SdzFirestoreError::Internal
+    })
+}
+
+struct SdzCachedToken {
+    access_token: String,
+    expires_at: Instant,
+}
+
+impl SdzCachedToken {
+    fn new(access_token: String, expires_in: Duration, fetched_at: Instant) -> Self {
+        Self {
+            access_token,
+            expires_at: fetched_at + expires_in,
+        }
+    }
+
+    fn is_fresh(&self, now: Instant) -> bool {
+        now + SDZ_TOKEN_REFRESH_MARGIN < self.expires_at
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SdzMetadataToken {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreErrorResponse {
+    error: FirestoreErrorStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreErrorStatus {
+    #[serde(default)]
+    status: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_token_expires_before_expires_in() {
+        let fetched_at = Instant::now();
+        let token = SdzCachedToken::new("t".into(), Duration::from_secs(3599), fetched_at);
+        assert!(token.is_fresh(fetched_at));
+        assert!(token.is_fresh(fetched_at + Duration::from_secs(3500)));
+        assert!(!token.is_fresh(fetched_at + Duration::from_secs(3540)));
+    }
+
+    #[test]
+    fn classify_prefers_rpc_status_over_http_status() {
+        let precondition =
+            r#"{"error":{"code":400,"message":"stale","status":"FAILED_PRECONDITION"}}"#;
+        assert_eq!(
+            sdz_classify_firestore_error(StatusCode::BAD_REQUEST, precondition),
+            SdzFirestoreError::FailedPrecondition
+        );
+        let aborted = r#"{"error":{"code":409,"message":"contention","status":"ABORTED"}}"#;
+        assert_eq!(
+            sdz_classify_firestore_error(StatusCode::CONFLICT, aborted),
+            SdzFirestoreError::Unavailable
+        );
+        assert_eq!(
+            sdz_classify_firestore_error(StatusCode::NOT_FOUND, ""),
+            SdzFirestoreError::NotFound
+        );
+        assert!(matches!(
+            sdz_classify_firestore_error(StatusCode::BAD_REQUEST, "bad"),
+            SdzFirestoreError::InvalidArgument(_)
+        ));
+        assert_eq!(
+            sdz_classify_firestore_error(StatusCode::SERVICE_UNAVAILABLE, "{}"),
+            SdzFirestoreError::Unavailable
+        );
+        assert_eq!(
+            sdz_firestore_doc_id("projects/p/databases/(default)/documents/spots/abc"),
+            Some("abc".to_string())
+        );
+    }
+}
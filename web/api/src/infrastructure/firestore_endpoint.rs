@@ -0,0 +1,97 @@
+const SDZ_FIRESTORE_DEFAULT_BASE_URL: &str = "https://firestore.googleapis.com";
+
+/// エミュレーターはこのトークンを管理者として扱い、セキュリティルールを適用しない
+pub const SDZ_FIRESTORE_EMULATOR_TOKEN: &str = "owner";
+
+/// Firestore REST APIの接続先。
+/// `SDZ_FIRESTORE_BASE_URL`（例: `http://localhost:8080`）か`FIRESTORE_EMULATOR_HOST`
+/// （例: `localhost:8080`）が設定されていればエミュレーターとみなし、アクセストークンを取得しない。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdzFirestoreEndpoint {
+    base_url: String,
+    emulator: bool,
+}
+
+impl SdzFirestoreEndpoint {
+    pub fn from_env() -> Self {
+        Self::from_settings(
+            std::env::var("SDZ_FIRESTORE_BASE_URL").ok(),
+            std::env::var("FIRESTORE_EMULATOR_HOST").ok(),
+        )
+    }
+
+    fn from_settings(base_url: Option<String>, emulator_host: Option<String>) -> Self {
+        let base_url = base_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .or_else(|| {
+                emulator_host
+                    .map(|host| host.trim().to_string())
+                    .filter(|host| !host.is_empty())
+                    .map(|host| format!("http://{}", host))
+            });
+        match base_url {
+            Some(base_url) => Self {
+                base_url,
+                emulator: true,
+            },
+            None => Self {
+                base_url: SDZ_FIRESTORE_DEFAULT_BASE_URL.to_string(),
+                emulator: false,
+            },
+        }
+    }
+
+    pub fn is_emulator(&self) -> bool {
+        self.emulator
+    }
+
+    /// `.../databases/(default)/documents` までのURL
+    pub fn documents_url(&self, project_id: &str) -> String {
+        format!(
+            "{}/v1/projects/{}/databases/(default)/documents",
+            self.base_url, project_id
+        )
+    }
+
+    /// `projects/...` から始まるリソース名をURLにする
+    pub fn resource_url(&self, name: &str) -> String {
+        format!("{}/v1/{}", self.base_url, name)
+    }
+}
+
+/// エミュレーター接続が設定されているか（build_stateでトークン必須チェックを外すため）
+pub fn sdz_firestore_emulator_configured() -> bool {
+    SdzFirestoreEndpoint::from_env().is_emulator()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_prefers_base_url_then_emulator_host() {
+        let default = SdzFirestoreEndpoint::from_settings(None, None);
+        assert!(!default.is_emulator());
+        assert_eq!(
+            default.documents_url("sdz"),
+            "https://firestore.googleapis.com/v1/projects/sdz/databases/(default)/documents"
+        );
+
+        let emulator = SdzFirestoreEndpoint::from_settings(None, Some("localhost:8080".into()));
+        assert!(emulator.is_emulator());
+        assert_eq!(
+            emulator.resource_url("projects/sdz/databases/(default)/documents/spots/a"),
+            "http://localhost:8080/v1/projects/sdz/databases/(default)/documents/spots/a"
+        );
+
+        let base = SdzFirestoreEndpoint::from_settings(
+            Some("http://127.0.0.1:9090/".into()),
+            Some("localhost:8080".into()),
+        );
+        assert_eq!(
+            base.documents_url("sdz"),
+            "http://127.0.0.1:9090/v1/projects/sdz/databases/(default)/documents"
+        );
+    }
+}
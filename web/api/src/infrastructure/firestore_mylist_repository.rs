@@ -1,164 +1,102 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use chrono::{DateTime, FixedOffset};
-use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
 
 use crate::{
-    application::use_cases::mylist_repository::SdzMyListRepository, domain::models::SdzMyListEntry,
+    application::use_cases::mylist_repository::SdzMyListRepository,
+    domain::models::SdzMyListEntry,
+    infrastructure::firestore_client::{
+        sdz_firestore_doc_id, SdzFirestoreClient, SdzFirestoreError,
+    },
     presentation::error::SdzApiError,
 };
 
+const SDZ_MYLIST_PAGE_SIZE: usize = 300;
+
 pub struct SdzFirestoreMyListRepository {
-    project_id: String,
-    bearer_token: Option<String>,
-    http: Client,
+    client: Arc<SdzFirestoreClient>,
 }
 
 impl SdzFirestoreMyListRepository {
-    pub fn new(project_id: String, bearer_token: Option<String>) -> Result<Self, SdzApiError> {
-        let http = Client::builder().build().map_err(|e| {
-            tracing::error!("Failed to build reqwest client: {:?}", e);
-            SdzApiError::Internal
-        })?;
-        Ok(Self {
-            project_id,
-            bearer_token,
-            http,
-        })
+    pub fn new(client: Arc<SdzFirestoreClient>) -> Self {
+        Self { client }
     }
 
-    async fn upsert_document(&self, user_id: &str, spot_id: &str) -> Result<(), SdzApiError> {
-        let url = format!(
-            "https://firestore.googleapis.com/v1/projects/{}/databases/(default)/documents/users/{}/mylist/{}",
-            self.project_id, user_id, spot_id
-        );
+    fn mylist_url(&self, user_id: &str) -> String {
+        self.client
+            .document_url(&format!("users/{}/mylist", user_id))
+    }
 
+    async fn upsert_document(&self, user_id: &str, spot_id: &str) -> Result<(), SdzApiError> {
+        let url = format!("{}/{}", self.mylist_url(user_id), spot_id);
         let body = json!({
             "fields": {
                 "spotId": { "stringValue": spot_id },
                 "createdAt": { "timestampValue": now_jst_string() }
             }
         });
-        let token = self.resolve_token().await?;
-
-        let resp = self
-            .http
-            .patch(url)
-            .bearer_auth(token)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("Firestore request error: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        map_status(resp.status(), resp.text().await).map(|_| ())
+        Ok(self.client.patch(&url, &[], &body).await?)
     }
 
-    async fn delete_document(&self, user_id: &str, spot_id: &str) -> Result<(), SdzApiError> {
-        let url = format!(
-            "https://firestore.googleapis.com/v1/projects/{}/databases/(default)/documents/users/{}/mylist/{}",
-            self.project_id, user_id, spot_id
-        );
-        let token = self.resolve_token().await?;
-        let resp = self
-            .http
-            .delete(url)
-            .bearer_auth(token)
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("Firestore request error: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        match resp.status() {
-            reqwest::StatusCode::OK | reqwest::StatusCode::NOT_FOUND => Ok(()),
-            code => {
-                let body = resp.text().await.unwrap_or_default();
-                tracing::error!("Firestore unexpected status: {} body: {}", code, body);
-                Err(SdzApiError::Internal)
-            }
+    async fn delete_document_by_url(&self, url: &str) -> Result<(), SdzApiError> {
+        match self.client.delete(url, &[]).await {
+            Ok(()) | Err(SdzFirestoreError::NotFound) => Ok(()),
+            Err(err) => Err(err.into()),
         }
     }
 
     async fn list_documents(&self, user_id: &str) -> Result<Vec<FirestoreMyListDoc>, SdzApiError> {
-        let url = format!(
-            "https://firestore.googleapis.com/v1/projects/{}/databases/(default)/documents/users/{}/mylist",
-            self.project_id, user_id
-        );
-        let token = self.resolve_token().await?;
-        let resp = self
-            .http
-            .get(url)
-            .bearer_auth(token)
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("Firestore request error: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        match resp.status() {
-            reqwest::StatusCode::OK => {
-                let list = resp.json::<FirestoreListResponse>().await.map_err(|e| {
-                    tracing::error!("Failed to parse Firestore response: {:?}", e);
-                    SdzApiError::Internal
-                })?;
-                Ok(list.documents.unwrap_or_default())
+        let url = self.mylist_url(user_id);
+        let page_size = SDZ_MYLIST_PAGE_SIZE.to_string();
+        let mut documents = Vec::new();
+        let mut page_token: Option<String> = None;
+        // listDocumentsはページ単位で返るため、nextPageTokenを辿って全件取得する
+        loop {
+            let mut query = vec![("pageSize", page_size.as_str())];
+            if let Some(page_token) = &page_token {
+                query.push(("pageToken", page_token.as_str()));
             }
-            reqwest::StatusCode::NOT_FOUND => Ok(vec![]),
-            code => {
-                let body = resp.text().await.unwrap_or_default();
-                tracing::error!("Firestore unexpected status: {} body: {}", code, body);
-                Err(SdzApiError::Internal)
+            let list = match self.client.get::<FirestoreListResponse>(&url, &query).await {
+                Ok(list) => list,
+                Err(SdzFirestoreError::NotFound) => return Ok(documents),
+                Err(err) => return Err(err.into()),
+            };
+            documents.extend(list.documents.unwrap_or_default());
+            match list.next_page_token.filter(|t| !t.is_empty()) {
+                Some(next) => page_token = Some(next),
+                None => return Ok(documents),
             }
         }
     }
 
-    async fn resolve_token(&self) -> Result<String, SdzApiError> {
-        if let Some(token) = self
-            .bearer_token
-            .as_ref()
-            .filter(|token| !token.trim().is_empty())
-        {
-            return Ok(token.to_string());
-        }
-        if let Ok(token) = std::env::var("SDZ_FIRESTORE_TOKEN") {
-            if !token.trim().is_empty() {
-                return Ok(token);
+    /// 全ユーザーのmylistサブコレクションから指定スポットのドキュメント名を集める
+    async fn query_documents_by_spot(&self, spot_id: &str) -> Result<Vec<String>, SdzApiError> {
+        let url = format!("{}:runQuery", self.client.document_url(""));
+        let body = json!({
+            "structuredQuery": {
+                "from": [{ "collectionId": "mylist", "allDescendants": true }],
+                "where": {
+                    "fieldFilter": {
+                        "field": { "fieldPath": "spotId" },
+                        "op": "EQUAL",
+                        "value": { "stringValue": spot_id }
+                    }
+                },
+                "select": { "fields": [{ "fieldPath": "__name__" }] },
+                "limit": SDZ_MYLIST_PAGE_SIZE
             }
-        }
-        self.fetch_metadata_token().await
-    }
-
-    async fn fetch_metadata_token(&self) -> Result<String, SdzApiError> {
-        let metadata_url = "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
-        let resp = self
-            .http
-            .get(metadata_url)
-            .header("Metadata-Flavor", "Google")
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to fetch metadata token: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        if !resp.status().is_success() {
-            let body = resp.text().await.unwrap_or_default();
-            tracing::error!("Metadata token error: {}", body);
-            return Err(SdzApiError::Internal);
-        }
-
-        let token = resp.json::<SdzMetadataToken>().await.map_err(|e| {
-            tracing::error!("Failed to parse metadata token: {:?}", e);
-            SdzApiError::Internal
-        })?;
-
-        Ok(token.access_token)
+        });
+        let rows = self
+            .client
+            .query::<Vec<FirestoreRunQueryRow>>(&url, &body)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row.document.map(|doc| doc.name))
+            .collect())
     }
 }
 
@@ -180,13 +118,46 @@ impl SdzMyListRepository for SdzFirestoreMyListRepository {
     }
 
     async fn remove(&self, user_id: &str, spot_id: &str) -> Result<(), SdzApiError> {
-        self.delete_document(user_id, spot_id).await
+        let url = format!("{}/{}", self.mylist_url(user_id), spot_id);
+        self.delete_document_by_url(&url).await
+    }
+
+    async fn remove_spot_from_all(&self, spot_id: &str) -> Result<usize, SdzApiError> {
+        let mut removed = 0;
+        // 削除済みのドキュメントは次のクエリに現れないため、空になるまで先頭ページを繰り返し処理する
+        loop {
+            let names = self.query_documents_by_spot(spot_id).await?;
+            if names.is_empty() {
+                return Ok(removed);
+            }
+            let page_len = names.len();
+            for name in names {
+                self.delete_document_by_url(&self.client.resource_url(&name))
+                    .await?;
+            }
+            removed += page_len;
+            if page_len < SDZ_MYLIST_PAGE_SIZE {
+                return Ok(removed);
+            }
+        }
     }
 }
 
 #[derive(Debug, Deserialize)]
 struct FirestoreListResponse {
     documents: Option<Vec<FirestoreMyListDoc>>,
+    #[serde(rename = "nextPageToken")]
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreRunQueryRow {
+    document: Option<FirestoreDocName>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreDocName {
+    name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -201,7 +172,7 @@ impl FirestoreMyListDoc {
             .fields
             .as_ref()
             .and_then(|fields| fields.spot_id.as_ref().map(|f| f.string_value.clone()))
-            .or_else(|| extract_doc_id(&self.name))?;
+            .or_else(|| sdz_firestore_doc_id(&self.name))?;
         let created_at = self
             .fields
             .as_ref()
@@ -235,16 +206,6 @@ struct TimestampField {
     timestamp_value: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct SdzMetadataToken {
-    #[serde(rename = "access_token")]
-    access_token: String,
-}
-
-fn extract_doc_id(name: &str) -> Option<String> {
-    name.split('/').next_back().map(|value| value.to_string())
-}
-
 fn now_jst_string() -> String {
     now_jst().to_rfc3339()
 }
@@ -253,15 +214,3 @@ fn now_jst() -> DateTime<FixedOffset> {
     let offset = FixedOffset::east_opt(9 * 3600).expect("valid offset");
     chrono::Utc::now().with_timezone(&offset)
 }
-
-fn map_status(
-    status: reqwest::StatusCode,
-    body: Result<String, reqwest::Error>,
-) -> Result<(), SdzApiError> {
-    if status.is_success() {
-        return Ok(());
-    }
-    let text = body.unwrap_or_default();
-    tracing::error!("Firestore unexpected status: {} body: {}", status, text);
-    Err(SdzApiError::Internal)
-}
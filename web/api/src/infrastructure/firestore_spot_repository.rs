@@ -1,96 +1,372 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map};
 
 use crate::{
-    application::use_cases::spot_repository::SdzSpotRepository,
-    domain::models::{
-        SdzSpot, SdzSpotApprovalStatus, SdzSpotBusinessHours, SdzSpotBusinessScheduleType,
-        SdzSpotLocation, SdzSpotParkAttributes, SdzSpotTimeRange, SdzStreetAttributes,
-        SdzStreetSection, SdzStreetSurfaceCondition,
+    application::use_cases::{
+        list_spots_use_case::SdzSpotTypeFilter,
+        pagination::{sdz_sort_spots_newest_first, SdzPageCursor, SdzSortCursor, SdzSortKey},
+        spot_repository::{
+            sdz_validate_batch_upsert, SdzSpotOrder, SdzSpotQuery, SdzSpotRepository,
+            SdzSpotVersionedWrite, SdzSpotsInBounds,
+        },
+    },
+    domain::{
+        geo::{sdz_encode_geohash, sdz_geohash_cells_for_bounds, SDZ_GEOHASH_PRECISION},
+        models::{
+            SdzSpot, SdzSpotApprovalStatus, SdzSpotBounds, SdzSpotBusinessHours,
+            SdzSpotBusinessScheduleType, SdzSpotFieldMask, SdzSpotLocation, SdzSpotParkAttributes,
+            SdzSpotTimeRange, SdzStreetAttributes, SdzStreetSection, SdzStreetSurfaceCondition,
+        },
+        search::sdz_normalize_search_text,
+    },
+    infrastructure::firestore_client::{
+        sdz_firestore_doc_id, SdzFirestoreClient, SdzFirestoreError,
     },
     presentation::error::SdzApiError,
 };
 
+/// 1回のrunQueryで取得する最大件数
+const SDZ_QUERY_MAX_LIMIT: usize = 500;
+/// 矩形検索で発行するgeohash範囲クエリの最大数
+const SDZ_BOUNDS_MAX_CELLS: usize = 9;
+/// 矩形検索で走査するドキュメント数の上限。超えた分は取得せず`truncated`で知らせる。
+/// 新着順に並べるためページごとに矩形全体を走査し直すので、2ページ目以降も1ページ目と同じ件数を読む
+const SDZ_BOUNDS_SCAN_LIMIT: usize = 5_000;
+
+/// 保存時にAPIのフィールドから導出している非正規化フィールド。
+/// 導入前に保存されたドキュメントには`backfill_derived_fields`で後付けする
+const SDZ_DERIVED_FIELDS: &[&str] = &[
+    "archived",
+    "geohash",
+    "isPark",
+    "isStreet",
+    "nameSort",
+    "ratingCountSort",
+    "ratingSort",
+];
+/// 評価なしのスポットの`ratingSort`。評価の高い順で末尾に並ぶ
+const SDZ_RATING_SORT_NONE: f64 = -1.0;
+
+/// `backfill_derived_fields`の結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SdzSpotBackfillReport {
+    pub scanned: usize,
+    /// 非正規化フィールドが欠けているか古いため書き込んだ件数（dry runでは書き込む予定の件数）
+    pub updated: usize,
+    /// フィールドの型が想定と異なり読み取れなかったドキュメント名
+    pub skipped: Vec<String>,
+    /// 読み取り後に更新されたため書き込まなかったドキュメント名（再実行で反映される）
+    pub conflicted: Vec<String>,
+}
+
 pub struct SdzFirestoreSpotRepository {
-    project_id: String,
-    bearer_token: Option<String>,
-    http: Client,
+    client: Arc<SdzFirestoreClient>,
 }
 
 impl SdzFirestoreSpotRepository {
-    pub fn new(project_id: String, bearer_token: Option<String>) -> Result<Self, SdzApiError> {
-        let http = Client::builder().build().map_err(|e| {
-            tracing::error!("Failed to build reqwest client: {:?}", e);
-            SdzApiError::Internal
-        })?;
-        Ok(Self {
-            project_id,
-            bearer_token,
-            http,
-        })
+    pub fn new(client: Arc<SdzFirestoreClient>) -> Self {
+        Self { client }
     }
 
-    fn resolve_token(&self) -> &str {
-        self.bearer_token.as_deref().unwrap_or("")
+    fn document_url(&self, spot_id: &str) -> String {
+        self.client.document_url(&format!("spots/{}", spot_id))
     }
 
     async fn upsert_document(&self, spot: &SdzSpot) -> Result<(), SdzApiError> {
-        let url = format!(
-            "https://firestore.googleapis.com/v1/projects/{}/databases/(default)/documents/spots/{}",
-            self.project_id, spot.sdz_spot_id
-        );
-
-        let body = build_firestore_doc(spot)?;
-
-        let resp = self
-            .http
-            .patch(url)
-            .bearer_auth(self.resolve_token())
-            .json(&body)
-            .send()
+        self.patch_document(spot, SdzSpotWriteCondition::None, None)
             .await
-            .map_err(|e| {
-                tracing::error!("Firestore request error: {:?}", e);
-                SdzApiError::Internal
-            })?;
+    }
 
-        map_status(resp.status(), resp.text().await).map(|_| ())
+    /// `mask`を指定するとupdateMaskに含めたフィールドだけを書き込み、それ以外のフィールド
+    /// （BigQuery連携などRustのモデルが知らないものを含む）は保持する。
+    /// マスクにあって値のないフィールドはFirestore側で削除される
+    async fn patch_document(
+        &self,
+        spot: &SdzSpot,
+        condition: SdzSpotWriteCondition<'_>,
+        mask: Option<&SdzSpotFieldMask>,
+    ) -> Result<(), SdzApiError> {
+        let url = self.document_url(&spot.sdz_spot_id);
+        let mut body = build_firestore_doc(spot)?;
+        let mut query: Vec<(&str, &str)> = match condition {
+            SdzSpotWriteCondition::None => Vec::new(),
+            SdzSpotWriteCondition::Exists => vec![("currentDocument.exists", "true")],
+            SdzSpotWriteCondition::UpdateTime(update_time) => {
+                vec![("currentDocument.updateTime", update_time)]
+            }
+        };
+        if let Some(mask) = mask {
+            let paths = firestore_mask_paths(mask);
+            if let Some(fields) = body["fields"].as_object_mut() {
+                fields.retain(|key, _| paths.contains(&key.as_str()));
+            }
+            query.extend(
+                paths
+                    .into_iter()
+                    .map(|path| ("updateMask.fieldPaths", path)),
+            );
+        }
+        match self.client.patch(&url, &query, &body).await {
+            Ok(()) => Ok(()),
+            Err(SdzFirestoreError::FailedPrecondition) => Err(SdzApiError::PreconditionFailed(
+                "spot was modified by another request".into(),
+            )),
+            Err(err) => Err(err.into()),
+        }
     }
 
     async fn get_document(&self, spot_id: &str) -> Result<Option<FirestoreSpotDoc>, SdzApiError> {
-        let url = format!(
-            "https://firestore.googleapis.com/v1/projects/{}/databases/(default)/documents/spots/{}",
-            self.project_id, spot_id
-        );
-        let resp = self
-            .http
-            .get(url)
-            .bearer_auth(self.resolve_token())
-            .send()
+        match self.client.get(&self.document_url(spot_id), &[]).await {
+            Ok(doc) => Ok(Some(doc)),
+            Err(SdzFirestoreError::NotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn delete_document(&self, spot_id: &str) -> Result<(), SdzApiError> {
+        // 存在しないドキュメントの削除も成功扱いになるため、exists前提条件でNotFoundを検出する
+        Ok(self
+            .client
+            .delete(
+                &self.document_url(spot_id),
+                &[("currentDocument.exists", "true")],
+            )
+            .await?)
+    }
+
+    async fn run_query(&self, body: &serde_json::Value) -> Result<Vec<SdzSpot>, SdzApiError> {
+        let url = format!("{}:runQuery", self.client.document_url(""));
+        let rows = self
+            .client
+            .query::<Vec<FirestoreRunQueryResponse>>(&url, body)
+            .await?;
+
+        let mut spots = Vec::new();
+        for row in rows {
+            if let Some(doc) = row.document {
+                if let Some(spot_id) = sdz_firestore_doc_id(&doc.name) {
+                    spots.push(doc.into_spot(spot_id));
+                }
+            }
+        }
+        Ok(spots)
+    }
+}
+
+impl SdzFirestoreSpotRepository {
+    /// 全スポットの非正規化フィールドを現在の値から計算し直し、保存値と異なるものだけ書き込む。
+    /// updateMaskで非正規化フィールドだけを書き込むため、updatedAtや管理外のフィールドは変わらない
+    pub async fn backfill_derived_fields(
+        &self,
+        dry_run: bool,
+    ) -> Result<SdzSpotBackfillReport, SdzApiError> {
+        let url = format!("{}:runQuery", self.client.document_url(""));
+        let mut report = SdzSpotBackfillReport::default();
+        let mut after: Option<String> = None;
+        loop {
+            let mut structured = json!({
+                "from": [{ "collectionId": "spots" }],
+                "orderBy": [{
+                    "field": { "fieldPath": "__name__" },
+                    "direction": "ASCENDING"
+                }],
+                "limit": SDZ_QUERY_MAX_LIMIT
+            });
+            if let Some(name) = &after {
+                structured["startAt"] = json!({
+                    "values": [{ "referenceValue": name }],
+                    "before": false
+                });
+            }
+            let rows = self
+                .client
+                .query::<Vec<FirestoreRawRunQueryResponse>>(
+                    &url,
+                    &json!({ "structuredQuery": structured }),
+                )
+                .await?;
+            let docs: Vec<FirestoreRawDoc> =
+                rows.into_iter().filter_map(|row| row.document).collect();
+            let exhausted = docs.len() < SDZ_QUERY_MAX_LIMIT;
+            after = docs.last().map(|doc| doc.name.clone());
+
+            let mut writes = Vec::new();
+            for doc in docs {
+                report.scanned += 1;
+                match build_derived_fields_write(&doc) {
+                    Ok(Some(write)) => writes.push((doc.name, write)),
+                    Ok(None) => {}
+                    Err(message) => {
+                        tracing::warn!(document = %doc.name, "skip backfill: {}", message);
+                        report.skipped.push(doc.name);
+                    }
+                }
+            }
+            if dry_run {
+                report.updated += writes.len();
+            } else if !writes.is_empty() {
+                self.commit_backfill(writes, &mut report).await?;
+            }
+            if exhausted {
+                return Ok(report);
+            }
+        }
+    }
+
+    async fn commit_backfill(
+        &self,
+        writes: Vec<(String, serde_json::Value)>,
+        report: &mut SdzSpotBackfillReport,
+    ) -> Result<(), SdzApiError> {
+        let batch = writes.iter().map(|(_, write)| write.clone()).collect();
+        match self.client.commit(batch).await {
+            Ok(()) => {
+                report.updated += writes.len();
+                Ok(())
+            }
+            Err(SdzFirestoreError::FailedPrecondition) => {
+                // 読み取り後に更新されたドキュメントが1件でもあるとバッチ全体が失敗するため、1件ずつ書き直す
+                for (name, write) in writes {
+                    match self.client.commit(vec![write]).await {
+                        Ok(()) => report.updated += 1,
+                        Err(SdzFirestoreError::FailedPrecondition) => report.conflicted.push(name),
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+                Ok(())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn query_recent(
+        &self,
+        query: &SdzSpotQuery,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError> {
+        let limit = limit.min(SDZ_QUERY_MAX_LIMIT) as i32;
+        let filters = build_query_filters(query);
+        let mut structured = json!({
+            "from": [{ "collectionId": "spots" }],
+            "orderBy": [
+                {
+                    "field": { "fieldPath": "createdAt" },
+                    "direction": "DESCENDING"
+                },
+                {
+                    "field": { "fieldPath": "__name__" },
+                    "direction": "DESCENDING"
+                }
+            ],
+            "limit": limit
+        });
+        if let Some(filter) = combine_filters(filters) {
+            structured["where"] = filter;
+        }
+        if let Some(cursor) = after {
+            // startAt + before=false でカーソル位置の次から取得する（startAfter相当）
+            structured["startAt"] = json!({
+                "values": [
+                    { "timestampValue": cursor.created_at.to_rfc3339() },
+                    { "referenceValue": self.client.document_name(&format!("spots/{}", cursor.sdz_spot_id)) }
+                ],
+                "before": false
+            });
+        }
+        self.run_query(&json!({ "structuredQuery": structured }))
             .await
-            .map_err(|e| {
-                tracing::error!("Firestore request error: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        match resp.status() {
-            reqwest::StatusCode::OK => {
-                let doc = resp.json::<FirestoreSpotDoc>().await.map_err(|e| {
-                    tracing::error!("Failed to parse Firestore response: {:?}", e);
-                    SdzApiError::Internal
-                })?;
-                Ok(Some(doc))
+    }
+
+    /// geohashセル内のスポットを`spots`に追加する。geohash順・ドキュメント名順にページングし、
+    /// `budget`件を走査した時点で続きが残っていればtrueを返す
+    async fn scan_geohash_cell(
+        &self,
+        cell: &str,
+        query: &SdzSpotQuery,
+        budget: usize,
+        spots: &mut Vec<SdzSpot>,
+    ) -> Result<bool, SdzApiError> {
+        let mut scanned = 0;
+        let mut after: Option<(String, String)> = None;
+        loop {
+            if scanned >= budget {
+                return Ok(true);
+            }
+            let limit = (budget - scanned).min(SDZ_QUERY_MAX_LIMIT);
+            let after_ref = after
+                .as_ref()
+                .map(|(geohash, name)| (geohash.as_str(), name.as_str()));
+            let body = build_geohash_cell_query(cell, query, limit, after_ref);
+            let page = self.run_query(&body).await?;
+            scanned += page.len();
+            let exhausted = page.len() < limit;
+            // 保存済みのgeohashは位置情報から導出しているため、カーソルも同じ計算で復元できる
+            after = page.last().and_then(|spot| {
+                spot.location.as_ref().map(|loc| {
+                    (
+                        sdz_encode_geohash(loc.lat, loc.lng, SDZ_GEOHASH_PRECISION),
+                        self.client
+                            .document_name(&format!("spots/{}", spot.sdz_spot_id)),
+                    )
+                })
+            });
+            spots.extend(page);
+            if exhausted {
+                return Ok(false);
             }
-            reqwest::StatusCode::NOT_FOUND => Ok(None),
-            code => {
-                let body = resp.text().await.unwrap_or_default();
-                tracing::error!("Firestore unexpected status: {} body: {}", code, body);
-                Err(SdzApiError::Internal)
+            if after.is_none() {
+                return Ok(true);
             }
         }
     }
+
+    async fn query_sorted(
+        &self,
+        query: &SdzSpotQuery,
+        order: SdzSpotOrder,
+        limit: usize,
+        after: Option<&SdzSortCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError> {
+        let after = after.map(|cursor| {
+            (
+                cursor,
+                self.client
+                    .document_name(&format!("spots/{}", cursor.sdz_spot_id)),
+            )
+        });
+        let body = build_sorted_query(
+            query,
+            order,
+            limit.min(SDZ_QUERY_MAX_LIMIT),
+            after
+                .as_ref()
+                .map(|(cursor, name)| (&cursor.key, name.as_str())),
+        );
+        self.run_query(&body).await
+    }
+
+    async fn run_count_query(&self, body: &serde_json::Value) -> Result<usize, SdzApiError> {
+        let url = format!("{}:runAggregationQuery", self.client.document_url(""));
+        let rows = self
+            .client
+            .query::<Vec<FirestoreAggregationResponse>>(&url, body)
+            .await?;
+
+        let count = rows
+            .into_iter()
+            .find_map(|row| row.result)
+            .and_then(|mut result| result.aggregate_fields.remove("count"))
+            .and_then(|field| field.integer_value.parse::<usize>().ok())
+            .unwrap_or(0);
+        Ok(count)
+    }
 }
 
 #[async_trait]
@@ -100,8 +376,93 @@ impl SdzSpotRepository for SdzFirestoreSpotRepository {
         Ok(spot)
     }
 
-    async fn update(&self, spot: SdzSpot) -> Result<SdzSpot, SdzApiError> {
-        self.upsert_document(&spot).await?;
+    async fn update(&self, spot: SdzSpot, mask: &SdzSpotFieldMask) -> Result<SdzSpot, SdzApiError> {
+        self.patch_document(&spot, SdzSpotWriteCondition::Exists, Some(mask))
+            .await?;
+        Ok(spot)
+    }
+
+    async fn upsert_many(&self, spots: Vec<SdzSpot>) -> Result<(), SdzApiError> {
+        sdz_validate_batch_upsert(&spots)?;
+        if spots.is_empty() {
+            return Ok(());
+        }
+        // commitは1リクエスト内の書き込みをまとめて適用し、1件でも失敗すれば何も書き込まない
+        let writes = spots
+            .iter()
+            .map(|spot| {
+                let document_name = self
+                    .client
+                    .document_name(&format!("spots/{}", spot.sdz_spot_id));
+                build_upsert_write(&document_name, spot)
+            })
+            .collect::<Result<Vec<_>, SdzApiError>>()?;
+        Ok(self.client.commit(writes).await?)
+    }
+
+    async fn upsert_many_if_unchanged(
+        &self,
+        writes: Vec<SdzSpotVersionedWrite>,
+    ) -> Result<Vec<String>, SdzApiError> {
+        let spots: Vec<SdzSpot> = writes.iter().map(|write| write.spot.clone()).collect();
+        sdz_validate_batch_upsert(&spots)?;
+        if writes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let writes = writes
+            .iter()
+            .map(|write| {
+                let document_name = self
+                    .client
+                    .document_name(&format!("spots/{}", write.spot.sdz_spot_id));
+                Ok((
+                    write.spot.sdz_spot_id.clone(),
+                    build_versioned_upsert_write(&document_name, write)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, SdzApiError>>()?;
+        let batch = writes.iter().map(|(_, write)| write.clone()).collect();
+        match self.client.commit(batch).await {
+            Ok(()) => Ok(Vec::new()),
+            Err(err) if is_write_conflict(&err) => {
+                // 前提が崩れた行が1件でもあるとバッチ全体が失敗するため、1件ずつ書き直して崩れた行だけを返す
+                let mut conflicted = Vec::new();
+                for (spot_id, write) in writes {
+                    match self.client.commit(vec![write]).await {
+                        Ok(()) => {}
+                        Err(err) if is_write_conflict(&err) => conflicted.push(spot_id),
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+                Ok(conflicted)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn update_if_unchanged(
+        &self,
+        spot: SdzSpot,
+        expected: &SdzSpot,
+        mask: &SdzSpotFieldMask,
+    ) -> Result<SdzSpot, SdzApiError> {
+        let Some(mut doc) = self.get_document(&spot.sdz_spot_id).await? else {
+            return Err(SdzApiError::NotFound);
+        };
+        // updatedAtで読み取り時点からの変更を検出し、ドキュメントのupdateTimeを
+        // 前提条件にして確認から書き込みまでの間の更新も検出する
+        let update_time = doc.update_time.take();
+        let current = doc.into_spot(spot.sdz_spot_id.clone());
+        if current.etag() != expected.etag() {
+            return Err(SdzApiError::PreconditionFailed(
+                "spot was modified by another request".into(),
+            ));
+        }
+        let condition = match update_time.as_deref() {
+            Some(update_time) => SdzSpotWriteCondition::UpdateTime(update_time),
+            None => SdzSpotWriteCondition::Exists,
+        };
+        self.patch_document(&spot, condition, Some(mask)).await?;
         Ok(spot)
     }
 
@@ -113,75 +474,467 @@ impl SdzSpotRepository for SdzFirestoreSpotRepository {
         Ok(Some(doc.into_spot(spot_id.to_string())))
     }
 
-    async fn list_recent(&self, _limit: usize) -> Result<Vec<SdzSpot>, SdzApiError> {
-        let limit = _limit.min(100) as i32;
-        let url = format!(
-            "https://firestore.googleapis.com/v1/projects/{}/databases/(default)/documents:runQuery",
-            self.project_id
-        );
+    async fn find_versioned(
+        &self,
+        spot_id: &str,
+    ) -> Result<Option<(SdzSpot, String)>, SdzApiError> {
+        let Some(mut doc) = self.get_document(spot_id).await? else {
+            return Ok(None);
+        };
+        // 取得したドキュメントには必ずupdateTimeが付く
+        let update_time = doc.update_time.take().ok_or(SdzApiError::Internal)?;
+        Ok(Some((doc.into_spot(spot_id.to_string()), update_time)))
+    }
+
+    async fn delete(&self, spot_id: &str) -> Result<(), SdzApiError> {
+        self.delete_document(spot_id).await
+    }
 
+    async fn list_recent(
+        &self,
+        query: &SdzSpotQuery,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError> {
+        if query.archived {
+            return self.query_recent(query, limit, after).await;
+        }
+        // archivedフィールドを持たない既存ドキュメントは等価フィルタに一致しないため、
+        // 公開中の抽出はクエリに含めず、アーカイブ済みを除いた分を追加取得して埋める
+        let limit = limit.min(SDZ_QUERY_MAX_LIMIT);
+        let mut spots = Vec::new();
+        let mut cursor = after.cloned();
+        loop {
+            let fetched = self.query_recent(query, limit, cursor.as_ref()).await?;
+            let exhausted = fetched.len() < limit;
+            cursor = fetched.last().map(SdzPageCursor::from_spot);
+            spots.extend(fetched.into_iter().filter(|spot| !spot.is_archived()));
+            if exhausted || spots.len() >= limit {
+                spots.truncate(limit);
+                return Ok(spots);
+            }
+        }
+    }
+
+    async fn list_sorted(
+        &self,
+        query: &SdzSpotQuery,
+        order: SdzSpotOrder,
+        limit: usize,
+        after: Option<&SdzSortCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError> {
+        if query.archived {
+            return self.query_sorted(query, order, limit, after).await;
+        }
+        // list_recentと同じく、公開中の抽出はアーカイブ済みを除いた分を追加取得して埋める
+        let limit = limit.min(SDZ_QUERY_MAX_LIMIT);
+        let mut spots = Vec::new();
+        let mut cursor = after.cloned();
+        loop {
+            let fetched = self
+                .query_sorted(query, order, limit, cursor.as_ref())
+                .await?;
+            let exhausted = fetched.len() < limit;
+            cursor = fetched
+                .last()
+                .map(|spot| SdzSortCursor::new(order.sort_key(spot), &spot.sdz_spot_id));
+            spots.extend(fetched.into_iter().filter(|spot| !spot.is_archived()));
+            if exhausted || spots.len() >= limit {
+                spots.truncate(limit);
+                return Ok(spots);
+            }
+        }
+    }
+
+    async fn list_in_bounds(
+        &self,
+        bounds: &SdzSpotBounds,
+        query: &SdzSpotQuery,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<SdzSpotsInBounds, SdzApiError> {
+        let cells = sdz_geohash_cells_for_bounds(bounds, SDZ_BOUNDS_MAX_CELLS);
+        let mut spots: Vec<SdzSpot> = Vec::new();
+        let mut truncated = false;
+        if cells.is_empty() {
+            // 全世界に近い範囲はgeohashで絞れないため、新着順にページングして矩形内のものを集める
+            let mut cursor = after.cloned();
+            let mut scanned = 0;
+            loop {
+                let page = self
+                    .list_recent(query, SDZ_QUERY_MAX_LIMIT, cursor.as_ref())
+                    .await?;
+                let exhausted = page.len() < SDZ_QUERY_MAX_LIMIT;
+                scanned += page.len();
+                cursor = page.last().map(SdzPageCursor::from_spot);
+                spots.extend(page.into_iter().filter(|spot| {
+                    spot.location
+                        .as_ref()
+                        .map(|loc| bounds.contains(loc))
+                        .unwrap_or(false)
+                }));
+                if exhausted || spots.len() >= limit {
+                    break;
+                }
+                if scanned >= SDZ_BOUNDS_SCAN_LIMIT {
+                    truncated = true;
+                    break;
+                }
+            }
+        }
+        // セル内はgeohash順にしか取得できないため、新着順に並べ替えられるようセルを全件走査する
+        for cell in cells {
+            let budget = SDZ_BOUNDS_SCAN_LIMIT.saturating_sub(spots.len());
+            if self
+                .scan_geohash_cell(&cell, query, budget, &mut spots)
+                .await?
+            {
+                truncated = true;
+                break;
+            }
+        }
+
+        Ok(SdzSpotsInBounds {
+            spots: select_bounds_page(spots, bounds, query, limit, after),
+            truncated,
+        })
+    }
+
+    async fn has_spot_with_image(&self, image_url: &str) -> Result<bool, SdzApiError> {
         let body = json!({
             "structuredQuery": {
                 "from": [{ "collectionId": "spots" }],
-                "orderBy": [{
-                    "field": { "fieldPath": "createdAt" },
-                    "direction": "DESCENDING"
-                }],
-                "limit": limit
+                "where": field_filter("images", "ARRAY_CONTAINS", string_value(image_url)),
+                "limit": 1
             }
         });
+        Ok(!self.run_query(&body).await?.is_empty())
+    }
 
-        let resp = self
-            .http
-            .post(url)
-            .bearer_auth(self.resolve_token())
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("Firestore runQuery request error: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await.unwrap_or_default();
-            tracing::error!(
-                "Firestore runQuery unexpected status {} body: {}",
-                status,
-                text
-            );
-            return Err(SdzApiError::Internal);
+    async fn count_image_spots_by_user(&self, user_id: &str) -> Result<usize, SdzApiError> {
+        // 画像が空のスポットはimagesフィールドを持たないため、!= [] で画像付きのみを数える。
+        // archivedフィールドを持たない既存ドキュメントは archived == false に一致しないため、
+        // 全件からアーカイブ済みを引いて数える（補完前のアーカイブ済みは数えてしまうが、上限を緩めることはない）
+        let count_body = |archived_only: bool| {
+            let mut filters = vec![
+                field_filter("userId", "EQUAL", string_value(user_id)),
+                field_filter("images", "NOT_EQUAL", json!({ "arrayValue": {} })),
+            ];
+            if archived_only {
+                filters.push(field_filter("archived", "EQUAL", bool_value(true)));
+            }
+            json!({
+                "structuredAggregationQuery": {
+                    "structuredQuery": {
+                        "from": [{ "collectionId": "spots" }],
+                        "where": combine_filters(filters),
+                    },
+                    "aggregations": [{ "alias": "count", "count": {} }]
+                }
+            })
+        };
+        let total = self.run_count_query(&count_body(false)).await?;
+        let archived = self.run_count_query(&count_body(true)).await?;
+        Ok(total.saturating_sub(archived))
+    }
+}
+
+// ─── 検索条件: SdzSpotQuery → where句 ───
+
+fn build_query_filters(query: &SdzSpotQuery) -> Vec<serde_json::Value> {
+    let mut filters = Vec::new();
+    if query.archived {
+        filters.push(field_filter("archived", "EQUAL", bool_value(true)));
+    }
+    if query.approved_only {
+        filters.push(field_filter(
+            "approvalStatus",
+            "EQUAL",
+            string_value(approval_status_as_str(&SdzSpotApprovalStatus::Approved)),
+        ));
+    }
+    match query.spot_type {
+        Some(SdzSpotTypeFilter::Park) => {
+            filters.push(field_filter("isPark", "EQUAL", bool_value(true)))
+        }
+        Some(SdzSpotTypeFilter::Street) => {
+            filters.push(field_filter("isStreet", "EQUAL", bool_value(true)))
         }
+        None => {}
+    }
+    if !query.tags_any.is_empty() {
+        let values: Vec<_> = query.tags_any.iter().map(|tag| string_value(tag)).collect();
+        filters.push(field_filter(
+            "tags",
+            "ARRAY_CONTAINS_ANY",
+            json!({ "arrayValue": { "values": values } }),
+        ));
+    }
+    filters
+}
 
-        let rows = resp
-            .json::<Vec<FirestoreRunQueryResponse>>()
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to parse Firestore runQuery response: {:?}", e);
-                SdzApiError::Internal
-            })?;
+/// 名前順・評価順・更新順のクエリ。同順位はドキュメント名（spotId）で、最後のorderByと同じ向きに並ぶ。
+/// `after`は直前ページ末尾の(並べ替えのキー, ドキュメント名)
+fn build_sorted_query(
+    query: &SdzSpotQuery,
+    order: SdzSpotOrder,
+    limit: usize,
+    after: Option<(&SdzSortKey, &str)>,
+) -> serde_json::Value {
+    let (fields, direction): (&[&str], &str) = match order {
+        SdzSpotOrder::Name => (&["nameSort"], "ASCENDING"),
+        SdzSpotOrder::Rating => (&["ratingSort", "ratingCountSort"], "DESCENDING"),
+        SdzSpotOrder::Updated => (&["updatedAt"], "DESCENDING"),
+    };
+    let order_by: Vec<_> = fields
+        .iter()
+        .chain(std::iter::once(&"__name__"))
+        .map(|field| {
+            json!({
+                "field": { "fieldPath": field },
+                "direction": direction
+            })
+        })
+        .collect();
+    let mut structured = json!({
+        "from": [{ "collectionId": "spots" }],
+        "orderBy": order_by,
+        "limit": limit
+    });
+    if let Some(filter) = combine_filters(build_query_filters(query)) {
+        structured["where"] = filter;
+    }
+    if let Some((key, name)) = after {
+        let mut values = match key {
+            SdzSortKey::Name { name } => vec![string_value(name)],
+            SdzSortKey::Rating { rating, count } => vec![
+                double_value(rating.unwrap_or(SDZ_RATING_SORT_NONE)),
+                integer_value(count),
+            ],
+            SdzSortKey::Updated { updated_at } => {
+                vec![json!({ "timestampValue": updated_at.to_rfc3339() })]
+            }
+            // 他の並び順のキーはここに来ない（ユースケースで並び順との一致を確認済み）
+            SdzSortKey::Relevance { .. } | SdzSortKey::Distance { .. } => Vec::new(),
+        };
+        values.push(json!({ "referenceValue": name }));
+        structured["startAt"] = json!({ "values": values, "before": false });
+    }
+    json!({ "structuredQuery": structured })
+}
 
-        let mut spots = Vec::new();
-        for row in rows {
-            if let Some(doc) = row.document {
-                if let Some(spot_id) = extract_doc_id(&doc.name) {
-                    spots.push(doc.into_spot(spot_id));
-                }
+/// geohashセル（前方一致）の範囲クエリ。`after`は直前ページ末尾の(geohash, ドキュメント名)
+fn build_geohash_cell_query(
+    cell: &str,
+    query: &SdzSpotQuery,
+    limit: usize,
+    after: Option<(&str, &str)>,
+) -> serde_json::Value {
+    let mut filters = build_query_filters(query);
+    filters.push(field_filter(
+        "geohash",
+        "GREATER_THAN_OR_EQUAL",
+        string_value(cell),
+    ));
+    filters.push(field_filter(
+        "geohash",
+        "LESS_THAN",
+        string_value(&format!("{}~", cell)),
+    ));
+    let mut structured = json!({
+        "from": [{ "collectionId": "spots" }],
+        "where": combine_filters(filters),
+        "orderBy": [
+            {
+                "field": { "fieldPath": "geohash" },
+                "direction": "ASCENDING"
+            },
+            {
+                "field": { "fieldPath": "__name__" },
+                "direction": "ASCENDING"
             }
-        }
-        Ok(spots)
+        ],
+        "limit": limit
+    });
+    if let Some((geohash, name)) = after {
+        structured["startAt"] = json!({
+            "values": [
+                { "stringValue": geohash },
+                { "referenceValue": name }
+            ],
+            "before": false
+        });
     }
+    json!({ "structuredQuery": structured })
+}
 
-    async fn count_image_spots_by_user(&self, _user_id: &str) -> Result<usize, SdzApiError> {
-        // Firestore側では簡易実装: 全取得してフィルタ
-        // 今後必要に応じてFirestoreクエリで最適化
-        Ok(0)
+fn field_filter(field: &str, op: &str, value: serde_json::Value) -> serde_json::Value {
+    json!({
+        "fieldFilter": {
+            "field": { "fieldPath": field },
+            "op": op,
+            "value": value
+        }
+    })
+}
+
+fn combine_filters(mut filters: Vec<serde_json::Value>) -> Option<serde_json::Value> {
+    match filters.len() {
+        0 => None,
+        1 => filters.pop(),
+        _ => Some(json!({
+            "compositeFilter": { "op": "AND", "filters": filters }
+        })),
     }
 }
 
 // ─── 書き込み: SdzSpot → Firestore ───
 
+/// 書き込み時の前提条件
+enum SdzSpotWriteCondition<'a> {
+    None,
+    Exists,
+    /// Firestore側のupdateTimeが一致する場合のみ
+    UpdateTime(&'a str),
+}
+
+/// APIのフィールド名のマスクに、そこから導出して保存している非正規化フィールドを加える
+fn firestore_mask_paths(mask: &SdzSpotFieldMask) -> Vec<&'static str> {
+    let mut paths: Vec<&'static str> = Vec::new();
+    for field in mask.iter() {
+        paths.push(field);
+        match field {
+            "location" => paths.push("geohash"),
+            "tags" | "parkAttributes" | "streetAttributes" => {
+                paths.extend(["isPark", "isStreet"]);
+            }
+            "deletedAt" => paths.push("archived"),
+            "name" => paths.push("nameSort"),
+            "googleRating" => paths.push("ratingSort"),
+            "googleRatingCount" => paths.push("ratingCountSort"),
+            _ => {}
+        }
+    }
+    paths.sort_unstable();
+    paths.dedup();
+    paths
+}
+
+/// 走査したスポットから矩形検索の1ページ分を選ぶ。
+/// geohashセルは矩形より広いので実座標で絞り込み直し、geohash順で取得しているためカーソルもここで適用する。
+/// 隣接セルの範囲クエリで重複したスポットは1件にまとめる
+fn select_bounds_page(
+    mut spots: Vec<SdzSpot>,
+    bounds: &SdzSpotBounds,
+    query: &SdzSpotQuery,
+    limit: usize,
+    after: Option<&SdzPageCursor>,
+) -> Vec<SdzSpot> {
+    let mut seen = HashSet::new();
+    spots.retain(|spot| {
+        spot.location
+            .as_ref()
+            .map(|loc| bounds.contains(loc))
+            .unwrap_or(false)
+            && after
+                .map(|cursor| cursor.comes_after(&spot.created_at, &spot.sdz_spot_id))
+                .unwrap_or(true)
+            && spot.is_archived() == query.archived
+            && seen.insert(spot.sdz_spot_id.clone())
+    });
+    sdz_sort_spots_newest_first(&mut spots);
+    spots.truncate(limit);
+    spots
+}
+
+/// 一括保存用の書き込み。APIが管理する全フィールドをupdateMaskに指定して丸ごと置き換え、
+/// BigQuery連携などRustのモデルが知らないフィールドは保持する
+fn build_upsert_write(
+    document_name: &str,
+    spot: &SdzSpot,
+) -> Result<serde_json::Value, SdzApiError> {
+    let mut doc = build_firestore_doc(spot)?;
+    doc["name"] = json!(document_name);
+    Ok(json!({
+        "update": doc,
+        "updateMask": { "fieldPaths": firestore_mask_paths(&SdzSpotFieldMask::all()) },
+    }))
+}
+
+/// 読み取り時点の版を前提条件にした一括保存用の書き込み。
+/// 版がなければドキュメントがまだ存在しないことを前提にする
+fn build_versioned_upsert_write(
+    document_name: &str,
+    write: &SdzSpotVersionedWrite,
+) -> Result<serde_json::Value, SdzApiError> {
+    let mut body = build_upsert_write(document_name, &write.spot)?;
+    body["currentDocument"] = match &write.expected_version {
+        Some(update_time) => json!({ "updateTime": update_time }),
+        None => json!({ "exists": false }),
+    };
+    Ok(body)
+}
+
+/// 前提条件つきの書き込みが、読み取り後の作成・更新・削除によって拒否されたか
+fn is_write_conflict(err: &SdzFirestoreError) -> bool {
+    matches!(
+        err,
+        SdzFirestoreError::FailedPrecondition
+            | SdzFirestoreError::AlreadyExists
+            | SdzFirestoreError::NotFound
+    )
+}
+
+/// 保存済みドキュメントの非正規化フィールドを計算し直し、保存値と異なる場合だけ書き込みを返す。
+/// 読み取り時点のupdateTimeを前提条件にし、その間にAPIから更新されたドキュメントは上書きしない
+fn build_derived_fields_write(doc: &FirestoreRawDoc) -> Result<Option<serde_json::Value>, String> {
+    let spot_id = sdz_firestore_doc_id(&doc.name).ok_or("invalid document name")?;
+    let fields: FirestoreSpotFields =
+        serde_json::from_value(serde_json::Value::Object(doc.fields.clone()))
+            .map_err(|e| e.to_string())?;
+    let spot = FirestoreSpotDoc {
+        fields,
+        update_time: None,
+    }
+    .into_spot(spot_id);
+    let expected = build_firestore_doc(&spot).map_err(|e| e.to_string())?;
+
+    let mut derived = Map::new();
+    let mut changed = false;
+    for field in SDZ_DERIVED_FIELDS {
+        let value = expected["fields"].get(*field);
+        changed |= !firestore_value_eq(value, doc.fields.get(*field));
+        if let Some(value) = value {
+            derived.insert(field.to_string(), value.clone());
+        }
+    }
+    if !changed {
+        return Ok(None);
+    }
+    let mut write = json!({
+        "update": { "name": doc.name, "fields": derived },
+        "updateMask": { "fieldPaths": SDZ_DERIVED_FIELDS },
+        "currentDocument": { "exists": true }
+    });
+    if let Some(update_time) = &doc.update_time {
+        write["currentDocument"] = json!({ "updateTime": update_time });
+    }
+    Ok(Some(write))
+}
+
+/// Firestoreの値を比較する。doubleValueは整数表記（`-1`など）で返ることがあるため数値として比べる
+fn firestore_value_eq(a: Option<&serde_json::Value>, b: Option<&serde_json::Value>) -> bool {
+    let double = |value: Option<&serde_json::Value>| {
+        value
+            .and_then(|value| value.get("doubleValue"))
+            .and_then(|value| value.as_f64())
+    };
+    match (double(a), double(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
 fn build_firestore_doc(spot: &SdzSpot) -> Result<serde_json::Value, SdzApiError> {
     let mut fields = Map::new();
 
@@ -201,6 +954,10 @@ fn build_firestore_doc(spot: &SdzSpot) -> Result<serde_json::Value, SdzApiError>
         );
     }
 
+    // 種別検索用の非正規化フィールド（既存ドキュメントは再保存で付与される）
+    fields.insert("isPark".into(), bool_value(spot.is_park()));
+    fields.insert("isStreet".into(), bool_value(spot.is_street()));
+
     if let Some(attrs) = &spot.sdz_park_attributes {
         if let Some(value) = build_park_attributes(attrs) {
             fields.insert("parkAttributes".into(), value);
@@ -255,6 +1012,11 @@ fn build_firestore_doc(spot: &SdzSpot) -> Result<serde_json::Value, SdzApiError>
                 }
             }),
         );
+        // 矩形検索用。位置情報の更新時にも毎回再計算される
+        fields.insert(
+            "geohash".into(),
+            string_value(&sdz_encode_geohash(loc.lat, loc.lng, SDZ_GEOHASH_PRECISION)),
+        );
     }
 
     fields.insert(
@@ -266,6 +1028,32 @@ fn build_firestore_doc(spot: &SdzSpot) -> Result<serde_json::Value, SdzApiError>
         json!({ "timestampValue": spot.updated_at.to_rfc3339() }),
     );
 
+    // 名前順・評価順の一覧用。orderByのフィールドがないドキュメントは結果に含まれないため常に書き込む
+    fields.insert(
+        "nameSort".into(),
+        string_value(&sdz_normalize_search_text(&spot.name)),
+    );
+    fields.insert(
+        "ratingSort".into(),
+        double_value(spot.sdz_google_rating.unwrap_or(SDZ_RATING_SORT_NONE)),
+    );
+    fields.insert(
+        "ratingCountSort".into(),
+        integer_value(spot.sdz_google_rating_count.unwrap_or(0)),
+    );
+
+    // アーカイブ済みの抽出用。未アーカイブのドキュメントにはfalseを明示する
+    fields.insert("archived".into(), bool_value(spot.is_archived()));
+    if let Some(deleted_at) = &spot.sdz_deleted_at {
+        fields.insert(
+            "deletedAt".into(),
+            json!({ "timestampValue": deleted_at.to_rfc3339() }),
+        );
+    }
+    if let Some(deleted_by) = &spot.sdz_deleted_by {
+        fields.insert("deletedBy".into(), string_value(deleted_by));
+    }
+
     Ok(json!({ "fields": fields }))
 }
 
@@ -428,6 +1216,8 @@ fn schedule_type_as_str(st: &SdzSpotBusinessScheduleType) -> &'static str {
 #[derive(Debug, Serialize, Deserialize)]
 struct FirestoreSpotDoc {
     fields: FirestoreSpotFields,
+    #[serde(rename = "updateTime", default, skip_serializing)]
+    update_time: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -468,6 +1258,10 @@ struct FirestoreSpotFields {
     created_at: Option<TimestampField>,
     #[serde(rename = "updatedAt")]
     updated_at: Option<TimestampField>,
+    #[serde(rename = "deletedAt")]
+    deleted_at: Option<TimestampField>,
+    #[serde(rename = "deletedBy")]
+    deleted_by: Option<StringField>,
     // 旧スキーマ（後方互換読み取り用）
     #[serde(rename = "trustLevel")]
     trust_level: Option<StringField>,
@@ -653,6 +1447,8 @@ impl FirestoreSpotDoc {
             sdz_user_id: fields.user_id.map(|s| s.string_value).unwrap_or_default(),
             created_at,
             updated_at,
+            sdz_deleted_at: parse_timestamp(fields.deleted_at.map(|t| t.timestamp_value)),
+            sdz_deleted_by: fields.deleted_by.map(|s| s.string_value),
         }
     }
 }
@@ -867,34 +1663,37 @@ fn parse_timestamp(ts: Option<String>) -> Option<chrono::DateTime<chrono::FixedO
     ts.and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
 }
 
-fn map_status(
-    status: reqwest::StatusCode,
-    body: Result<String, reqwest::Error>,
-) -> Result<(), SdzApiError> {
-    match status {
-        reqwest::StatusCode::OK | reqwest::StatusCode::CREATED => Ok(()),
-        reqwest::StatusCode::BAD_REQUEST => {
-            tracing::error!("Firestore returned 400: {:?}", body);
-            Err(SdzApiError::BadRequest("invalid data for Firestore".into()))
-        }
-        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
-            tracing::error!("Firestore returned auth error: {:?}", body);
-            Err(SdzApiError::Unauthorized)
-        }
-        reqwest::StatusCode::NOT_FOUND => Err(SdzApiError::NotFound),
-        code => {
-            let text = body.unwrap_or_default();
-            tracing::error!("Firestore unexpected status {} body: {}", code, text);
-            Err(SdzApiError::Internal)
-        }
-    }
-}
-
 #[derive(Debug, Deserialize)]
 struct FirestoreRunQueryResponse {
     document: Option<FirestoreSpotDocWithName>,
 }
 
+/// 非正規化フィールドの補完用。モデルに変換せず保存値のまま比較する
+#[derive(Debug, Deserialize)]
+struct FirestoreRawRunQueryResponse {
+    document: Option<FirestoreRawDoc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreRawDoc {
+    name: String,
+    #[serde(default)]
+    fields: Map<String, serde_json::Value>,
+    #[serde(rename = "updateTime", default)]
+    update_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreAggregationResponse {
+    result: Option<FirestoreAggregationResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreAggregationResult {
+    #[serde(rename = "aggregateFields")]
+    aggregate_fields: HashMap<String, IntegerField>,
+}
+
 #[derive(Debug, Deserialize)]
 struct FirestoreSpotDocWithName {
     name: String,
@@ -905,11 +1704,369 @@ impl FirestoreSpotDocWithName {
     fn into_spot(self, spot_id: String) -> SdzSpot {
         let doc = FirestoreSpotDoc {
             fields: self.fields,
+            update_time: None,
         };
         doc.into_spot(spot_id)
     }
 }
 
-fn extract_doc_id(name: &str) -> Option<String> {
-    name.split('/').next_back().map(|s| s.to_owned())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::SdzCreateSpotParams;
+
+    #[test]
+    fn mask_paths_include_derived_fields() {
+        let mask = SdzSpotFieldMask::from_fields(&["location", "name", "tags", "updatedAt"]);
+        assert_eq!(
+            firestore_mask_paths(&mask),
+            vec![
+                "geohash",
+                "isPark",
+                "isStreet",
+                "location",
+                "name",
+                "nameSort",
+                "tags",
+                "updatedAt"
+            ]
+        );
+        assert_eq!(
+            firestore_mask_paths(&SdzSpotFieldMask::archive_state()),
+            vec!["archived", "deletedAt", "deletedBy", "updatedAt"]
+        );
+    }
+
+    #[test]
+    fn upsert_write_masks_every_managed_field() {
+        let spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: "spot-1".into(),
+            name: "Test Park".into(),
+            description: Some("desc".into()),
+            location: Some(SdzSpotLocation {
+                lat: 35.0,
+                lng: 139.0,
+            }),
+            tags: vec!["park".into()],
+            images: vec!["https://example.com/a.jpg".into()],
+            sdz_approval_status: None,
+            sdz_park_attributes: Some(SdzSpotParkAttributes {
+                official_url: Some("https://example.com".into()),
+                business_hours: None,
+                access_info: None,
+                phone_number: None,
+            }),
+            sdz_street_attributes: None,
+            sdz_instagram_tag: Some("tag".into()),
+            sdz_instagram_location_url: Some("https://instagram.com/l".into()),
+            sdz_instagram_profile_url: Some("https://instagram.com/p".into()),
+            sdz_google_place_id: Some("place".into()),
+            sdz_google_maps_url: Some("https://maps.google.com".into()),
+            sdz_address: Some("address".into()),
+            sdz_phone_number: Some("03-0000-0000".into()),
+            sdz_google_rating: Some(4.5),
+            sdz_google_rating_count: Some(10),
+            sdz_google_types: vec!["park".into()],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap()
+        .archive("admin-1");
+
+        let write = build_upsert_write(
+            "projects/p/databases/(default)/documents/spots/spot-1",
+            &spot,
+        )
+        .unwrap();
+        assert_eq!(
+            write["update"]["name"],
+            "projects/p/databases/(default)/documents/spots/spot-1"
+        );
+        let paths: Vec<&str> = write["updateMask"]["fieldPaths"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|path| path.as_str().unwrap())
+            .collect();
+        for field in write["update"]["fields"].as_object().unwrap().keys() {
+            assert!(paths.contains(&field.as_str()), "{field} is not masked");
+        }
+        for field in SDZ_DERIVED_FIELDS {
+            assert!(paths.contains(field), "{field} is not masked");
+        }
+        // 値のないフィールドも削除されるようにマスクに含める
+        assert!(paths.contains(&"streetAttributes"));
+    }
+
+    #[test]
+    fn bounds_page_continues_after_cursor() {
+        let bounds = SdzSpotBounds::parse("35.0,139.0,36.0,140.0").unwrap();
+        let spot_at = |id: &str, lat: f64, minutes: i64| {
+            let mut spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+                sdz_spot_id: id.into(),
+                name: id.into(),
+                description: None,
+                location: Some(SdzSpotLocation { lat, lng: 139.5 }),
+                tags: vec![],
+                images: vec![],
+                sdz_approval_status: None,
+                sdz_park_attributes: None,
+                sdz_street_attributes: None,
+                sdz_instagram_tag: None,
+                sdz_instagram_location_url: None,
+                sdz_instagram_profile_url: None,
+                sdz_google_place_id: None,
+                sdz_google_maps_url: None,
+                sdz_address: None,
+                sdz_phone_number: None,
+                sdz_google_rating: None,
+                sdz_google_rating_count: None,
+                sdz_google_types: vec![],
+                sdz_user_id: "user-1".into(),
+            })
+            .unwrap();
+            spot.created_at -= chrono::Duration::minutes(minutes);
+            spot
+        };
+        // geohash順（走査順）と新着順は一致しない。隣接セルから同じスポットが重複して返ることもある
+        let scanned = vec![
+            spot_at("d", 35.1, 4),
+            spot_at("a", 35.2, 1),
+            spot_at("outside", 36.5, 0),
+            spot_at("c", 35.3, 3),
+            spot_at("b", 35.4, 2),
+            spot_at("a", 35.2, 1),
+        ];
+        let query = SdzSpotQuery::default();
+
+        let first = select_bounds_page(scanned.clone(), &bounds, &query, 2, None);
+        let ids: Vec<_> = first.iter().map(|s| s.sdz_spot_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+
+        let cursor = SdzPageCursor::from_spot(first.last().unwrap());
+        let second = select_bounds_page(scanned, &bounds, &query, 2, Some(&cursor));
+        let ids: Vec<_> = second.iter().map(|s| s.sdz_spot_id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "d"]);
+    }
+
+    #[test]
+    fn sorted_query_orders_by_sort_fields_and_name() {
+        let query = SdzSpotQuery {
+            approved_only: true,
+            ..Default::default()
+        };
+        let key = SdzSortKey::Rating {
+            rating: None,
+            count: 0,
+        };
+        let body = build_sorted_query(&query, SdzSpotOrder::Rating, 50, Some((&key, "spots/a")));
+        let structured = &body["structuredQuery"];
+        let order_by: Vec<_> = structured["orderBy"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|order| {
+                (
+                    order["field"]["fieldPath"].as_str().unwrap(),
+                    order["direction"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            order_by,
+            vec![
+                ("ratingSort", "DESCENDING"),
+                ("ratingCountSort", "DESCENDING"),
+                ("__name__", "DESCENDING")
+            ]
+        );
+        assert_eq!(
+            structured["startAt"]["values"],
+            json!([
+                { "doubleValue": SDZ_RATING_SORT_NONE },
+                { "integerValue": "0" },
+                { "referenceValue": "spots/a" }
+            ])
+        );
+        assert_eq!(
+            structured["where"]["fieldFilter"]["field"]["fieldPath"],
+            "approvalStatus"
+        );
+    }
+
+    #[test]
+    fn firestore_value_eq_compares_doubles_numerically() {
+        let stored = json!({ "doubleValue": -1 });
+        let built = double_value(SDZ_RATING_SORT_NONE);
+        assert!(firestore_value_eq(Some(&stored), Some(&built)));
+        assert!(!firestore_value_eq(Some(&built), None));
+        assert!(!firestore_value_eq(
+            Some(&string_value("a")),
+            Some(&string_value("b"))
+        ));
+    }
+
+    #[test]
+    fn geohash_cell_query_pages_after_cursor() {
+        let query = SdzSpotQuery::default();
+        let first = build_geohash_cell_query("xn76", &query, 500, None);
+        assert!(first["structuredQuery"].get("startAt").is_none());
+        assert_eq!(
+            first["structuredQuery"]["orderBy"][1]["field"]["fieldPath"],
+            "__name__"
+        );
+
+        let next = build_geohash_cell_query(
+            "xn76",
+            &query,
+            500,
+            Some((
+                "xn76urx4",
+                "projects/p/databases/(default)/documents/spots/a",
+            )),
+        );
+        let start = &next["structuredQuery"]["startAt"];
+        assert_eq!(start["before"], false);
+        assert_eq!(start["values"][0]["stringValue"], "xn76urx4");
+        assert_eq!(
+            start["values"][1]["referenceValue"],
+            "projects/p/databases/(default)/documents/spots/a"
+        );
+    }
+
+    #[test]
+    fn derived_fields_write_only_when_stale() {
+        let name = "projects/p/databases/(default)/documents/spots/a";
+        let fields = json!({
+            "name": { "stringValue": "park" },
+            "location": { "mapValue": { "fields": {
+                "lat": { "doubleValue": 35.0 },
+                "lng": { "doubleValue": 139.0 }
+            } } },
+            "bqScore": { "integerValue": "3" }
+        });
+        let raw = |fields: serde_json::Value| FirestoreRawDoc {
+            name: name.into(),
+            fields: fields.as_object().unwrap().clone(),
+            update_time: Some("2026-01-01T00:00:00Z".into()),
+        };
+
+        let write = build_derived_fields_write(&raw(fields.clone()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(write["updateMask"]["fieldPaths"], json!(SDZ_DERIVED_FIELDS));
+        assert_eq!(
+            write["currentDocument"]["updateTime"],
+            "2026-01-01T00:00:00Z"
+        );
+        let written = write["update"]["fields"].as_object().unwrap();
+        assert!(written.contains_key("geohash"));
+        assert_eq!(written["isStreet"], json!({ "booleanValue": false }));
+        assert_eq!(written["archived"], json!({ "booleanValue": false }));
+        assert!(!written.contains_key("name"));
+
+        let mut current = fields;
+        for (field, value) in written {
+            current[field] = value.clone();
+        }
+        assert!(build_derived_fields_write(&raw(current)).unwrap().is_none());
+    }
+
+    /// `FIRESTORE_EMULATOR_HOST` が設定されているときだけ、実際のRESTの経路を通す
+    #[tokio::test]
+    async fn firestore_emulator_spot_roundtrip() {
+        if !crate::infrastructure::firestore_endpoint::sdz_firestore_emulator_configured() {
+            return;
+        }
+        let client = SdzFirestoreClient::new("sdz-emulator-test".into(), None).unwrap();
+        let repo = SdzFirestoreSpotRepository::new(Arc::new(client));
+        let spot = SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: uuid::Uuid::new_v4().to_string(),
+            name: "emulator park".into(),
+            description: None,
+            location: Some(SdzSpotLocation {
+                lat: 35.0,
+                lng: 139.0,
+            }),
+            tags: vec!["park".into()],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "user-1".into(),
+        })
+        .unwrap();
+
+        repo.create(spot.clone()).await.unwrap();
+        let found = repo.find_by_id(&spot.sdz_spot_id).await.unwrap().unwrap();
+        assert_eq!(found.name, "emulator park");
+
+        repo.delete(&spot.sdz_spot_id).await.unwrap();
+        assert!(repo.find_by_id(&spot.sdz_spot_id).await.unwrap().is_none());
+
+        let mut renamed = spot.clone();
+        renamed.name = "batched park".into();
+        repo.upsert_many(vec![renamed.clone()]).await.unwrap();
+        let found = repo.find_by_id(&spot.sdz_spot_id).await.unwrap().unwrap();
+        assert_eq!(found.name, "batched park");
+
+        // 管理外のフィールドは一括保存で上書きしても残る
+        let url = repo.document_url(&spot.sdz_spot_id);
+        repo.client
+            .patch(
+                &url,
+                &[("updateMask.fieldPaths", "bqScore")],
+                &json!({ "fields": { "bqScore": { "integerValue": "7" } } }),
+            )
+            .await
+            .unwrap();
+        renamed.name = "rebatched park".into();
+        repo.upsert_many(vec![renamed]).await.unwrap();
+        let raw: FirestoreRawDoc = repo.client.get(&url, &[]).await.unwrap();
+        assert_eq!(raw.fields["bqScore"], json!({ "integerValue": "7" }));
+        assert_eq!(
+            raw.fields["name"],
+            json!({ "stringValue": "rebatched park" })
+        );
+
+        // 読み取り後に更新されたドキュメントは版つきの一括保存で上書きしない
+        let (read, version) = repo
+            .find_versioned(&spot.sdz_spot_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut concurrent = read.clone();
+        concurrent.name = "concurrent park".into();
+        repo.upsert_many(vec![concurrent]).await.unwrap();
+        let mut stale = read;
+        stale.name = "stale park".into();
+        let conflicted = repo
+            .upsert_many_if_unchanged(vec![SdzSpotVersionedWrite {
+                spot: stale.clone(),
+                expected_version: Some(version),
+            }])
+            .await
+            .unwrap();
+        assert_eq!(conflicted, vec![spot.sdz_spot_id.clone()]);
+        let conflicted = repo
+            .upsert_many_if_unchanged(vec![SdzSpotVersionedWrite {
+                spot: stale,
+                expected_version: None,
+            }])
+            .await
+            .unwrap();
+        assert_eq!(conflicted, vec![spot.sdz_spot_id.clone()]);
+        let found = repo.find_by_id(&spot.sdz_spot_id).await.unwrap().unwrap();
+        assert_eq!(found.name, "concurrent park");
+        repo.delete(&spot.sdz_spot_id).await.unwrap();
+    }
 }
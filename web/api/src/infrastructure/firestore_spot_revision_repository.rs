@@ -0,0 +1,283 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    application::use_cases::{
+        pagination::SdzPageCursor, spot_revision_repository::SdzSpotRevisionRepository,
+    },
+    domain::{models::SdzSpot, revision::SdzSpotRevision},
+    infrastructure::firestore_client::{
+        sdz_firestore_doc_id, SdzFirestoreClient, SdzFirestoreError,
+    },
+    presentation::error::SdzApiError,
+};
+
+/// 1回のクエリで取得する版の上限
+const SDZ_REVISION_QUERY_MAX_LIMIT: usize = 200;
+
+/// `spots/{spotId}/revisions/{revisionId}` に版を保存する。
+/// snapshotはFirestoreの型付きフィールドに展開せず、APIのJSON表現を文字列で持つ
+pub struct SdzFirestoreSpotRevisionRepository {
+    client: Arc<SdzFirestoreClient>,
+}
+
+impl SdzFirestoreSpotRevisionRepository {
+    pub fn new(client: Arc<SdzFirestoreClient>) -> Self {
+        Self { client }
+    }
+
+    fn spot_document_url(&self, spot_id: &str) -> String {
+        self.client.document_url(&format!("spots/{}", spot_id))
+    }
+}
+
+#[async_trait]
+impl SdzSpotRevisionRepository for SdzFirestoreSpotRevisionRepository {
+    async fn append(&self, revision: SdzSpotRevision) -> Result<(), SdzApiError> {
+        let url = format!(
+            "{}/revisions/{}",
+            self.spot_document_url(&revision.sdz_spot_id),
+            revision.sdz_revision_id
+        );
+        let snapshot = serde_json::to_string(&revision.snapshot).map_err(|e| {
+            tracing::error!("Failed to serialize spot snapshot: {:?}", e);
+            SdzApiError::Internal
+        })?;
+        let changed_fields: Vec<_> = revision
+            .changed_fields
+            .iter()
+            .map(|field| json!({ "stringValue": field }))
+            .collect();
+        let body = json!({
+            "fields": {
+                "editorUserId": { "stringValue": revision.sdz_editor_user_id },
+                "changedFields": { "arrayValue": { "values": changed_fields } },
+                "snapshot": { "stringValue": snapshot },
+                "createdAt": { "timestampValue": revision.created_at.to_rfc3339() }
+            }
+        });
+        Ok(self.client.patch(&url, &[], &body).await?)
+    }
+
+    async fn list_by_spot(
+        &self,
+        spot_id: &str,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<Vec<SdzSpotRevision>, SdzApiError> {
+        let url = format!("{}:runQuery", self.spot_document_url(spot_id));
+        let after_name = after.map(|cursor| {
+            self.client.document_name(&format!(
+                "spots/{}/revisions/{}",
+                spot_id, cursor.sdz_spot_id
+            ))
+        });
+        let body = build_revisions_query(
+            limit.min(SDZ_REVISION_QUERY_MAX_LIMIT),
+            after.zip(after_name.as_deref()),
+        );
+        let rows = self
+            .client
+            .query::<Vec<FirestoreRunQueryRow>>(&url, &body)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row.document)
+            .filter_map(|doc| doc.into_revision(spot_id))
+            .collect())
+    }
+
+    async fn delete_by_spot(&self, spot_id: &str) -> Result<usize, SdzApiError> {
+        // Firestoreは親ドキュメントの削除でサブコレクションを消さないため、版を明示的に削除する。
+        // 削除済みの版は次のクエリに現れないので、空になるまで先頭ページを繰り返し処理する
+        let url = format!("{}:runQuery", self.spot_document_url(spot_id));
+        let body = json!({
+            "structuredQuery": {
+                "from": [{ "collectionId": "revisions" }],
+                "select": { "fields": [{ "fieldPath": "__name__" }] },
+                "limit": SDZ_REVISION_QUERY_MAX_LIMIT
+            }
+        });
+        let mut deleted = 0;
+        loop {
+            let rows = self
+                .client
+                .query::<Vec<FirestoreNameRow>>(&url, &body)
+                .await?;
+            let writes: Vec<_> = rows
+                .into_iter()
+                .filter_map(|row| row.document)
+                .map(|doc| json!({ "delete": doc.name }))
+                .collect();
+            if writes.is_empty() {
+                return Ok(deleted);
+            }
+            let page_len = writes.len();
+            self.client.commit(writes).await?;
+            deleted += page_len;
+            if page_len < SDZ_REVISION_QUERY_MAX_LIMIT {
+                return Ok(deleted);
+            }
+        }
+    }
+
+    async fn find(
+        &self,
+        spot_id: &str,
+        revision_id: &str,
+    ) -> Result<Option<SdzSpotRevision>, SdzApiError> {
+        let url = format!(
+            "{}/revisions/{}",
+            self.spot_document_url(spot_id),
+            revision_id
+        );
+        match self.client.get::<FirestoreRevisionDoc>(&url, &[]).await {
+            Ok(doc) => Ok(doc.into_revision(spot_id)),
+            Err(SdzFirestoreError::NotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// createdAtの降順、同時刻はドキュメント名の降順（`sdz_cmp_newest_first`と同じ順序）
+fn build_revisions_query(limit: usize, after: Option<(&SdzPageCursor, &str)>) -> serde_json::Value {
+    let mut structured = json!({
+        "from": [{ "collectionId": "revisions" }],
+        "orderBy": [
+            { "field": { "fieldPath": "createdAt" }, "direction": "DESCENDING" },
+            { "field": { "fieldPath": "__name__" }, "direction": "DESCENDING" }
+        ],
+        "limit": limit
+    });
+    if let Some((cursor, document_name)) = after {
+        structured["startAt"] = json!({
+            "values": [
+                { "timestampValue": cursor.created_at.to_rfc3339() },
+                { "referenceValue": document_name }
+            ],
+            "before": false
+        });
+    }
+    json!({ "structuredQuery": structured })
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreNameRow {
+    document: Option<FirestoreDocName>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreDocName {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreRunQueryRow {
+    document: Option<FirestoreRevisionDoc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreRevisionDoc {
+    name: String,
+    fields: FirestoreRevisionFields,
+}
+
+impl FirestoreRevisionDoc {
+    /// snapshotが読めない版は壊れたデータとして読み飛ばす
+    fn into_revision(self, spot_id: &str) -> Option<SdzSpotRevision> {
+        let revision_id = sdz_firestore_doc_id(&self.name)?;
+        let snapshot = match serde_json::from_str::<SdzSpot>(&self.fields.snapshot.string_value) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::warn!(
+                    revision_id = %revision_id,
+                    "Failed to parse spot revision snapshot: {:?}",
+                    e
+                );
+                return None;
+            }
+        };
+        let created_at =
+            chrono::DateTime::parse_from_rfc3339(&self.fields.created_at.timestamp_value).ok()?;
+        Some(SdzSpotRevision {
+            sdz_revision_id: revision_id,
+            sdz_spot_id: spot_id.to_string(),
+            sdz_editor_user_id: self
+                .fields
+                .editor_user_id
+                .map(|field| field.string_value)
+                .unwrap_or_default(),
+            changed_fields: self
+                .fields
+                .changed_fields
+                .and_then(|field| field.array_value.values)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|field| field.string_value)
+                .collect(),
+            snapshot,
+            created_at,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FirestoreRevisionFields {
+    #[serde(rename = "editorUserId")]
+    editor_user_id: Option<StringField>,
+    #[serde(rename = "changedFields")]
+    changed_fields: Option<ArrayField>,
+    snapshot: StringField,
+    #[serde(rename = "createdAt")]
+    created_at: TimestampField,
+}
+
+#[derive(Debug, Deserialize)]
+struct StringField {
+    #[serde(rename = "stringValue")]
+    string_value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimestampField {
+    #[serde(rename = "timestampValue")]
+    timestamp_value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArrayField {
+    #[serde(rename = "arrayValue")]
+    array_value: ArrayValues,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArrayValues {
+    values: Option<Vec<StringField>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn revisions_query_starts_after_cursor() {
+        let created_at = chrono::DateTime::parse_from_rfc3339("2026-01-02T09:00:00+09:00").unwrap();
+        let cursor = SdzPageCursor::new(created_at, "rev-1");
+        let body = build_revisions_query(21, Some((&cursor, "spots/a/revisions/rev-1")));
+        let structured = &body["structuredQuery"];
+        assert_eq!(structured["limit"], 21);
+        assert_eq!(structured["orderBy"][1]["field"]["fieldPath"], "__name__");
+        assert_eq!(structured["orderBy"][1]["direction"], "DESCENDING");
+        assert_eq!(structured["startAt"]["before"], false);
+        assert_eq!(
+            structured["startAt"]["values"][1]["referenceValue"],
+            "spots/a/revisions/rev-1"
+        );
+        assert!(build_revisions_query(21, None)["structuredQuery"]
+            .get("startAt")
+            .is_none());
+    }
+}
@@ -1,105 +1,34 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::Deserialize;
 
 use crate::{
-    application::use_cases::user_repository::SdzUserRepository, domain::models::SdzUser,
-    presentation::error::SdzApiError,
+    application::use_cases::user_repository::SdzUserRepository,
+    domain::models::SdzUser,
+    infrastructure::firestore_client::{SdzFirestoreClient, SdzFirestoreError},
 };
 
-/// Firestoreの`users`コレクションからユーザーを取得するリポジトリ。
-/// 認証には環境変数`SDZ_FIRESTORE_TOKEN`かCloud Runのメタデータサーバートークンを使用する。
+/// Firestoreの`users`コレクションからユーザーを取得するリポジトリ
 pub struct SdzFirestoreUserRepository {
-    project_id: String,
-    bearer_token: Option<String>,
-    http: Client,
+    client: Arc<SdzFirestoreClient>,
 }
 
 impl SdzFirestoreUserRepository {
-    pub fn new(project_id: String, bearer_token: Option<String>) -> Result<Self, SdzApiError> {
-        let http = Client::builder().build().map_err(|e| {
-            tracing::error!("Failed to build reqwest client: {:?}", e);
-            SdzApiError::Internal
-        })?;
-        Ok(Self {
-            project_id,
-            bearer_token,
-            http,
-        })
-    }
-
-    async fn get_document(&self, user_id: &str) -> Result<Option<FirestoreUserDoc>, SdzApiError> {
-        let url = format!("https://firestore.googleapis.com/v1/projects/{}/databases/(default)/documents/users/{}", self.project_id, user_id);
-        let token = self.resolve_token().await?;
-        let resp = self
-            .http
-            .get(url)
-            .bearer_auth(token)
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("Firestore request error: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        match resp.status() {
-            reqwest::StatusCode::OK => {
-                let doc = resp.json::<FirestoreUserDoc>().await.map_err(|e| {
-                    tracing::error!("Failed to parse Firestore response: {:?}", e);
-                    SdzApiError::Internal
-                })?;
-                Ok(Some(doc))
-            }
-            reqwest::StatusCode::NOT_FOUND => Ok(None),
-            code => {
-                let body = resp.text().await.unwrap_or_default();
-                tracing::error!("Firestore unexpected status: {} body: {}", code, body);
-                Err(SdzApiError::Internal)
-            }
-        }
+    pub fn new(client: Arc<SdzFirestoreClient>) -> Self {
+        Self { client }
     }
 
-    async fn resolve_token(&self) -> Result<String, SdzApiError> {
-        if let Some(token) = self
-            .bearer_token
-            .as_ref()
-            .filter(|token| !token.trim().is_empty())
-        {
-            return Ok(token.to_string());
-        }
-        if let Ok(token) = std::env::var("SDZ_FIRESTORE_TOKEN") {
-            if !token.trim().is_empty() {
-                return Ok(token);
-            }
+    async fn get_document(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<FirestoreUserDoc>, SdzFirestoreError> {
+        let url = self.client.document_url(&format!("users/{}", user_id));
+        match self.client.get(&url, &[]).await {
+            Ok(doc) => Ok(Some(doc)),
+            Err(SdzFirestoreError::NotFound) => Ok(None),
+            Err(err) => Err(err),
         }
-        self.fetch_metadata_token().await
-    }
-
-    async fn fetch_metadata_token(&self) -> Result<String, SdzApiError> {
-        let metadata_url = "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
-        let resp = self
-            .http
-            .get(metadata_url)
-            .header("Metadata-Flavor", "Google")
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to fetch metadata token: {:?}", e);
-                SdzApiError::Internal
-            })?;
-
-        if !resp.status().is_success() {
-            let body = resp.text().await.unwrap_or_default();
-            tracing::error!("Metadata token error: {}", body);
-            return Err(SdzApiError::Internal);
-        }
-
-        let token = resp.json::<SdzMetadataToken>().await.map_err(|e| {
-            tracing::error!("Failed to parse metadata token: {:?}", e);
-            SdzApiError::Internal
-        })?;
-
-        Ok(token.access_token)
     }
 }
 
@@ -140,9 +69,3 @@ struct StringField {
     #[serde(rename = "stringValue")]
     string_value: String,
 }
-
-#[derive(Debug, Deserialize)]
-struct SdzMetadataToken {
-    #[serde(rename = "access_token")]
-    access_token: String,
-}
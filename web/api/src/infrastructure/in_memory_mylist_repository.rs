@@ -6,7 +6,10 @@ use chrono::{DateTime, FixedOffset};
 use tokio::sync::RwLock;
 
 use crate::{
-    application::use_cases::mylist_repository::SdzMyListRepository, domain::models::SdzMyListEntry,
+    application::use_cases::{
+        mylist_repository::SdzMyListRepository, pagination::sdz_cmp_newest_first,
+    },
+    domain::models::SdzMyListEntry,
     presentation::error::SdzApiError,
 };
 
@@ -19,7 +22,15 @@ pub struct SdzInMemoryMyListRepository {
 impl SdzMyListRepository for SdzInMemoryMyListRepository {
     async fn list_by_user(&self, user_id: &str) -> Result<Vec<SdzMyListEntry>, SdzApiError> {
         let store = self.store.read().await;
-        Ok(store.get(user_id).cloned().unwrap_or_default())
+        let mut list = store.get(user_id).cloned().unwrap_or_default();
+        // 同時刻に追加された場合もページングで順序がぶれないようにする
+        list.sort_by(|a, b| {
+            sdz_cmp_newest_first(
+                (&a.created_at, &a.sdz_spot_id),
+                (&b.created_at, &b.sdz_spot_id),
+            )
+        });
+        Ok(list)
     }
 
     async fn add(&self, user_id: &str, spot_id: &str) -> Result<(), SdzApiError> {
@@ -45,6 +56,17 @@ impl SdzMyListRepository for SdzInMemoryMyListRepository {
         }
         Ok(())
     }
+
+    async fn remove_spot_from_all(&self, spot_id: &str) -> Result<usize, SdzApiError> {
+        let mut store = self.store.write().await;
+        let mut removed = 0;
+        for list in store.values_mut() {
+            let before = list.len();
+            list.retain(|item| item.sdz_spot_id != spot_id);
+            removed += before - list.len();
+        }
+        Ok(removed)
+    }
 }
 
 fn now_jst() -> DateTime<FixedOffset> {
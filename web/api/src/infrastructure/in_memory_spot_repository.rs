@@ -5,7 +5,16 @@ use async_trait::async_trait;
 use tokio::sync::RwLock;
 
 use crate::{
-    application::use_cases::spot_repository::SdzSpotRepository, domain::models::SdzSpot,
+    application::use_cases::{
+        pagination::{
+            sdz_cmp_sort_keys, sdz_sort_spots_newest_first, SdzPageCursor, SdzSortCursor,
+        },
+        spot_repository::{
+            sdz_validate_batch_upsert, SdzSpotOrder, SdzSpotQuery, SdzSpotRepository,
+            SdzSpotVersionedWrite, SdzSpotsInBounds,
+        },
+    },
+    domain::models::{SdzSpot, SdzSpotBounds, SdzSpotFieldMask},
     presentation::error::SdzApiError,
 };
 
@@ -22,7 +31,12 @@ impl SdzSpotRepository for SdzInMemorySpotRepository {
         Ok(spot)
     }
 
-    async fn update(&self, spot: SdzSpot) -> Result<SdzSpot, SdzApiError> {
+    // 構造体の全フィールドを保持しているので、マスクに関係なく丸ごと置き換えてよい
+    async fn update(
+        &self,
+        spot: SdzSpot,
+        _mask: &SdzSpotFieldMask,
+    ) -> Result<SdzSpot, SdzApiError> {
         let mut store = self.store.write().await;
         if !store.contains_key(&spot.sdz_spot_id) {
             return Err(SdzApiError::NotFound);
@@ -31,25 +45,267 @@ impl SdzSpotRepository for SdzInMemorySpotRepository {
         Ok(spot)
     }
 
+    async fn upsert_many(&self, spots: Vec<SdzSpot>) -> Result<(), SdzApiError> {
+        // 検証を終えてから1つのロックの中でまとめて書き込む
+        sdz_validate_batch_upsert(&spots)?;
+        let mut store = self.store.write().await;
+        for spot in spots {
+            store.insert(spot.sdz_spot_id.clone(), spot);
+        }
+        Ok(())
+    }
+
+    // 版にはupdatedAtから作るETagを使う
+    async fn upsert_many_if_unchanged(
+        &self,
+        writes: Vec<SdzSpotVersionedWrite>,
+    ) -> Result<Vec<String>, SdzApiError> {
+        let spots: Vec<SdzSpot> = writes.iter().map(|write| write.spot.clone()).collect();
+        sdz_validate_batch_upsert(&spots)?;
+        let mut store = self.store.write().await;
+        let mut conflicted = Vec::new();
+        for SdzSpotVersionedWrite {
+            spot,
+            expected_version,
+        } in writes
+        {
+            let current_version = store.get(&spot.sdz_spot_id).map(SdzSpot::etag);
+            if current_version != expected_version {
+                conflicted.push(spot.sdz_spot_id);
+                continue;
+            }
+            store.insert(spot.sdz_spot_id.clone(), spot);
+        }
+        Ok(conflicted)
+    }
+
+    async fn update_if_unchanged(
+        &self,
+        spot: SdzSpot,
+        expected: &SdzSpot,
+        _mask: &SdzSpotFieldMask,
+    ) -> Result<SdzSpot, SdzApiError> {
+        let mut store = self.store.write().await;
+        let current = store.get(&spot.sdz_spot_id).ok_or(SdzApiError::NotFound)?;
+        if current.etag() != expected.etag() {
+            return Err(SdzApiError::PreconditionFailed(
+                "spot was modified by another request".into(),
+            ));
+        }
+        store.insert(spot.sdz_spot_id.clone(), spot.clone());
+        Ok(spot)
+    }
+
     async fn find_by_id(&self, spot_id: &str) -> Result<Option<SdzSpot>, SdzApiError> {
         let store = self.store.read().await;
         Ok(store.get(spot_id).cloned())
     }
 
-    async fn list_recent(&self, limit: usize) -> Result<Vec<SdzSpot>, SdzApiError> {
+    async fn find_versioned(
+        &self,
+        spot_id: &str,
+    ) -> Result<Option<(SdzSpot, String)>, SdzApiError> {
+        let store = self.store.read().await;
+        Ok(store.get(spot_id).map(|spot| (spot.clone(), spot.etag())))
+    }
+
+    async fn delete(&self, spot_id: &str) -> Result<(), SdzApiError> {
+        let mut store = self.store.write().await;
+        store
+            .remove(spot_id)
+            .map(|_| ())
+            .ok_or(SdzApiError::NotFound)
+    }
+
+    async fn list_recent(
+        &self,
+        query: &SdzSpotQuery,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError> {
         let store = self.store.read().await;
-        let mut list: Vec<_> = store.values().cloned().collect();
-        list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let mut list: Vec<_> = store
+            .values()
+            .filter(|spot| query.matches(spot) && is_after(spot, after))
+            .cloned()
+            .collect();
+        sdz_sort_spots_newest_first(&mut list);
         list.truncate(limit);
         Ok(list)
     }
 
+    async fn list_sorted(
+        &self,
+        query: &SdzSpotQuery,
+        order: SdzSpotOrder,
+        limit: usize,
+        after: Option<&SdzSortCursor>,
+    ) -> Result<Vec<SdzSpot>, SdzApiError> {
+        let store = self.store.read().await;
+        let mut list: Vec<_> = store
+            .values()
+            .filter(|spot| query.matches(spot))
+            .map(|spot| (order.sort_key(spot), spot))
+            .filter(|(key, spot)| {
+                after
+                    .map(|cursor| cursor.comes_after(key, &spot.sdz_spot_id))
+                    .unwrap_or(true)
+            })
+            .collect();
+        list.sort_by(|(a_key, a), (b_key, b)| {
+            sdz_cmp_sort_keys((a_key, &a.sdz_spot_id), (b_key, &b.sdz_spot_id))
+        });
+        Ok(list
+            .into_iter()
+            .take(limit)
+            .map(|(_, spot)| spot.clone())
+            .collect())
+    }
+
+    async fn list_in_bounds(
+        &self,
+        bounds: &SdzSpotBounds,
+        query: &SdzSpotQuery,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<SdzSpotsInBounds, SdzApiError> {
+        let store = self.store.read().await;
+        let mut list: Vec<_> = store
+            .values()
+            .filter(|spot| {
+                spot.location
+                    .as_ref()
+                    .map(|loc| bounds.contains(loc))
+                    .unwrap_or(false)
+                    && query.matches(spot)
+                    && is_after(spot, after)
+            })
+            .cloned()
+            .collect();
+        sdz_sort_spots_newest_first(&mut list);
+        list.truncate(limit);
+        Ok(SdzSpotsInBounds {
+            spots: list,
+            truncated: false,
+        })
+    }
+
+    async fn has_spot_with_image(&self, image_url: &str) -> Result<bool, SdzApiError> {
+        let store = self.store.read().await;
+        Ok(store
+            .values()
+            .any(|spot| spot.images.iter().any(|image| image == image_url)))
+    }
+
     async fn count_image_spots_by_user(&self, user_id: &str) -> Result<usize, SdzApiError> {
         let store = self.store.read().await;
         let count = store
             .values()
-            .filter(|spot| spot.sdz_user_id == user_id && !spot.images.is_empty())
+            .filter(|spot| {
+                spot.sdz_user_id == user_id && !spot.images.is_empty() && !spot.is_archived()
+            })
             .count();
         Ok(count)
     }
 }
+
+fn is_after(spot: &SdzSpot, after: Option<&SdzPageCursor>) -> bool {
+    after
+        .map(|cursor| cursor.comes_after(&spot.created_at, &spot.sdz_spot_id))
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::SdzCreateSpotParams;
+
+    fn spot(spot_id: &str) -> SdzSpot {
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: spot_id.into(),
+            name: format!("park {}", spot_id),
+            description: None,
+            location: None,
+            tags: vec!["park".into()],
+            images: vec![],
+            sdz_approval_status: None,
+            sdz_park_attributes: None,
+            sdz_street_attributes: None,
+            sdz_instagram_tag: None,
+            sdz_instagram_location_url: None,
+            sdz_instagram_profile_url: None,
+            sdz_google_place_id: None,
+            sdz_google_maps_url: None,
+            sdz_address: None,
+            sdz_phone_number: None,
+            sdz_google_rating: None,
+            sdz_google_rating_count: None,
+            sdz_google_types: vec![],
+            sdz_user_id: "admin-1".into(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn upsert_many_is_all_or_nothing() {
+        let repo = SdzInMemorySpotRepository::default();
+        repo.create(spot("a")).await.unwrap();
+
+        let mut renamed = spot("a");
+        renamed.name = "renamed".into();
+        let err = repo
+            .upsert_many(vec![renamed.clone(), spot("b"), spot("b")])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdzApiError::BadRequest(_)));
+        assert_eq!(repo.find_by_id("a").await.unwrap().unwrap().name, "park a");
+        assert!(repo.find_by_id("b").await.unwrap().is_none());
+
+        repo.upsert_many(vec![renamed, spot("b")]).await.unwrap();
+        assert_eq!(repo.find_by_id("a").await.unwrap().unwrap().name, "renamed");
+        assert!(repo.find_by_id("b").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn upsert_many_if_unchanged_skips_stale_rows() {
+        let repo = SdzInMemorySpotRepository::default();
+        repo.create(spot("a")).await.unwrap();
+        let (read, version) = repo.find_versioned("a").await.unwrap().unwrap();
+
+        let mut concurrent = read.clone();
+        concurrent.name = "concurrent".into();
+        concurrent.updated_at += chrono::Duration::seconds(1);
+        repo.upsert_many(vec![concurrent]).await.unwrap();
+
+        let mut stale = read;
+        stale.name = "stale".into();
+        let conflicted = repo
+            .upsert_many_if_unchanged(vec![
+                SdzSpotVersionedWrite {
+                    spot: stale,
+                    expected_version: Some(version),
+                },
+                SdzSpotVersionedWrite {
+                    spot: spot("b"),
+                    expected_version: None,
+                },
+            ])
+            .await
+            .unwrap();
+        assert_eq!(conflicted, vec!["a".to_string()]);
+        assert_eq!(
+            repo.find_by_id("a").await.unwrap().unwrap().name,
+            "concurrent"
+        );
+        assert!(repo.find_by_id("b").await.unwrap().is_some());
+
+        let conflicted = repo
+            .upsert_many_if_unchanged(vec![SdzSpotVersionedWrite {
+                spot: spot("b"),
+                expected_version: None,
+            }])
+            .await
+            .unwrap();
+        assert_eq!(conflicted, vec!["b".to_string()]);
+    }
+}
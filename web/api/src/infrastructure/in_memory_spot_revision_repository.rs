@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::{
+    application::use_cases::{
+        pagination::{sdz_cmp_newest_first, SdzPageCursor},
+        spot_revision_repository::SdzSpotRevisionRepository,
+    },
+    domain::revision::SdzSpotRevision,
+    presentation::error::SdzApiError,
+};
+
+#[derive(Clone, Default)]
+pub struct SdzInMemorySpotRevisionRepository {
+    store: Arc<RwLock<HashMap<String, Vec<SdzSpotRevision>>>>,
+}
+
+#[async_trait]
+impl SdzSpotRevisionRepository for SdzInMemorySpotRevisionRepository {
+    async fn append(&self, revision: SdzSpotRevision) -> Result<(), SdzApiError> {
+        let mut store = self.store.write().await;
+        store
+            .entry(revision.sdz_spot_id.clone())
+            .or_default()
+            .push(revision);
+        Ok(())
+    }
+
+    async fn list_by_spot(
+        &self,
+        spot_id: &str,
+        limit: usize,
+        after: Option<&SdzPageCursor>,
+    ) -> Result<Vec<SdzSpotRevision>, SdzApiError> {
+        let store = self.store.read().await;
+        let mut revisions: Vec<SdzSpotRevision> = store
+            .get(spot_id)
+            .map(|list| {
+                list.iter()
+                    .filter(|revision| {
+                        after
+                            .map(|cursor| {
+                                cursor.comes_after(&revision.created_at, &revision.sdz_revision_id)
+                            })
+                            .unwrap_or(true)
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        revisions.sort_by(|a, b| {
+            sdz_cmp_newest_first(
+                (&a.created_at, &a.sdz_revision_id),
+                (&b.created_at, &b.sdz_revision_id),
+            )
+        });
+        revisions.truncate(limit);
+        Ok(revisions)
+    }
+
+    async fn delete_by_spot(&self, spot_id: &str) -> Result<usize, SdzApiError> {
+        let mut store = self.store.write().await;
+        Ok(store.remove(spot_id).map(|list| list.len()).unwrap_or(0))
+    }
+
+    async fn find(
+        &self,
+        spot_id: &str,
+        revision_id: &str,
+    ) -> Result<Option<SdzSpotRevision>, SdzApiError> {
+        let store = self.store.read().await;
+        Ok(store.get(spot_id).and_then(|list| {
+            list.iter()
+                .find(|revision| revision.sdz_revision_id == revision_id)
+                .cloned()
+        }))
+    }
+}
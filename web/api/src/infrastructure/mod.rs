@@ -1,8 +1,14 @@
+pub mod firestore_client;
+pub mod firestore_endpoint;
 pub mod firestore_mylist_repository;
 pub mod firestore_spot_repository;
+pub mod firestore_spot_revision_repository;
 pub mod firestore_user_repository;
 pub mod in_memory_mylist_repository;
 pub mod in_memory_spot_repository;
+pub mod in_memory_spot_revision_repository;
 pub mod in_memory_user_repository;
+pub mod retry_policy;
+pub mod spot_csv;
 pub mod storage_disabled_repository;
 pub mod storage_signed_url_repository;
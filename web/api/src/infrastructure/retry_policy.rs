@@ -0,0 +1,294 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::presentation::error::SdzApiError;
+
+const SDZ_RETRY_MAX_ATTEMPTS: u32 = 3;
+const SDZ_RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+const SDZ_RETRY_MAX_DELAY: Duration = Duration::from_secs(2);
+/// 連続してこの回数だけ一時的な失敗が続いたらブレーカーを開く
+const SDZ_BREAKER_FAILURE_THRESHOLD: u32 = 5;
+/// ブレーカーを開いてから試行を1件だけ通すまでの時間
+const SDZ_BREAKER_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// リトライ対象かどうかを判定できるエラー
+pub trait SdzRetryableError {
+    /// 429/503やタイムアウトなど、時間をおけば成功しうる失敗か
+    fn is_transient(&self) -> bool;
+    /// ブレーカーが開いていて呼び出しを行わなかったときのエラー
+    fn circuit_open(service: &str) -> Self;
+}
+
+impl SdzRetryableError for SdzApiError {
+    fn is_transient(&self) -> bool {
+        matches!(self, SdzApiError::ServiceUnavailable(_))
+    }
+
+    fn circuit_open(service: &str) -> Self {
+        SdzApiError::ServiceUnavailable(format!("{} is temporarily unavailable", service))
+    }
+}
+
+/// Google APIの呼び出しに使うリトライ方針とサーキットブレーカー。
+/// 冪等な操作のみ指数バックオフ（ジッター付き）で再試行し、非冪等な操作は1回だけ呼ぶ。
+/// ブレーカーは冪等性に関係なく全呼び出しで共有する
+pub struct SdzRetryPolicy {
+    service: &'static str,
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    breaker: SdzCircuitBreaker,
+}
+
+impl SdzRetryPolicy {
+    pub fn new(service: &'static str) -> Self {
+        Self::with_settings(
+            service,
+            SDZ_RETRY_MAX_ATTEMPTS,
+            SDZ_RETRY_BASE_DELAY,
+            SDZ_RETRY_MAX_DELAY,
+            SdzCircuitBreaker::new(SDZ_BREAKER_FAILURE_THRESHOLD, SDZ_BREAKER_OPEN_DURATION),
+        )
+    }
+
+    fn with_settings(
+        service: &'static str,
+        max_attempts: u32,
+        base_delay: Duration,
+        max_delay: Duration,
+        breaker: SdzCircuitBreaker,
+    ) -> Self {
+        Self {
+            service,
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay,
+            breaker,
+        }
+    }
+
+    pub async fn execute<T, E, F, Fut>(&self, idempotent: bool, mut operation: F) -> Result<T, E>
+    where
+        E: SdzRetryableError + std::fmt::Debug,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let max_attempts = if idempotent { self.max_attempts } else { 1 };
+        let mut attempt = 1;
+        loop {
+            if !self.breaker.allow(Instant::now()) {
+                tracing::warn!(
+                    service = self.service,
+                    "circuit breaker is open, failing fast"
+                );
+                return Err(E::circuit_open(self.service));
+            }
+            match operation().await {
+                Ok(value) => {
+                    self.breaker.record_success();
+                    return Ok(value);
+                }
+                Err(err) if err.is_transient() => {
+                    self.breaker.record_failure(Instant::now());
+                    if attempt >= max_attempts {
+                        return Err(err);
+                    }
+                    let delay = self.backoff(attempt, sdz_random_fraction());
+                    tracing::warn!(
+                        service = self.service,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        error = ?err,
+                        "transient error, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                // 4xxなどの恒久的な失敗は相手側が応答しているので、ブレーカー上は成功扱い
+                Err(err) => {
+                    self.breaker.record_success();
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    /// equal jitter: 上限の半分は必ず待ち、残り半分をランダムにする
+    fn backoff(&self, attempt: u32, fraction: f64) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        let half = exp / 2;
+        half + half.mul_f64(fraction.clamp(0.0, 1.0))
+    }
+}
+
+struct SdzCircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<SdzBreakerState>,
+}
+
+#[derive(Default)]
+struct SdzBreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl SdzCircuitBreaker {
+    fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold,
+            open_duration,
+            state: Mutex::new(SdzBreakerState::default()),
+        }
+    }
+
+    /// 開いている間は拒否し、期限を過ぎたら次の期限までに1件だけ試行を通す（half-open）
+    fn allow(&self, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.open_until {
+            Some(until) if now < until => false,
+            Some(_) => {
+                state.open_until = Some(now + self.open_duration);
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.consecutive_failures = 0;
+        state.open_until = None;
+    }
+
+    fn record_failure(&self, now: Instant) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.open_until.is_some() || state.consecutive_failures >= self.failure_threshold {
+            if state.open_until.is_none() {
+                tracing::warn!(
+                    failures = state.consecutive_failures,
+                    "opening circuit breaker"
+                );
+            }
+            state.open_until = Some(now + self.open_duration);
+        }
+    }
+}
+
+/// 0.0〜1.0の乱数。ジッター用なので暗号論的な強さは不要
+fn sdz_random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn policy(threshold: u32) -> SdzRetryPolicy {
+        SdzRetryPolicy::with_settings(
+            "test",
+            3,
+            Duration::ZERO,
+            Duration::ZERO,
+            SdzCircuitBreaker::new(threshold, Duration::from_secs(30)),
+        )
+    }
+
+    fn unavailable() -> SdzApiError {
+        SdzApiError::ServiceUnavailable("down".into())
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_with_jitter_and_cap() {
+        let policy = SdzRetryPolicy::new("test");
+        assert_eq!(policy.backoff(1, 0.0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1, 1.0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2, 0.0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(10, 1.0), Duration::from_secs(2));
+        let fraction = sdz_random_fraction();
+        assert!((0.0..1.0).contains(&fraction));
+    }
+
+    #[tokio::test]
+    async fn retries_only_idempotent_transient_failures() {
+        let policy = policy(10);
+        let calls = AtomicU32::new(0);
+        let result = policy
+            .execute(true, || async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(unavailable())
+                } else {
+                    Ok("ok")
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<(), _> = policy
+            .execute(false, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(unavailable())
+            })
+            .await;
+        assert!(matches!(result, Err(SdzApiError::ServiceUnavailable(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<(), _> = policy
+            .execute(true, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(SdzApiError::NotFound)
+            })
+            .await;
+        assert!(matches!(result, Err(SdzApiError::NotFound)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn open_breaker_fails_fast_then_allows_one_trial() {
+        let policy = policy(3);
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = policy
+            .execute(true, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(unavailable())
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let result: Result<(), SdzApiError> = policy
+            .execute(true, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+        assert!(matches!(result, Err(SdzApiError::ServiceUnavailable(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let later = Instant::now() + Duration::from_secs(31);
+        assert!(policy.breaker.allow(later));
+        assert!(!policy.breaker.allow(later));
+        policy.breaker.record_success();
+        assert!(policy.breaker.allow(later));
+    }
+}
@@ -0,0 +1,512 @@
+use std::{collections::HashSet, fmt, io::Read};
+
+use serde::Deserialize;
+
+use crate::domain::models::{
+    SdzCreateSpotParams, SdzSpot, SdzSpotApprovalStatus, SdzSpotBusinessHours,
+    SdzSpotBusinessScheduleType, SdzSpotLocation, SdzSpotParkAttributes, SdzSpotTimeRange,
+    SdzStreetAttributes, SdzStreetSection, SdzStreetSurfaceCondition,
+};
+
+/// 1セル内で複数の値を並べるときの区切り（tags / images / googleTypes / streetSections）
+const SDZ_CSV_LIST_SEPARATOR: char = '|';
+/// streetSectionsの1要素内の区切り（`type:count:heightCm:widthCm:notes`）
+const SDZ_CSV_SECTION_SEPARATOR: char = ':';
+
+/// 受け付ける列。レイアウトは`docs/spot_csv_import.md`を参照
+const SDZ_CSV_COLUMNS: [&str; 33] = [
+    "spotId",
+    "name",
+    "description",
+    "lat",
+    "lng",
+    "tags",
+    "images",
+    "approvalStatus",
+    "officialUrl",
+    "accessInfo",
+    "parkPhoneNumber",
+    "hoursType",
+    "hours24",
+    "weekdayHours",
+    "weekendHours",
+    "hoursNote",
+    "surfaceMaterial",
+    "surfaceRoughness",
+    "surfaceCrack",
+    "streetSections",
+    "difficulty",
+    "streetNotes",
+    "instagramTag",
+    "instagramLocationUrl",
+    "instagramProfileUrl",
+    "googlePlaceId",
+    "googleMapsUrl",
+    "address",
+    "phoneNumber",
+    "googleRating",
+    "googleRatingCount",
+    "googleTypes",
+    "userId",
+];
+const SDZ_CSV_REQUIRED_COLUMNS: [&str; 2] = ["spotId", "name"];
+
+/// CSVのどの行で何が問題だったか。行番号はヘッダーを1行目とした物理行
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdzSpotCsvRowError {
+    pub line: u64,
+    pub spot_id: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for SdzSpotCsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.spot_id {
+            Some(spot_id) => write!(f, "line {} ({}): {}", self.line, spot_id, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/// 検証済みのスポットと、取り込めなかった行
+#[derive(Debug, Default)]
+pub struct SdzSpotCsvImport {
+    pub spots: Vec<SdzSpot>,
+    pub errors: Vec<SdzSpotCsvRowError>,
+}
+
+/// CSVを読み、1行ずつスポットに変換して`sdz_validate_spot`で検証する。
+/// 行単位の失敗は`errors`に積んで続行し、ヘッダー不備のようにファイル全体が読めない場合のみErrを返す。
+/// `userId`列が空の行は`default_user_id`を登録者にする
+pub fn sdz_parse_spot_csv<R: Read>(
+    reader: R,
+    default_user_id: &str,
+) -> Result<SdzSpotCsvImport, SdzSpotCsvRowError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers = reader.headers().map_err(|e| file_error(&e))?.clone();
+    for header in headers.iter() {
+        if !SDZ_CSV_COLUMNS.contains(&header) {
+            return Err(header_error(format!("unknown column: {}", header)));
+        }
+    }
+    for required in SDZ_CSV_REQUIRED_COLUMNS {
+        if !headers.iter().any(|header| header == required) {
+            return Err(header_error(format!("missing column: {}", required)));
+        }
+    }
+
+    let mut import = SdzSpotCsvImport::default();
+    let mut seen = HashSet::new();
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                import.errors.push(file_error(&e));
+                continue;
+            }
+        };
+        let line = record.position().map(|pos| pos.line()).unwrap_or_default();
+        let row = match record.deserialize::<SdzSpotCsvRow>(Some(&headers)) {
+            Ok(row) => row,
+            Err(e) => {
+                import.errors.push(SdzSpotCsvRowError {
+                    line,
+                    spot_id: None,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let spot_id = row.spot_id.clone();
+        let result = row.into_spot(default_user_id).and_then(|spot| {
+            if seen.insert(spot.sdz_spot_id.clone()) {
+                Ok(spot)
+            } else {
+                Err("duplicate spotId in file".to_string())
+            }
+        });
+        match result {
+            Ok(spot) => import.spots.push(spot),
+            Err(message) => import.errors.push(SdzSpotCsvRowError {
+                line,
+                spot_id,
+                message,
+            }),
+        }
+    }
+    Ok(import)
+}
+
+fn header_error(message: String) -> SdzSpotCsvRowError {
+    SdzSpotCsvRowError {
+        line: 1,
+        spot_id: None,
+        message,
+    }
+}
+
+fn file_error(err: &csv::Error) -> SdzSpotCsvRowError {
+    SdzSpotCsvRowError {
+        line: err.position().map(|pos| pos.line()).unwrap_or(1),
+        spot_id: None,
+        message: err.to_string(),
+    }
+}
+
+/// CSVの1行。空セルはNoneになる
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SdzSpotCsvRow {
+    spot_id: Option<String>,
+    name: Option<String>,
+    description: Option<String>,
+    lat: Option<String>,
+    lng: Option<String>,
+    tags: Option<String>,
+    images: Option<String>,
+    approval_status: Option<String>,
+    official_url: Option<String>,
+    access_info: Option<String>,
+    park_phone_number: Option<String>,
+    hours_type: Option<String>,
+    hours24: Option<String>,
+    weekday_hours: Option<String>,
+    weekend_hours: Option<String>,
+    hours_note: Option<String>,
+    surface_material: Option<String>,
+    surface_roughness: Option<String>,
+    surface_crack: Option<String>,
+    street_sections: Option<String>,
+    difficulty: Option<String>,
+    street_notes: Option<String>,
+    instagram_tag: Option<String>,
+    instagram_location_url: Option<String>,
+    instagram_profile_url: Option<String>,
+    google_place_id: Option<String>,
+    google_maps_url: Option<String>,
+    address: Option<String>,
+    phone_number: Option<String>,
+    google_rating: Option<String>,
+    google_rating_count: Option<String>,
+    google_types: Option<String>,
+    user_id: Option<String>,
+}
+
+impl SdzSpotCsvRow {
+    fn into_spot(self, default_user_id: &str) -> Result<SdzSpot, String> {
+        let spot_id = self
+            .spot_id
+            .clone()
+            .ok_or_else(|| "spotId is required".to_string())?;
+        let location = match (self.lat.as_deref(), self.lng.as_deref()) {
+            (Some(lat), Some(lng)) => Some(SdzSpotLocation {
+                lat: parse_number("lat", lat)?,
+                lng: parse_number("lng", lng)?,
+            }),
+            (None, None) => None,
+            _ => return Err("lat and lng must be set together".into()),
+        };
+        let approval_status = match self.approval_status.as_deref() {
+            // マスターデータは管理者がキュレーションしたものなので、省略時は承認済みとする
+            None | Some("approved") => SdzSpotApprovalStatus::Approved,
+            Some("pending") => SdzSpotApprovalStatus::Pending,
+            Some("rejected") => SdzSpotApprovalStatus::Rejected,
+            Some(other) => return Err(format!("unknown approvalStatus: {}", other)),
+        };
+        let park_attributes = self.park_attributes()?;
+        let street_attributes = self.street_attributes()?;
+        let google_rating = self
+            .google_rating
+            .as_deref()
+            .map(|raw| parse_number("googleRating", raw))
+            .transpose()?;
+        let google_rating_count = self
+            .google_rating_count
+            .as_deref()
+            .map(|raw| parse_number("googleRatingCount", raw))
+            .transpose()?;
+
+        SdzSpot::new_with_id(SdzCreateSpotParams {
+            sdz_spot_id: spot_id,
+            name: self.name.unwrap_or_default(),
+            description: self.description,
+            location,
+            tags: split_list(self.tags.as_deref()),
+            images: split_list(self.images.as_deref()),
+            sdz_approval_status: Some(approval_status),
+            sdz_park_attributes: park_attributes,
+            sdz_street_attributes: street_attributes,
+            sdz_instagram_tag: self.instagram_tag,
+            sdz_instagram_location_url: self.instagram_location_url,
+            sdz_instagram_profile_url: self.instagram_profile_url,
+            sdz_google_place_id: self.google_place_id,
+            sdz_google_maps_url: self.google_maps_url,
+            sdz_address: self.address,
+            sdz_phone_number: self.phone_number,
+            sdz_google_rating: google_rating,
+            sdz_google_rating_count: google_rating_count,
+            sdz_google_types: split_list(self.google_types.as_deref()),
+            sdz_user_id: self.user_id.unwrap_or_else(|| default_user_id.to_string()),
+        })
+        .map_err(|e| e.to_string())
+    }
+
+    fn park_attributes(&self) -> Result<Option<SdzSpotParkAttributes>, String> {
+        let business_hours = self.business_hours()?;
+        if self.official_url.is_none()
+            && self.access_info.is_none()
+            && self.park_phone_number.is_none()
+            && business_hours.is_none()
+        {
+            return Ok(None);
+        }
+        Ok(Some(SdzSpotParkAttributes {
+            official_url: self.official_url.clone(),
+            business_hours,
+            access_info: self.access_info.clone(),
+            phone_number: self.park_phone_number.clone(),
+        }))
+    }
+
+    /// weekendHoursが空でweekdayHoursがあれば「土日も平日と同じ」とみなす
+    fn business_hours(&self) -> Result<Option<SdzSpotBusinessHours>, String> {
+        if self.hours_type.is_none()
+            && self.hours24.is_none()
+            && self.weekday_hours.is_none()
+            && self.weekend_hours.is_none()
+            && self.hours_note.is_none()
+        {
+            return Ok(None);
+        }
+        let schedule_type = self
+            .hours_type
+            .as_deref()
+            .map(|raw| {
+                serde_json::from_value::<SdzSpotBusinessScheduleType>(serde_json::Value::String(
+                    raw.to_string(),
+                ))
+                .map_err(|_| format!("unknown hoursType: {}", raw))
+            })
+            .transpose()?;
+        let is_24_hours = self
+            .hours24
+            .as_deref()
+            .map(parse_bool)
+            .transpose()?
+            .unwrap_or(false);
+        let weekday = self
+            .weekday_hours
+            .as_deref()
+            .map(|raw| parse_time_range("weekdayHours", raw))
+            .transpose()?;
+        let weekend = self
+            .weekend_hours
+            .as_deref()
+            .map(|raw| parse_time_range("weekendHours", raw))
+            .transpose()?;
+        Ok(Some(SdzSpotBusinessHours {
+            schedule_type,
+            is_24_hours,
+            same_as_weekday: weekday.is_some() && weekend.is_none(),
+            weekday,
+            weekend,
+            note: self.hours_note.clone(),
+        }))
+    }
+
+    fn street_attributes(&self) -> Result<Option<SdzStreetAttributes>, String> {
+        let sections = self
+            .street_sections
+            .as_deref()
+            .map(|raw| {
+                split_list(Some(raw))
+                    .iter()
+                    .map(|section| parse_street_section(section))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        let surface_condition = if self.surface_roughness.is_some() || self.surface_crack.is_some()
+        {
+            Some(SdzStreetSurfaceCondition {
+                roughness: self.surface_roughness.clone(),
+                crack: self.surface_crack.clone(),
+            })
+        } else {
+            None
+        };
+        if self.surface_material.is_none()
+            && surface_condition.is_none()
+            && sections.is_none()
+            && self.difficulty.is_none()
+            && self.street_notes.is_none()
+        {
+            return Ok(None);
+        }
+        Ok(Some(SdzStreetAttributes {
+            surface_material: self.surface_material.clone(),
+            surface_condition,
+            sections,
+            difficulty: self.difficulty.clone(),
+            notes: self.street_notes.clone(),
+        }))
+    }
+}
+
+fn split_list(raw: Option<&str>) -> Vec<String> {
+    raw.map(|raw| {
+        raw.split(SDZ_CSV_LIST_SEPARATOR)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn parse_number<T: std::str::FromStr>(column: &str, raw: &str) -> Result<T, String> {
+    raw.parse::<T>()
+        .map_err(|_| format!("{} must be a number: {}", column, raw))
+}
+
+fn parse_bool(raw: &str) -> Result<bool, String> {
+    match raw.to_ascii_lowercase().as_str() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(format!("hours24 must be true or false: {}", raw)),
+    }
+}
+
+/// `HH:MM-HH:MM`。終了は`24:00`まで指定できる
+fn parse_time_range(column: &str, raw: &str) -> Result<SdzSpotTimeRange, String> {
+    let invalid = || format!("{} must be HH:MM-HH:MM: {}", column, raw);
+    let (start, end) = raw.split_once('-').ok_or_else(invalid)?;
+    Ok(SdzSpotTimeRange {
+        start_minutes: parse_minutes(start.trim()).ok_or_else(invalid)?,
+        end_minutes: parse_minutes(end.trim()).ok_or_else(invalid)?,
+    })
+}
+
+fn parse_minutes(raw: &str) -> Option<u16> {
+    let (hours, minutes) = raw.split_once(':')?;
+    let hours = hours.parse::<u16>().ok()?;
+    let minutes = minutes.parse::<u16>().ok()?;
+    if minutes >= 60 || hours > 24 || (hours == 24 && minutes > 0) {
+        return None;
+    }
+    Some(hours * 60 + minutes)
+}
+
+/// `type:count:heightCm:widthCm:notes`。typeより後ろは省略・空欄にできる
+fn parse_street_section(raw: &str) -> Result<SdzStreetSection, String> {
+    let mut parts = raw.splitn(5, SDZ_CSV_SECTION_SEPARATOR).map(str::trim);
+    let section_type = parts.next().unwrap_or_default().to_string();
+    let mut next_number = |label: &str| -> Result<Option<u16>, String> {
+        match parts.next().filter(|value| !value.is_empty()) {
+            Some(value) => value
+                .parse::<u16>()
+                .map(Some)
+                .map_err(|_| format!("streetSections {} must be a number: {}", label, raw)),
+            None => Ok(None),
+        }
+    };
+    let count = next_number("count")?;
+    let height_cm = next_number("heightCm")?;
+    let width_cm = next_number("widthCm")?;
+    let notes = parts
+        .next()
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    Ok(SdzStreetSection {
+        section_type,
+        count,
+        height_cm,
+        width_cm,
+        notes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_business_hours_and_street_sections() {
+        let csv = "\
+spotId,name,lat,lng,tags,hoursType,weekdayHours,weekendHours,hoursNote,streetSections,surfaceRoughness
+park-1,Park,35.1,139.2,パーク|ボウル,regular,09:00-21:00,,,,
+street-1,Street,,,ストリート,,,,,ledge:2:40::低め|rail,smooth
+school-1,School,,,,schoolOnly,,,平日夕方のみ,,
+";
+        let import = sdz_parse_spot_csv(csv.as_bytes(), "importer").unwrap();
+        assert!(import.errors.is_empty(), "{:?}", import.errors);
+        assert_eq!(import.spots.len(), 3);
+
+        let park = &import.spots[0];
+        assert_eq!(park.tags, vec!["パーク", "ボウル"]);
+        assert_eq!(park.sdz_user_id, "importer");
+        assert!(park.is_approved());
+        let hours = park
+            .sdz_park_attributes
+            .as_ref()
+            .and_then(|attrs| attrs.business_hours.as_ref())
+            .unwrap();
+        assert!(hours.same_as_weekday);
+        assert_eq!(hours.weekday.as_ref().unwrap().start_minutes, 9 * 60);
+        assert!(park.sdz_street_attributes.is_none());
+
+        let street = import.spots[1].sdz_street_attributes.as_ref().unwrap();
+        let sections = street.sections.as_ref().unwrap();
+        assert_eq!(sections[0].section_type, "ledge");
+        assert_eq!(sections[0].count, Some(2));
+        assert_eq!(sections[0].height_cm, Some(40));
+        assert_eq!(sections[0].width_cm, None);
+        assert_eq!(sections[0].notes.as_deref(), Some("低め"));
+        assert_eq!(sections[1].section_type, "rail");
+        assert_eq!(
+            street
+                .surface_condition
+                .as_ref()
+                .unwrap()
+                .roughness
+                .as_deref(),
+            Some("smooth")
+        );
+        assert!(import.spots[1].location.is_none());
+    }
+
+    #[test]
+    fn reports_row_errors_and_keeps_valid_rows() {
+        let csv = "\
+spotId,name,lat,lng,weekdayHours,weekendHours
+ok-1,OK,35,139,,
+bad-lat,Bad,95,139,,
+bad-hours,Hours,,,21:00-09:00,
+,No Id,,,,
+ok-1,Dup,,,,
+bad-time,Time,,,9時から,
+";
+        let import = sdz_parse_spot_csv(csv.as_bytes(), "importer").unwrap();
+        assert_eq!(import.spots.len(), 1);
+        let errors: Vec<String> = import.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "line 3 (bad-lat): lat must be between -90 and 90",
+                "line 4 (bad-hours): business hours are invalid",
+                "line 5: spotId is required",
+                "line 6 (ok-1): duplicate spotId in file",
+                "line 7 (bad-time): weekdayHours must be HH:MM-HH:MM: 9時から",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_or_missing_columns() {
+        let err = sdz_parse_spot_csv("spotId,name,latitude\n".as_bytes(), "importer").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: unknown column: latitude");
+        let err = sdz_parse_spot_csv("spotId,lat\n".as_bytes(), "importer").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: missing column: name");
+    }
+}
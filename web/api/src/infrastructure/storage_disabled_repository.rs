@@ -18,4 +18,13 @@ impl SdzStorageRepository for SdzDisabledStorageRepository {
     ) -> Result<SdzUploadUrlResult, SdzApiError> {
         Err(SdzApiError::Internal)
     }
+
+    fn object_name_from_url(&self, _object_url: &str) -> Option<String> {
+        // アップロードURLを発行していないため、管理対象のオブジェクトも存在しない
+        None
+    }
+
+    async fn delete_object(&self, _object_name: &str) -> Result<(), SdzApiError> {
+        Ok(())
+    }
 }
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
 use chrono::{DateTime, Duration, FixedOffset, Utc};
 use hex::ToHex;
-use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -11,6 +11,7 @@ use crate::{
     application::use_cases::storage_repository::{
         SdzStorageRepository, SdzUploadUrlRequest, SdzUploadUrlResult,
     },
+    infrastructure::retry_policy::SdzRetryPolicy,
     presentation::error::SdzApiError,
 };
 
@@ -22,6 +23,7 @@ pub struct SdzStorageSignedUrlRepository {
     sdz_service_account_email: String,
     sdz_expires_in: u32,
     http: Client,
+    sign_blob_retry: SdzRetryPolicy,
 }
 
 impl SdzStorageSignedUrlRepository {
@@ -39,6 +41,7 @@ impl SdzStorageSignedUrlRepository {
             sdz_service_account_email,
             sdz_expires_in,
             http,
+            sign_blob_retry: SdzRetryPolicy::new("IAM signBlob"),
         })
     }
 
@@ -76,7 +79,18 @@ impl SdzStorageSignedUrlRepository {
         Ok(token.access_token)
     }
 
+    /// 署名は同じ入力に対して副作用がないため、一時的な失敗は再試行する
     async fn sign_blob(&self, access_token: &str, payload: &str) -> Result<String, SdzApiError> {
+        self.sign_blob_retry
+            .execute(true, || self.request_sign_blob(access_token, payload))
+            .await
+    }
+
+    async fn request_sign_blob(
+        &self,
+        access_token: &str,
+        payload: &str,
+    ) -> Result<String, SdzApiError> {
         let url = format!(
             "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/{}:signBlob",
             self.sdz_service_account_email
@@ -95,12 +109,18 @@ impl SdzStorageSignedUrlRepository {
             .await
             .map_err(|e| {
                 tracing::error!("signBlob request error: {:?}", e);
-                SdzApiError::Internal
+                SdzApiError::ServiceUnavailable("IAM signBlob request failed".into())
             })?;
 
-        if !resp.status().is_success() {
+        let status = resp.status();
+        if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
-            tracing::error!("signBlob error: {}", body);
+            tracing::error!("signBlob error: {} body: {}", status, body);
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                return Err(SdzApiError::ServiceUnavailable(
+                    "IAM signBlob is temporarily unavailable".into(),
+                ));
+            }
             return Err(SdzApiError::Internal);
         }
 
@@ -165,6 +185,20 @@ impl SdzStorageSignedUrlRepository {
             SDZ_STORAGE_HOST, self.sdz_bucket, encoded_path
         )
     }
+
+    /// build_object_urlの逆変換。別バケットや外部URLはNoneを返す
+    fn parse_object_name(&self, object_url: &str) -> Option<String> {
+        let prefix = format!("https://{}/{}/", SDZ_STORAGE_HOST, self.sdz_bucket);
+        let encoded = object_url.strip_prefix(&prefix)?;
+        let encoded = encoded.split(['?', '#']).next().unwrap_or_default();
+        if encoded.is_empty() {
+            return None;
+        }
+        percent_decode_str(encoded)
+            .decode_utf8()
+            .ok()
+            .map(|name| name.into_owned())
+    }
 }
 
 #[async_trait]
@@ -231,6 +265,41 @@ impl SdzStorageRepository for SdzStorageSignedUrlRepository {
             sdz_expires_at: expires_at,
         })
     }
+
+    fn object_name_from_url(&self, object_url: &str) -> Option<String> {
+        self.parse_object_name(object_url)
+    }
+
+    async fn delete_object(&self, object_name: &str) -> Result<(), SdzApiError> {
+        // JSON APIではオブジェクト名の'/'もエンコードして1セグメントとして渡す
+        let url = format!(
+            "https://{}/storage/v1/b/{}/o/{}",
+            SDZ_STORAGE_HOST,
+            self.sdz_bucket,
+            sdz_encode_query(object_name)
+        );
+        let access_token = self.fetch_access_token().await?;
+        let resp = self
+            .http
+            .delete(url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("Storage delete request error: {:?}", e);
+                SdzApiError::Internal
+            })?;
+
+        match resp.status() {
+            status if status.is_success() => Ok(()),
+            reqwest::StatusCode::NOT_FOUND => Ok(()),
+            code => {
+                let body = resp.text().await.unwrap_or_default();
+                tracing::error!("Storage delete unexpected status: {} body: {}", code, body);
+                Err(SdzApiError::Internal)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -329,3 +398,23 @@ fn sdz_to_jst(time: DateTime<Utc>) -> DateTime<FixedOffset> {
     let offset = FixedOffset::east_opt(9 * 3600).expect("valid offset");
     time.with_timezone(&offset)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_object_name_roundtrips_object_url() {
+        let repo =
+            SdzStorageSignedUrlRepository::new("sdz-bucket".into(), "sa@example.com".into(), 900)
+                .unwrap();
+        let object_name = "spots/user-1/写真 1.jpg";
+        let url = repo.build_object_url(object_name);
+        assert_eq!(repo.parse_object_name(&url).as_deref(), Some(object_name));
+        assert_eq!(
+            repo.parse_object_name("https://storage.googleapis.com/other/spots/a.jpg"),
+            None
+        );
+        assert_eq!(repo.parse_object_name("https://example.com/a.jpg"), None);
+    }
+}
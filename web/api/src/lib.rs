@@ -0,0 +1,7 @@
+// ユースケースは状態を持たない構造体を`new()`で作る方針なので、Defaultは実装しない
+#![allow(clippy::new_without_default)]
+
+pub mod application;
+pub mod domain;
+pub mod infrastructure;
+pub mod presentation;
@@ -1,7 +1,4 @@
-mod application;
-mod domain;
-mod infrastructure;
-mod presentation;
+use sdz_api::presentation;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -24,8 +24,14 @@ pub enum SdzApiError {
     Unauthorized,
     #[error("Not Found")]
     NotFound,
+    #[error("Quota Exceeded: {0}")]
+    QuotaExceeded(String),
+    #[error("Precondition Failed: {0}")]
+    PreconditionFailed(String),
     #[error("Internal Server Error")]
     Internal,
+    #[error("Service Unavailable: {0}")]
+    ServiceUnavailable(String),
 }
 
 impl SdzApiError {
@@ -35,7 +41,11 @@ impl SdzApiError {
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::Unauthorized => StatusCode::UNAUTHORIZED,
             Self::NotFound => StatusCode::NOT_FOUND,
+            // 時間をおいても解消しないため、再試行を促す429ではなく409で返す
+            Self::QuotaExceeded(_) => StatusCode::CONFLICT,
+            Self::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
             Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
@@ -45,7 +55,10 @@ impl SdzApiError {
             Self::Forbidden(_) => "SDZ-E-1002",
             Self::Unauthorized => "SDZ-E-1001",
             Self::NotFound => "SDZ-E-4004",
+            Self::QuotaExceeded(_) => "SDZ-E-4009",
+            Self::PreconditionFailed(_) => "SDZ-E-4012",
             Self::Internal => "SDZ-E-9001",
+            Self::ServiceUnavailable(_) => "SDZ-E-5003",
         }
     }
 
@@ -55,7 +68,10 @@ impl SdzApiError {
             Self::Forbidden(_) => "SDZ-API-4030",
             Self::Unauthorized => "SDZ-API-4010",
             Self::NotFound => "SDZ-API-4040",
+            Self::QuotaExceeded(_) => "SDZ-API-4090",
+            Self::PreconditionFailed(_) => "SDZ-API-4120",
             Self::Internal => "SDZ-API-5000",
+            Self::ServiceUnavailable(_) => "SDZ-API-5030",
         }
     }
 }
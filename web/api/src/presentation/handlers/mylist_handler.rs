@@ -1,6 +1,6 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
@@ -9,7 +9,9 @@ use serde::Serialize;
 use crate::{
     application::use_cases::{
         add_mylist_use_case::{SdzAddMyListInput, SdzAddMyListUseCase},
+        export_mylist_use_case::{SdzExportMyListUseCase, SdzMyListExportFormat},
         list_mylist_use_case::SdzListMyListUseCase,
+        pagination::SdzPageRequest,
         remove_mylist_use_case::SdzRemoveMyListUseCase,
     },
     presentation::{error::SdzApiError, middleware::auth::SdzAuthUser, router::SdzAppState},
@@ -22,21 +24,77 @@ struct SdzMyListActionResponse {
     status: String,
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct SdzListMyListQuery {
+    pub limit: Option<usize>,
+    #[serde(rename = "pageToken")]
+    pub page_token: Option<String>,
+}
+
 pub async fn handle_list_mylist(
     State(state): State<SdzAppState>,
     auth_user: SdzAuthUser,
+    Query(params): Query<SdzListMyListQuery>,
 ) -> impl IntoResponse {
+    let page = SdzPageRequest::from_query(params.limit, params.page_token.as_deref())?;
     let use_case = SdzListMyListUseCase::new();
     let spots = use_case
         .execute(
             state.mylist_repo.clone(),
             state.spot_repo.clone(),
             auth_user,
+            page,
         )
         .await?;
     Ok::<_, SdzApiError>((StatusCode::OK, Json(spots)))
 }
 
+/// スポット詳細ページのリンク先。未設定時はローカル開発用のUI
+const SDZ_DEFAULT_WEB_BASE_URL: &str = "http://localhost:3000";
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SdzExportMyListQuery {
+    pub format: Option<String>,
+}
+
+/// マイリストをKML/GPXファイルとしてダウンロードさせる
+pub async fn handle_export_mylist(
+    State(state): State<SdzAppState>,
+    auth_user: SdzAuthUser,
+    Query(params): Query<SdzExportMyListQuery>,
+) -> impl IntoResponse {
+    let format = params
+        .format
+        .as_deref()
+        .and_then(SdzMyListExportFormat::parse)
+        .ok_or_else(|| SdzApiError::BadRequest("format must be kml or gpx".to_string()))?;
+    let web_base_url = std::env::var("SDZ_WEB_BASE_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| SDZ_DEFAULT_WEB_BASE_URL.to_string());
+    let use_case = SdzExportMyListUseCase::new();
+    let body = use_case
+        .execute(
+            state.mylist_repo.clone(),
+            state.spot_repo.clone(),
+            auth_user,
+            format,
+            web_base_url.trim(),
+        )
+        .await?;
+    Ok::<_, SdzApiError>((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", format.file_name()),
+            ),
+        ],
+        body,
+    ))
+}
+
 pub async fn handle_add_mylist(
     State(state): State<SdzAppState>,
     auth_user: SdzAuthUser,
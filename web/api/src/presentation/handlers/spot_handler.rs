@@ -1,19 +1,42 @@
+use std::convert::Infallible;
+
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     Json,
 };
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 
 use crate::{
     application::use_cases::{
+        archive_spot_use_case::SdzArchiveSpotUseCase,
+        bulk_upsert_spots_use_case::SdzBulkUpsertSpotsUseCase,
+        cluster_spots_use_case::{SdzClusterSpotsUseCase, SDZ_CLUSTER_MAX_ZOOM},
         create_spot_use_case::{CreateSpotInput, SdzCreateSpotUseCase},
+        delete_spot_use_case::SdzDeleteSpotUseCase,
+        export_spots_geojson_use_case::SdzExportSpotsGeoJsonUseCase,
+        find_duplicate_spots_use_case::{
+            SdzFindDuplicateSpotsUseCase, SDZ_DEDUP_DEFAULT_THRESHOLD,
+        },
         generate_upload_url_use_case::{SdzGenerateUploadUrlInput, SdzGenerateUploadUrlUseCase},
         get_spot_use_case::SdzGetSpotUseCase,
-        list_spots_use_case::{SdzListSpotsUseCase, SdzSpotSearchFilter, SdzSpotTypeFilter},
+        list_archived_spots_use_case::SdzListArchivedSpotsUseCase,
+        list_spot_revisions_use_case::SdzListSpotRevisionsUseCase,
+        list_spots_use_case::{
+            SdzListSpotsUseCase, SdzSpotNearby, SdzSpotSearchFilter, SdzSpotSort,
+            SdzSpotTypeFilter, SdzStreetFilter,
+        },
+        pagination::SdzPageRequest,
+        restore_spot_use_case::SdzRestoreSpotUseCase,
+        revert_spot_use_case::SdzRevertSpotUseCase,
+        spot_facets_use_case::SdzSpotFacetsUseCase,
+        spot_view::SdzSpotView,
         update_spot_use_case::{SdzUpdateSpotUseCase, UpdateSpotInput},
     },
-    domain::models::SdzSpotApprovalStatus,
+    domain::models::{sdz_now_jst, SdzSpotApprovalStatus, SdzSpotBounds},
     presentation::{
         error::SdzApiError,
         middleware::{admin::SdzAdminUser, auth::SdzAuthUser, client::SdzClientApp},
@@ -92,7 +115,11 @@ pub async fn handle_get_spot(
     let spot = use_case
         .execute(state.spot_repo.clone(), spot_id, None)
         .await?;
-    Ok::<_, SdzApiError>((StatusCode::OK, Json(spot)))
+    Ok::<_, SdzApiError>((
+        StatusCode::OK,
+        [(header::ETAG, spot.etag())],
+        Json(SdzSpotView::new(spot, sdz_now_jst())),
+    ))
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -102,12 +129,101 @@ pub struct SdzListSpotsQuery {
     #[serde(rename = "type")]
     pub spot_type: Option<String>,
     pub tags: Option<String>,
+    /// `minLat,minLng,maxLat,maxLng`
+    pub bbox: Option<String>,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    #[serde(rename = "radiusM")]
+    pub radius_m: Option<f64>,
+    pub limit: Option<usize>,
+    #[serde(rename = "pageToken")]
+    pub page_token: Option<String>,
+    #[serde(rename = "openNow")]
+    pub open_now: Option<bool>,
+    /// ストリートのセクション種別（例: `handrail`, `ledge`）
+    pub section: Option<String>,
+    #[serde(rename = "minHeightCm")]
+    pub min_height_cm: Option<u16>,
+    #[serde(rename = "maxHeightCm")]
+    pub max_height_cm: Option<u16>,
+    pub difficulty: Option<String>,
+    #[serde(rename = "surfaceMaterial")]
+    pub surface_material: Option<String>,
+    /// `newest|relevance|name|rating|updated|distance`
+    pub sort: Option<String>,
 }
 
 pub async fn handle_list_spots(
     State(state): State<SdzAppState>,
     Query(params): Query<SdzListSpotsQuery>,
 ) -> impl IntoResponse {
+    let (limit, page_token) = (params.limit, params.page_token.clone());
+    let filter = build_search_filter(params)?;
+    // 新着順とそれ以外でページトークンの形式が異なる
+    let page = if filter.is_ranked() {
+        SdzPageRequest::from_sorted_query(limit, page_token.as_deref())?
+    } else {
+        SdzPageRequest::from_query(limit, page_token.as_deref())?
+    };
+    let use_case = SdzListSpotsUseCase::new();
+    let spots = use_case
+        .execute(state.spot_repo.clone(), page, None, filter)
+        .await?;
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(spots)))
+}
+
+/// 一覧と同じ絞り込み条件で、値ごとの件数を返す（limit・pageToken・sortは無視する）
+pub async fn handle_spot_facets(
+    State(state): State<SdzAppState>,
+    Query(params): Query<SdzListSpotsQuery>,
+) -> impl IntoResponse {
+    let filter = build_search_filter(params)?;
+    let use_case = SdzSpotFacetsUseCase::new();
+    let facets = use_case
+        .execute(state.spot_repo.clone(), None, filter)
+        .await?;
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(facets)))
+}
+
+fn build_search_filter(params: SdzListSpotsQuery) -> Result<SdzSpotSearchFilter, SdzApiError> {
+    let bounds = params
+        .bbox
+        .as_deref()
+        .map(|raw| {
+            SdzSpotBounds::parse(raw).ok_or_else(|| {
+                SdzApiError::BadRequest("bbox must be minLat,minLng,maxLat,maxLng".to_string())
+            })
+        })
+        .transpose()?;
+    let nearby = match (params.lat, params.lng) {
+        (Some(lat), Some(lng)) => Some(SdzSpotNearby::new(lat, lng, params.radius_m).ok_or_else(
+            || SdzApiError::BadRequest("lat/lng out of range or radiusM not positive".to_string()),
+        )?),
+        (None, None) if params.radius_m.is_none() => None,
+        _ => {
+            return Err(SdzApiError::BadRequest(
+                "lat and lng are required for nearby search".to_string(),
+            ))
+        }
+    };
+    if bounds.is_some() && nearby.is_some() {
+        return Err(SdzApiError::BadRequest(
+            "bbox cannot be combined with lat/lng".to_string(),
+        ));
+    }
+    let sort = params
+        .sort
+        .as_deref()
+        .map(|raw| {
+            SdzSpotSort::parse(raw)
+                .ok_or_else(|| SdzApiError::BadRequest(format!("unknown sort: {}", raw)))
+        })
+        .transpose()?;
+    if sort == Some(SdzSpotSort::Distance) && nearby.is_none() {
+        return Err(SdzApiError::BadRequest(
+            "sort=distance requires lat and lng".to_string(),
+        ));
+    }
     let filter = SdzSpotSearchFilter {
         query: params.query,
         spot_type: params
@@ -118,12 +234,68 @@ pub async fn handle_list_spots(
             .tags
             .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
             .unwrap_or_default(),
+        bounds,
+        nearby,
+        sort,
+        open_now: params.open_now.unwrap_or(false),
+        street: SdzStreetFilter {
+            section_type: non_empty(params.section),
+            min_height_cm: params.min_height_cm,
+            max_height_cm: params.max_height_cm,
+            difficulty: non_empty(params.difficulty),
+            surface_material: non_empty(params.surface_material),
+        },
     };
-    let use_case = SdzListSpotsUseCase::new();
-    let spots = use_case
-        .execute(state.spot_repo.clone(), 50, None, filter)
+    if let (Some(min), Some(max)) = (filter.street.min_height_cm, filter.street.max_height_cm) {
+        if min > max {
+            return Err(SdzApiError::BadRequest(
+                "minHeightCm must be <= maxHeightCm".to_string(),
+            ));
+        }
+    }
+    Ok(filter)
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SdzClusterSpotsQuery {
+    /// `minLat,minLng,maxLat,maxLng`
+    pub bbox: Option<String>,
+    pub zoom: Option<u8>,
+}
+
+pub async fn handle_cluster_spots(
+    State(state): State<SdzAppState>,
+    Query(params): Query<SdzClusterSpotsQuery>,
+) -> impl IntoResponse {
+    let bounds = params
+        .bbox
+        .as_deref()
+        .and_then(SdzSpotBounds::parse)
+        .ok_or_else(|| {
+            SdzApiError::BadRequest("bbox must be minLat,minLng,maxLat,maxLng".to_string())
+        })?;
+    let zoom = params
+        .zoom
+        .filter(|zoom| *zoom <= SDZ_CLUSTER_MAX_ZOOM)
+        .ok_or_else(|| {
+            SdzApiError::BadRequest(format!("zoom must be 0..={}", SDZ_CLUSTER_MAX_ZOOM))
+        })?;
+    let use_case = SdzClusterSpotsUseCase::new();
+    let clusters = use_case
+        .execute(state.spot_repo.clone(), bounds, zoom)
         .await?;
-    Ok::<_, SdzApiError>((StatusCode::OK, Json(spots)))
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(clusters)))
+}
+
+/// 承認済みスポットのGeoJSON。位置情報のないスポットは`unlocated`に列挙する
+pub async fn handle_export_spots_geojson(State(state): State<SdzAppState>) -> impl IntoResponse {
+    let use_case = SdzExportSpotsGeoJsonUseCase::new();
+    let collection = use_case.execute(state.spot_repo.clone()).await?;
+    Ok::<_, SdzApiError>((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/geo+json")],
+        Json(collection),
+    ))
 }
 
 pub async fn handle_update_spot(
@@ -131,6 +303,7 @@ pub async fn handle_update_spot(
     Path(spot_id): Path<String>,
     auth_user: SdzAuthUser,
     client_app: SdzClientApp,
+    headers: HeaderMap,
     Json(payload): Json<UpdateSpotInput>,
 ) -> impl IntoResponse {
     if !client_app.is_mobile() {
@@ -145,7 +318,14 @@ pub async fn handle_update_spot(
     );
     let use_case = SdzUpdateSpotUseCase::new();
     let updated = use_case
-        .execute(state.spot_repo.clone(), spot_id, payload)
+        .execute(
+            state.spot_repo.clone(),
+            state.revision_repo.clone(),
+            &auth_user.sdz_user_id,
+            spot_id,
+            payload,
+            if_match_header(&headers)?,
+        )
         .await?;
     tracing::info!(
         event_code = "SDZ-API-2004",
@@ -153,7 +333,50 @@ pub async fn handle_update_spot(
         spot_id = %updated.sdz_spot_id,
         "spot updated"
     );
-    Ok::<_, SdzApiError>((StatusCode::OK, Json(updated)))
+    Ok::<_, SdzApiError>((
+        StatusCode::OK,
+        [(header::ETAG, updated.etag())],
+        Json(updated),
+    ))
+}
+
+pub async fn handle_delete_spot(
+    State(state): State<SdzAppState>,
+    Path(spot_id): Path<String>,
+    auth_user: SdzAuthUser,
+    client_app: SdzClientApp,
+) -> impl IntoResponse {
+    if !client_app.is_mobile() {
+        return Err(SdzApiError::Forbidden("mobile client required".to_string()));
+    }
+    tracing::info!(
+        event_code = "SDZ-API-2005",
+        component = "presentation",
+        user_id = %auth_user.sdz_user_id,
+        spot_id = %spot_id,
+        "delete spot requested"
+    );
+    let use_case = SdzDeleteSpotUseCase::new();
+    let deleted = use_case
+        .execute(
+            state.spot_repo.clone(),
+            state.mylist_repo.clone(),
+            state.storage_repo.clone(),
+            state.revision_repo.clone(),
+            auth_user,
+            spot_id,
+        )
+        .await?;
+    tracing::info!(
+        event_code = "SDZ-API-2006",
+        component = "presentation",
+        spot_id = %deleted.sdz_spot_id,
+        removed_mylist_entries = deleted.removed_mylist_entries,
+        deleted_images = deleted.deleted_images,
+        deleted_revisions = deleted.deleted_revisions,
+        "spot deleted"
+    );
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(deleted)))
 }
 
 pub async fn handle_admin_create_spot(
@@ -175,7 +398,7 @@ pub async fn handle_admin_create_spot(
         sdz_user_id: admin_user.sdz_user_id,
     };
 
-    let use_case = SdzCreateSpotUseCase::new();
+    let use_case = SdzCreateSpotUseCase::for_admin();
     let created = use_case
         .execute(state.spot_repo.clone(), auth_user, payload)
         .await?;
@@ -193,6 +416,7 @@ pub async fn handle_admin_update_spot(
     State(state): State<SdzAppState>,
     admin_user: SdzAdminUser,
     Path(spot_id): Path<String>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateSpotInput>,
 ) -> impl IntoResponse {
     tracing::info!(
@@ -203,9 +427,16 @@ pub async fn handle_admin_update_spot(
         "admin update spot requested"
     );
 
-    let use_case = SdzUpdateSpotUseCase::new();
+    let use_case = SdzUpdateSpotUseCase::for_admin();
     let updated = use_case
-        .execute(state.spot_repo.clone(), spot_id, payload)
+        .execute(
+            state.spot_repo.clone(),
+            state.revision_repo.clone(),
+            &admin_user.sdz_user_id,
+            spot_id,
+            payload,
+            if_match_header(&headers)?,
+        )
         .await?;
 
     tracing::info!(
@@ -214,7 +445,263 @@ pub async fn handle_admin_update_spot(
         spot_id = %updated.sdz_spot_id,
         "admin spot updated"
     );
-    Ok::<_, SdzApiError>((StatusCode::OK, Json(updated)))
+    Ok::<_, SdzApiError>((
+        StatusCode::OK,
+        [(header::ETAG, updated.etag())],
+        Json(updated),
+    ))
+}
+
+pub async fn handle_admin_delete_spot(
+    State(state): State<SdzAppState>,
+    admin_user: SdzAdminUser,
+    Path(spot_id): Path<String>,
+) -> impl IntoResponse {
+    tracing::info!(
+        event_code = "SDZ-API-3005",
+        component = "presentation",
+        user_id = %admin_user.sdz_user_id,
+        spot_id = %spot_id,
+        "admin delete spot requested"
+    );
+
+    let auth_user = SdzAuthUser {
+        sdz_user_id: admin_user.sdz_user_id,
+    };
+
+    let use_case = SdzDeleteSpotUseCase::for_admin();
+    let deleted = use_case
+        .execute(
+            state.spot_repo.clone(),
+            state.mylist_repo.clone(),
+            state.storage_repo.clone(),
+            state.revision_repo.clone(),
+            auth_user,
+            spot_id,
+        )
+        .await?;
+
+    tracing::info!(
+        event_code = "SDZ-API-3006",
+        component = "presentation",
+        spot_id = %deleted.sdz_spot_id,
+        removed_mylist_entries = deleted.removed_mylist_entries,
+        deleted_images = deleted.deleted_images,
+        deleted_revisions = deleted.deleted_revisions,
+        "admin spot deleted"
+    );
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(deleted)))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SdzAdminListArchivedQuery {
+    pub limit: Option<usize>,
+    #[serde(rename = "pageToken")]
+    pub page_token: Option<String>,
+}
+
+pub async fn handle_admin_list_archived_spots(
+    State(state): State<SdzAppState>,
+    _admin_user: SdzAdminUser,
+    Query(params): Query<SdzAdminListArchivedQuery>,
+) -> impl IntoResponse {
+    let page = SdzPageRequest::from_query(params.limit, params.page_token.as_deref())?;
+    let use_case = SdzListArchivedSpotsUseCase::new();
+    let spots = use_case.execute(state.spot_repo.clone(), page).await?;
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(spots)))
+}
+
+pub async fn handle_admin_archive_spot(
+    State(state): State<SdzAppState>,
+    admin_user: SdzAdminUser,
+    Path(spot_id): Path<String>,
+) -> impl IntoResponse {
+    tracing::info!(
+        event_code = "SDZ-API-3007",
+        component = "presentation",
+        user_id = %admin_user.sdz_user_id,
+        spot_id = %spot_id,
+        "admin archive spot requested"
+    );
+
+    let use_case = SdzArchiveSpotUseCase::new();
+    let archived = use_case
+        .execute(state.spot_repo.clone(), &admin_user.sdz_user_id, spot_id)
+        .await?;
+
+    tracing::info!(
+        event_code = "SDZ-API-3008",
+        component = "presentation",
+        spot_id = %archived.sdz_spot_id,
+        "admin spot archived"
+    );
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(archived)))
+}
+
+pub async fn handle_admin_restore_spot(
+    State(state): State<SdzAppState>,
+    admin_user: SdzAdminUser,
+    Path(spot_id): Path<String>,
+) -> impl IntoResponse {
+    tracing::info!(
+        event_code = "SDZ-API-3009",
+        component = "presentation",
+        user_id = %admin_user.sdz_user_id,
+        spot_id = %spot_id,
+        "admin restore spot requested"
+    );
+
+    let use_case = SdzRestoreSpotUseCase::new();
+    let restored = use_case.execute(state.spot_repo.clone(), spot_id).await?;
+
+    tracing::info!(
+        event_code = "SDZ-API-3010",
+        component = "presentation",
+        spot_id = %restored.sdz_spot_id,
+        "admin spot restored"
+    );
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(restored)))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SdzAdminListRevisionsQuery {
+    pub limit: Option<usize>,
+    #[serde(rename = "pageToken")]
+    pub page_token: Option<String>,
+}
+
+pub async fn handle_admin_list_spot_revisions(
+    State(state): State<SdzAppState>,
+    _admin_user: SdzAdminUser,
+    Path(spot_id): Path<String>,
+    Query(params): Query<SdzAdminListRevisionsQuery>,
+) -> impl IntoResponse {
+    let page = SdzPageRequest::from_query(params.limit, params.page_token.as_deref())?;
+    let use_case = SdzListSpotRevisionsUseCase::new();
+    let revisions = use_case
+        .execute(
+            state.spot_repo.clone(),
+            state.revision_repo.clone(),
+            spot_id,
+            page,
+        )
+        .await?;
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(revisions)))
+}
+
+pub async fn handle_admin_revert_spot(
+    State(state): State<SdzAppState>,
+    admin_user: SdzAdminUser,
+    Path((spot_id, revision_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    tracing::info!(
+        event_code = "SDZ-API-3011",
+        component = "presentation",
+        user_id = %admin_user.sdz_user_id,
+        spot_id = %spot_id,
+        revision_id = %revision_id,
+        "admin revert spot requested"
+    );
+
+    let use_case = SdzRevertSpotUseCase::new();
+    let reverted = use_case
+        .execute(
+            state.spot_repo.clone(),
+            state.revision_repo.clone(),
+            &admin_user.sdz_user_id,
+            spot_id,
+            &revision_id,
+        )
+        .await?;
+
+    tracing::info!(
+        event_code = "SDZ-API-3012",
+        component = "presentation",
+        spot_id = %reverted.sdz_spot_id,
+        "admin spot reverted"
+    );
+    Ok::<_, SdzApiError>((
+        StatusCode::OK,
+        [(header::ETAG, reverted.etag())],
+        Json(reverted),
+    ))
+}
+
+/// 一括登録の受付上限（バイト）。通常のJSONリクエストより大きい本文を許可する
+pub const SDZ_BULK_MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// NDJSONでスポットを一括登録し、1行ごとの結果をNDJSONで順次返す
+pub async fn handle_admin_bulk_upsert_spots(
+    State(state): State<SdzAppState>,
+    admin_user: SdzAdminUser,
+    body: String,
+) -> impl IntoResponse {
+    tracing::info!(
+        event_code = "SDZ-API-3013",
+        component = "presentation",
+        user_id = %admin_user.sdz_user_id,
+        "admin bulk upsert spots requested"
+    );
+
+    let use_case = SdzBulkUpsertSpotsUseCase::new();
+    let lines = use_case.parse(&body)?;
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        let summary = use_case
+            .execute(
+                state.spot_repo.clone(),
+                state.revision_repo.clone(),
+                &admin_user.sdz_user_id,
+                lines,
+                tx,
+            )
+            .await;
+        tracing::info!(
+            event_code = "SDZ-API-3014",
+            component = "presentation",
+            created = summary.created,
+            updated = summary.updated,
+            invalid = summary.invalid,
+            failed = summary.failed,
+            "admin bulk upsert spots finished"
+        );
+    });
+
+    let stream = ReceiverStream::new(rx).map(|result| {
+        let mut line = serde_json::to_string(&result).unwrap_or_default();
+        line.push('\n');
+        Ok::<_, Infallible>(line)
+    });
+    Ok::<_, SdzApiError>((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(stream),
+    ))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SdzAdminDuplicatesQuery {
+    pub threshold: Option<f64>,
+}
+
+pub async fn handle_admin_list_duplicate_spots(
+    State(state): State<SdzAppState>,
+    admin_user: SdzAdminUser,
+    Query(params): Query<SdzAdminDuplicatesQuery>,
+) -> impl IntoResponse {
+    let threshold = params.threshold.unwrap_or(SDZ_DEDUP_DEFAULT_THRESHOLD);
+    let use_case = SdzFindDuplicateSpotsUseCase::new();
+    let report = use_case.execute(state.spot_repo.clone(), threshold).await?;
+    tracing::info!(
+        event_code = "SDZ-API-3015",
+        component = "presentation",
+        user_id = %admin_user.sdz_user_id,
+        threshold = threshold,
+        pairs = report.pairs.len(),
+        truncated = report.truncated,
+        "admin duplicate spots listed"
+    );
+    Ok::<_, SdzApiError>((StatusCode::OK, Json(report)))
 }
 
 pub async fn handle_admin_create_upload_url(
@@ -248,3 +735,21 @@ pub async fn handle_admin_create_upload_url(
 
     Ok::<_, SdzApiError>((StatusCode::OK, Json(result)))
 }
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// If-Matchヘッダー（任意）。指定がなければ無条件に更新する
+fn if_match_header(headers: &HeaderMap) -> Result<Option<&str>, SdzApiError> {
+    headers
+        .get(header::IF_MATCH)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| SdzApiError::BadRequest("invalid If-Match header".into()))
+        })
+        .transpose()
+}
@@ -1,4 +1,6 @@
-use axum::{body::Body, http::Request, routing::delete, routing::get, Router};
+use axum::{
+    body::Body, extract::DefaultBodyLimit, http::Request, routing::delete, routing::get, Router,
+};
 use http::{HeaderValue, Method};
 use std::time::Duration;
 use tower_http::{
@@ -12,15 +14,20 @@ use std::sync::Arc;
 use crate::{
     application::use_cases::{
         mylist_repository::SdzMyListRepository, spot_repository::SdzSpotRepository,
+        spot_revision_repository::SdzSpotRevisionRepository,
         storage_repository::SdzStorageRepository, user_repository::SdzUserRepository,
     },
     domain::models::SdzUser,
     infrastructure::{
+        firestore_client::SdzFirestoreClient,
+        firestore_endpoint::sdz_firestore_emulator_configured,
         firestore_mylist_repository::SdzFirestoreMyListRepository,
         firestore_spot_repository::SdzFirestoreSpotRepository,
+        firestore_spot_revision_repository::SdzFirestoreSpotRevisionRepository,
         firestore_user_repository::SdzFirestoreUserRepository,
         in_memory_mylist_repository::SdzInMemoryMyListRepository,
         in_memory_spot_repository::SdzInMemorySpotRepository,
+        in_memory_spot_revision_repository::SdzInMemorySpotRevisionRepository,
         in_memory_user_repository::SdzInMemoryUserRepository,
         storage_disabled_repository::SdzDisabledStorageRepository,
         storage_signed_url_repository::SdzStorageSignedUrlRepository,
@@ -44,9 +51,20 @@ pub fn sdz_build_router() -> Router {
             axum::routing::post(spot_handler::handle_create_upload_url),
         )
         .route("/sdz/spots", get(spot_handler::handle_list_spots))
+        .route("/sdz/spots/facets", get(spot_handler::handle_spot_facets))
+        .route(
+            "/sdz/spots.geojson",
+            get(spot_handler::handle_export_spots_geojson),
+        )
+        .route(
+            "/sdz/spots/clusters",
+            get(spot_handler::handle_cluster_spots),
+        )
         .route(
             "/sdz/spots/{spot_id}",
-            get(spot_handler::handle_get_spot).patch(spot_handler::handle_update_spot),
+            get(spot_handler::handle_get_spot)
+                .patch(spot_handler::handle_update_spot)
+                .delete(spot_handler::handle_delete_spot),
         )
         .route(
             "/sdz/admin/spots",
@@ -54,7 +72,37 @@ pub fn sdz_build_router() -> Router {
         )
         .route(
             "/sdz/admin/spots/{spot_id}",
-            axum::routing::patch(spot_handler::handle_admin_update_spot),
+            axum::routing::patch(spot_handler::handle_admin_update_spot)
+                .delete(spot_handler::handle_admin_delete_spot),
+        )
+        .route(
+            "/sdz/admin/spots:bulk",
+            axum::routing::post(spot_handler::handle_admin_bulk_upsert_spots)
+                .layer(DefaultBodyLimit::max(spot_handler::SDZ_BULK_MAX_BODY_BYTES)),
+        )
+        .route(
+            "/sdz/admin/spots/archived",
+            get(spot_handler::handle_admin_list_archived_spots),
+        )
+        .route(
+            "/sdz/admin/spots/duplicates",
+            get(spot_handler::handle_admin_list_duplicate_spots),
+        )
+        .route(
+            "/sdz/admin/spots/{spot_id}/archive",
+            axum::routing::post(spot_handler::handle_admin_archive_spot),
+        )
+        .route(
+            "/sdz/admin/spots/{spot_id}/restore",
+            axum::routing::post(spot_handler::handle_admin_restore_spot),
+        )
+        .route(
+            "/sdz/admin/spots/{spot_id}/revisions",
+            get(spot_handler::handle_admin_list_spot_revisions),
+        )
+        .route(
+            "/sdz/admin/spots/{spot_id}/revisions/{revision_id}/revert",
+            axum::routing::post(spot_handler::handle_admin_revert_spot),
         )
         .route(
             "/sdz/admin/spots/upload-url",
@@ -64,6 +112,10 @@ pub fn sdz_build_router() -> Router {
             "/sdz/mylist",
             get(mylist_handler::handle_list_mylist).post(mylist_handler::handle_add_mylist),
         )
+        .route(
+            "/sdz/mylist/export",
+            get(mylist_handler::handle_export_mylist),
+        )
         .route(
             "/sdz/mylist/{spot_id}",
             delete(mylist_handler::handle_remove_mylist),
@@ -123,6 +175,7 @@ fn cors_layer() -> CorsLayer {
         .allow_origin(allowed_origins())
         .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE])
         .allow_headers(tower_http::cors::Any)
+        .expose_headers([http::header::ETAG, http::header::CONTENT_DISPOSITION])
 }
 
 fn allowed_origins() -> AllowOrigin {
@@ -162,24 +215,24 @@ fn build_state() -> SdzAppState {
         {
             let token = std::env::var("SDZ_FIRESTORE_TOKEN").ok();
             let on_cloud_run = std::env::var("K_SERVICE").is_ok();
-            if token.is_some() || on_cloud_run {
-                if let (Ok(user_repo), Ok(spot_repo), Ok(mylist_repo)) = (
-                    SdzFirestoreUserRepository::new(project_id.clone(), token.clone()),
-                    SdzFirestoreSpotRepository::new(project_id.clone(), token.clone()),
-                    SdzFirestoreMyListRepository::new(project_id.clone(), token.clone()),
-                ) {
+            // エミュレーター接続時はトークン不要
+            if token.is_some() || on_cloud_run || sdz_firestore_emulator_configured() {
+                // トークンキャッシュとコネクションプールを全リポジトリで共有する
+                if let Ok(client) = SdzFirestoreClient::new(project_id, token) {
+                    let client = Arc::new(client);
                     return SdzAppState {
-                        user_repo: Arc::new(user_repo),
-                        spot_repo: Arc::new(spot_repo),
-                        mylist_repo: Arc::new(mylist_repo),
+                        user_repo: Arc::new(SdzFirestoreUserRepository::new(client.clone())),
+                        spot_repo: Arc::new(SdzFirestoreSpotRepository::new(client.clone())),
+                        mylist_repo: Arc::new(SdzFirestoreMyListRepository::new(client.clone())),
+                        revision_repo: Arc::new(SdzFirestoreSpotRevisionRepository::new(client)),
                         storage_repo,
                     };
                 } else {
-                    tracing::warn!("Failed to init Firestore repo, falling back to in-memory");
+                    tracing::warn!("Failed to init Firestore client, falling back to in-memory");
                 }
             } else {
                 tracing::warn!(
-                    "SDZ_USE_FIRESTORE=1 but token missing and not Cloud Run. Falling back to in-memory."
+                    "SDZ_USE_FIRESTORE=1 but token missing, not Cloud Run and no emulator configured. Falling back to in-memory."
                 );
             }
         } else {
@@ -200,6 +253,7 @@ fn build_state() -> SdzAppState {
         user_repo: Arc::new(repo),
         spot_repo: Arc::new(SdzInMemorySpotRepository::default()),
         mylist_repo: Arc::new(SdzInMemoryMyListRepository::default()),
+        revision_repo: Arc::new(SdzInMemorySpotRevisionRepository::default()),
         storage_repo,
     }
 }
@@ -209,6 +263,7 @@ pub struct SdzAppState {
     pub user_repo: Arc<dyn SdzUserRepository>,
     pub spot_repo: Arc<dyn SdzSpotRepository>,
     pub mylist_repo: Arc<dyn SdzMyListRepository>,
+    pub revision_repo: Arc<dyn SdzSpotRevisionRepository>,
     pub storage_repo: Arc<dyn SdzStorageRepository>,
 }
 
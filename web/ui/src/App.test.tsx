@@ -23,7 +23,7 @@ describe('App', () => {
   it('renders map heading', () => {
     globalThis.fetch = vi.fn().mockResolvedValue({
       ok: true,
-      json: async () => [],
+      json: async () => ({ items: [] }),
     } as Response);
     render(
       <MemoryRouter>
@@ -38,21 +38,23 @@ describe('App', () => {
   it('toggles favorites in list', async () => {
     globalThis.fetch = vi.fn().mockResolvedValue({
       ok: true,
-      json: async () => [
-        {
-          spotId: 'spot-1',
-          name: 'Favorite Spot',
-          description: null,
-          location: { lat: 35.0, lng: 139.0 },
-          tags: ['smoke'],
-          images: [],
-          trustLevel: 'unverified',
-          trustSources: [],
-          userId: 'user-1',
-          createdAt: '2025-01-01T00:00:00Z',
-          updatedAt: '2025-01-01T00:00:00Z',
-        },
-      ],
+      json: async () => ({
+        items: [
+          {
+            spotId: 'spot-1',
+            name: 'Favorite Spot',
+            description: null,
+            location: { lat: 35.0, lng: 139.0 },
+            tags: ['smoke'],
+            images: [],
+            trustLevel: 'unverified',
+            trustSources: [],
+            userId: 'user-1',
+            createdAt: '2025-01-01T00:00:00Z',
+            updatedAt: '2025-01-01T00:00:00Z',
+          },
+        ],
+      }),
     } as Response);
 
     render(
@@ -73,21 +75,23 @@ describe('App', () => {
   it('shows favorites page', async () => {
     globalThis.fetch = vi.fn().mockResolvedValue({
       ok: true,
-      json: async () => [
-        {
-          spotId: 'spot-1',
-          name: 'Favorite Spot',
-          description: null,
-          location: { lat: 35.0, lng: 139.0 },
-          tags: ['smoke'],
-          images: [],
-          trustLevel: 'unverified',
-          trustSources: [],
-          userId: 'user-1',
-          createdAt: '2025-01-01T00:00:00Z',
-          updatedAt: '2025-01-01T00:00:00Z',
-        },
-      ],
+      json: async () => ({
+        items: [
+          {
+            spotId: 'spot-1',
+            name: 'Favorite Spot',
+            description: null,
+            location: { lat: 35.0, lng: 139.0 },
+            tags: ['smoke'],
+            images: [],
+            trustLevel: 'unverified',
+            trustSources: [],
+            userId: 'user-1',
+            createdAt: '2025-01-01T00:00:00Z',
+            updatedAt: '2025-01-01T00:00:00Z',
+          },
+        ],
+      }),
     } as Response);
 
     window.localStorage.setItem('sdzFavorites', JSON.stringify(['spot-1']));
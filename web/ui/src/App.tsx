@@ -1,5 +1,5 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react';
-import type { SdzSpot } from './types/spot';
+import type { SdzPage, SdzSpot } from './types/spot';
 import { useAuth } from './contexts/useAuth';
 import { Route, Routes, useNavigate, useParams } from 'react-router-dom';
 import { CircleMarker, MapContainer, TileLayer, Tooltip, useMap } from 'react-leaflet';
@@ -435,8 +435,8 @@ function App() {
         if (!res.ok) {
           throw new Error(`Failed to fetch spots: ${res.status}`);
         }
-        const data: SdzSpot[] = await res.json();
-        setSpots(data);
+        const data: SdzPage<SdzSpot> = await res.json();
+        setSpots(data.items);
       } catch (err) {
         if (err instanceof Error && err.name === 'AbortError') return;
         setError((err as Error).message);
@@ -1,19 +1,73 @@
 import { useCallback, useEffect, useState } from 'react';
 import { useNavigate, useParams } from 'react-router-dom';
 import { useAuth } from '../contexts/useAuth';
-import type { SdzSpot, SdzPlaceResult } from '../types/spot';
+import type { SdzSpot, SdzSpotParkAttributes, SdzPlaceResult } from '../types/spot';
 import {
   sdzAdminCreateSpot,
   sdzAdminUpdateSpot,
   sdzAdminGetUploadUrl,
   sdzAdminUploadImage,
 } from '../lib/SdzAdminApi';
+import type { SdzSpotClearableField } from '../lib/SdzAdminApi';
 import { SdzAdminMapPicker } from './SdzAdminMapPicker';
 
 const sdzApiUrl = import.meta.env.VITE_SDZ_API_URL || 'http://localhost:8080';
 
 const SDZ_ALLOWED_URL_SCHEMES = ['https:', 'http:'];
 
+// フォームで編集できる任意項目（フォームにない項目は削除対象にしない）。
+// 読み込み時に値があり、フォームで空にした項目だけを削除する
+const sdzFormOptionalFields = [
+  'description',
+  'location',
+  'parkAttributes',
+  'instagramLocationUrl',
+  'googlePlaceId',
+  'googleMapsUrl',
+  'address',
+  'phoneNumber',
+  'googleRating',
+  'googleRatingCount',
+] as const satisfies readonly SdzSpotClearableField[];
+
+interface SdzParkForm {
+  officialUrl: string;
+  businessHoursNote: string;
+  accessInfo: string;
+  phoneNumber: string | undefined;
+}
+
+// パーク属性は丸ごと置き換わるため、保存済みの値にフォームの入力を重ねる。
+// フォームで扱えない値（構造化した営業時間など）はそのまま残す
+function sdzMergeParkAttributes(
+  loaded: SdzSpotParkAttributes | undefined,
+  form: SdzParkForm,
+): SdzSpotParkAttributes | undefined {
+  let businessHours = loaded?.businessHours;
+  if (form.businessHoursNote !== (businessHours?.note ?? '')) {
+    if (businessHours && businessHours.scheduleType !== 'manual') {
+      businessHours = { ...businessHours, note: form.businessHoursNote || undefined };
+    } else if (form.businessHoursNote) {
+      businessHours = {
+        scheduleType: 'manual',
+        is24Hours: false,
+        sameAsWeekday: false,
+        note: form.businessHoursNote,
+      };
+    } else {
+      businessHours = undefined;
+    }
+  }
+  const merged: SdzSpotParkAttributes = {
+    ...loaded,
+    officialUrl: form.officialUrl || undefined,
+    businessHours,
+    accessInfo: form.accessInfo || undefined,
+    phoneNumber: form.phoneNumber,
+  };
+  return Object.values(merged).some((value) => value !== undefined) ? merged : undefined;
+}
+
 function sdzValidateUrl(value: string): string | null {
   if (!value) return null;
   try {
@@ -137,6 +191,7 @@ export function SdzAdminSpotForm() {
   const [sdzSuccess, setSdzSuccess] = useState<string | null>(null);
   const [sdzSavedFields, setSdzSavedFields] = useState<SdzSavedField[]>([]);
   const [sdzLoadingSpot, setSdzLoadingSpot] = useState(false);
+  const [sdzLoadedSpot, setSdzLoadedSpot] = useState<SdzSpot | null>(null);
 
   const handleLocationChange = useCallback((lat: string, lng: string) => {
     setSdzLat(lat);
@@ -170,6 +225,7 @@ export function SdzAdminSpotForm() {
         return res.json() as Promise<SdzSpot>;
       })
       .then((spot) => {
+        setSdzLoadedSpot(spot);
         setSdzName(spot.name);
         setSdzDescription(spot.description ?? '');
         setSdzLat(spot.location?.lat?.toString() ?? '');
@@ -256,23 +312,19 @@ export function SdzAdminSpotForm() {
         ? { lat: parsedLat, lng: parsedLng }
         : undefined;
 
-    const hasParkData = sdzOfficialUrl || sdzBusinessHoursNote || sdzAccessInfo || sdzPhoneNumber;
+    // 編集時の電話番号欄はトップレベルのphoneNumberを読み込んでいるため、そこへ書き戻す。
+    // パーク属性側の電話番号はフォームに出していないので保存済みの値を残す
+    const loaded = isEdit ? sdzLoadedSpot : null;
     const parkAttributes =
-      sdzSpotType === 'park' && hasParkData
-        ? {
-            officialUrl: sdzOfficialUrl || undefined,
-            businessHours: sdzBusinessHoursNote
-              ? {
-                  scheduleType: 'manual' as const,
-                  is24Hours: false,
-                  sameAsWeekday: false,
-                  note: sdzBusinessHoursNote,
-                }
-              : undefined,
-            accessInfo: sdzAccessInfo || undefined,
-            phoneNumber: sdzPhoneNumber || undefined,
-          }
+      sdzSpotType === 'park'
+        ? sdzMergeParkAttributes(loaded?.parkAttributes, {
+            officialUrl: sdzOfficialUrl,
+            businessHoursNote: sdzBusinessHoursNote,
+            accessInfo: sdzAccessInfo,
+            phoneNumber: isEdit ? loaded?.parkAttributes?.phoneNumber : sdzPhoneNumber || undefined,
+          })
         : undefined;
+    const phoneNumber = isEdit || !parkAttributes ? sdzPhoneNumber || undefined : undefined;
 
     const payload = {
       name: sdzName,
@@ -285,7 +337,7 @@ export function SdzAdminSpotForm() {
       googlePlaceId: sdzGooglePlaceId || undefined,
       googleMapsUrl: sdzGoogleMapsUrl || undefined,
       address: sdzAddress || undefined,
-      phoneNumber: !parkAttributes ? sdzPhoneNumber || undefined : undefined,
+      phoneNumber,
       googleRating: sdzGoogleRating ? parseFloat(sdzGoogleRating) : undefined,
       googleRatingCount: sdzGoogleRatingCount ? parseInt(sdzGoogleRatingCount) : undefined,
       googleTypes: sdzGoogleTypes.length > 0 ? sdzGoogleTypes : undefined,
@@ -293,7 +345,11 @@ export function SdzAdminSpotForm() {
 
     try {
       if (isEdit) {
-        await sdzAdminUpdateSpot(idToken, id, payload);
+        // 未指定は「変更なし」扱いのため、読み込み時に値があってフォームで空にした項目だけ削除を明示する
+        const clearFields = sdzFormOptionalFields.filter(
+          (field) => loaded?.[field] != null && payload[field] === undefined,
+        );
+        await sdzAdminUpdateSpot(idToken, id, { ...payload, clearFields });
         setSdzSuccess('スポットを更新しました');
       } else {
         await sdzAdminCreateSpot(idToken, payload);
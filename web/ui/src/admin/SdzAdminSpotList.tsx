@@ -1,6 +1,6 @@
 import { useEffect, useState } from 'react';
 import { Link } from 'react-router-dom';
-import type { SdzSpot } from '../types/spot';
+import type { SdzPage, SdzSpot } from '../types/spot';
 
 const sdzApiUrl = import.meta.env.VITE_SDZ_API_URL || 'http://localhost:8080';
 
@@ -14,9 +14,9 @@ export function SdzAdminSpotList() {
     fetch(`${sdzApiUrl}/sdz/spots`)
       .then((res) => {
         if (!res.ok) throw new Error(`HTTP ${res.status}`);
-        return res.json() as Promise<SdzSpot[]>;
+        return res.json() as Promise<SdzPage<SdzSpot>>;
       })
-      .then(setSdzSpots)
+      .then((page) => setSdzSpots(page.items))
       .catch((err) => setSdzError((err as Error).message))
       .finally(() => setSdzLoading(false));
   }, []);
@@ -22,6 +22,23 @@ export interface SdzSpotPayload {
   googleTypes?: string[];
 }
 
+/** 更新時に明示的に削除できる任意項目 */
+export type SdzSpotClearableField =
+  | 'description'
+  | 'location'
+  | 'approvalStatus'
+  | 'parkAttributes'
+  | 'streetAttributes'
+  | 'instagramTag'
+  | 'instagramLocationUrl'
+  | 'instagramProfileUrl'
+  | 'googlePlaceId'
+  | 'googleMapsUrl'
+  | 'address'
+  | 'phoneNumber'
+  | 'googleRating'
+  | 'googleRatingCount';
+
 async function sdzAdminFetch(
   path: string,
   idToken: string,
@@ -52,7 +69,7 @@ export async function sdzAdminCreateSpot(idToken: string, payload: SdzSpotPayloa
 export async function sdzAdminUpdateSpot(
   idToken: string,
   spotId: string,
-  payload: Partial<SdzSpotPayload>,
+  payload: Partial<SdzSpotPayload> & { clearFields?: SdzSpotClearableField[] },
 ): Promise<void> {
   await sdzAdminFetch(`/sdz/admin/spots/${spotId}`, idToken, {
     method: 'PATCH',
@@ -83,4 +83,16 @@ export interface SdzSpot {
   userId: string;
   createdAt: string;
   updatedAt: string;
+  /** アーカイブ済みのみ（管理画面） */
+  deletedAt?: string;
+  deletedBy?: string;
+  /** 一覧・詳細の閲覧時に付与される。null は判定不能 */
+  distanceM?: number;
+  isOpenNow?: boolean | null;
+  nextChangeAt?: string;
+}
+
+export interface SdzPage<T> {
+  items: T[];
+  nextPageToken?: string;
 }